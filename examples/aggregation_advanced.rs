@@ -82,7 +82,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Query: Active categories with total amount > 500");
 
     let sql = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .having("total > {}", &[&500i64])
@@ -217,7 +217,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Query: Active categories, total > 1000, sorted DESC, top 3");
 
     let sql = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .avg_as("amount", "average")
@@ -376,7 +376,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Query: WHERE + HAVING + LIMIT + OFFSET");
 
     let sql = Order::agg_query()
-        .where_("status = {} AND amount > {}", &["active", "100"])  // $1, $2
+        .where_("status = {} AND amount > {}", &[&"active", &100i64])  // $1, $2
         .group_by("category")
         .sum_as("amount", "total")
         .having("total > {}", &[&500i64])  // $3
@@ -204,7 +204,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Query: Total amount for active orders");
 
     let sql = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .sum("amount")
         .build();
 
@@ -224,7 +224,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Query: Total amount per category for active orders");
 
     let sql = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .build();
@@ -247,7 +247,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("    High-value customers (completed orders > $100, with > 5 orders)\n");
 
     let vip_customers: Vec<(String, i64, Option<f64>)> = Order::agg_query()
-        .where_("status = {} AND amount > {}", &["completed", "100.0"])
+        .where_("status = {} AND amount > {}", &[&"completed", &100.0f64])
         .group_by("customer_id")
         .count()
         .sum("amount")
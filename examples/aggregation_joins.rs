@@ -94,7 +94,7 @@ async fn example_1_basic_join(pool: &PgPool) -> Result<(), Box<dyn std::error::E
     // Join orders with customers to get regional sales
     let sql = Order::agg_query()
         .join("customers", "orders.customer_id = customers.id")
-        .where_("customers.status = {} AND orders.status = {}", &["active", "completed"])
+        .where_("customers.status = {} AND orders.status = {}", &[&"active", &"completed"])
         .group_by("customers.region")
         .sum_as("orders.amount", "total_revenue")
         .count_as("order_count")
@@ -140,7 +140,7 @@ async fn example_2_left_join(pool: &PgPool) -> Result<(), Box<dyn std::error::Er
     // LEFT JOIN ensures we get all products, even those with no orders
     let sql = Order::agg_query()
         .join_left("products", "orders.product_id = products.id")
-        .where_("orders.status = {}", &["completed"])
+        .where_("orders.status = {}", &[&"completed"])
         .group_by("products.category")
         .sum_as("orders.amount", "total_revenue")
         .count_as("units_sold")
@@ -182,7 +182,7 @@ async fn example_3_multiple_joins(pool: &PgPool) -> Result<(), Box<dyn std::erro
     let sql = Order::agg_query()
         .join("customers", "orders.customer_id = customers.id")
         .join("products", "orders.product_id = products.id")
-        .where_("customers.tier = {} AND orders.status = {}", &["gold", "completed"])
+        .where_("customers.tier = {} AND orders.status = {}", &[&"gold", &"completed"])
         .group_by("customers.region")
         .group_by("products.category")
         .sum_as("orders.amount", "total_revenue")
@@ -233,7 +233,7 @@ async fn example_4_having_with_join(pool: &PgPool) -> Result<(), Box<dyn std::er
     // Analyze revenue by customer tier, filtering high-revenue tiers
     let sql = Order::agg_query()
         .join("customers", "orders.customer_id = customers.id")
-        .where_("orders.status = {}", &["completed"])
+        .where_("orders.status = {}", &[&"completed"])
         .group_by("customers.tier")
         .sum_as("orders.amount", "total_revenue")
         .count_as("customer_count")
@@ -287,7 +287,7 @@ async fn example_5_pagination(pool: &PgPool) -> Result<(), Box<dyn std::error::E
         .join("customers", "orders.customer_id = customers.id")
         .join_left("products", "orders.product_id = products.id")
         .where_("customers.status = {} AND orders.status = {} AND orders.amount > {}",
-                &["active", "completed", "100"])
+                &[&"active", &"completed", &100i64])
         .group_by("customers.region")
         .group_by("products.category")
         .sum_as("orders.amount", "total_revenue")
@@ -392,8 +392,8 @@ async fn example_7_self_join(pool: &PgPool) -> Result<(), Box<dyn std::error::Er
     // This is useful for comparing related records
     let sql = Order::agg_query()
         .join("orders AS ref_orders", "orders.id = ref_orders.id")
-        .where_("ref_orders.status = {}", &["completed"])
-        .where_("orders.status = {}", &["pending"])
+        .where_("ref_orders.status = {}", &[&"completed"])
+        .where_("orders.status = {}", &[&"pending"])
         .sum("ref_orders.amount")
         .build();
 
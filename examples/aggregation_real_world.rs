@@ -112,7 +112,7 @@ async fn sales_dashboard_example(pool: &PgPool) -> Result<(), Box<dyn std::error
 
     // Sales by completed status, grouped by category, sorted by revenue
     let sql = SalesOrder::agg_query()
-        .where_("status = {}", &["completed"])
+        .where_("status = {}", &[&"completed"])
         .group_by("product_category")
         .sum_as("amount", "total_revenue")
         .count_as("order_count")
@@ -159,7 +159,7 @@ async fn customer_leaderboard_example(pool: &PgPool) -> Result<(), Box<dyn std::
 
     // Top 10 customers by total spend
     let sql = SalesOrder::agg_query()
-        .where_("status = {}", &["completed"])
+        .where_("status = {}", &[&"completed"])
         .group_by("customer_id")
         .sum_as("amount", "total_spent")
         .count_as("order_count")
@@ -207,7 +207,7 @@ async fn regional_analysis_example(pool: &PgPool) -> Result<(), Box<dyn std::err
     // This is a simplified example - in reality you'd need more complex queries
     // or multiple queries to get the top category per region
     let sql = SalesOrder::agg_query()
-        .where_("status = {}", &["completed"])
+        .where_("status = {}", &[&"completed"])
         .group_by("region")
         .sum_as("amount", "total_revenue")
         .count_as("order_count")
@@ -248,7 +248,7 @@ async fn website_analytics_example(pool: &PgPool) -> Result<(), Box<dyn std::err
 
     // Page views by URL
     let sql = WebsiteEvent::agg_query()
-        .where_("event_type = {}", &["page_view"])
+        .where_("event_type = {}", &[&"page_view"])
         .group_by("page_url")
         .count_as("page_views")
         .order_by("page_views", "DESC")
@@ -338,7 +338,7 @@ async fn paginated_report_example(pool: &PgPool) -> Result<(), Box<dyn std::erro
     let offset = (page - 1) * per_page;
 
     let sql = SalesOrder::agg_query()
-        .where_("status = {}", &["completed"])
+        .where_("status = {}", &[&"completed"])
         .group_by("product_category")
         .sum_as("amount", "total_revenue")
         .count_as("order_count")
@@ -18,18 +18,21 @@ fn main() {
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: true,  // ✅ UUID 类型
+                is_lazy: false,
             },
             sqlx_struct_enhanced::ColumnDefinition {
                 name: "customer_name".to_string(),
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
             sqlx_struct_enhanced::ColumnDefinition {
                 name: "amount".to_string(),
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
         ],
     };
@@ -46,12 +49,14 @@ fn main() {
                 cast_as: None,
                 is_decimal: true,  // DECIMAL 类型
                 is_uuid: false,
+                is_lazy: false,
             },
             sqlx_struct_enhanced::ColumnDefinition {
                 name: "name".to_string(),
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
         ],
     };
@@ -68,18 +73,21 @@ fn main() {
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
             sqlx_struct_enhanced::ColumnDefinition {
                 name: "name".to_string(),
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
             sqlx_struct_enhanced::ColumnDefinition {
                 name: "email".to_string(),
                 cast_as: None,
                 is_decimal: false,
                 is_uuid: false,
+                is_lazy: false,
             },
         ],
     };
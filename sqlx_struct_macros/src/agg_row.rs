@@ -0,0 +1,65 @@
+//! Code generation for the `AggRow` derive macro.
+//!
+//! Each named field becomes an entry of `AggRow::FIELDS` with the cast its
+//! type needs, so that `fetch_all_as` can check the fields against the
+//! aggregation query and cast the matched expressions. The cast is inferred
+//! from the last segment of the field type, looking through `Option`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{DeriveInput, GenericArgument, PathArguments, Type};
+
+pub fn derive_agg_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new(name.span(), "AggRow can only be derived for structs")),
+    };
+    let entries = fields.iter()
+        .map(|field| {
+            let ident = field.ident.as_ref()
+                .ok_or_else(|| syn::Error::new(field.span(), "AggRow requires named fields"))?;
+            let field_name = ident.to_string().trim_start_matches("r#").to_string();
+            let cast = match cast_for(&field.ty) {
+                Some(variant) => {
+                    let variant = syn::Ident::new(variant, field.ty.span());
+                    quote! { ::std::option::Option::Some(::sqlx_struct_enhanced::aggregate::AggCast::#variant) }
+                }
+                None => quote! { ::std::option::Option::None },
+            };
+            Ok(quote! { (#field_name, #cast) })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::sqlx_struct_enhanced::aggregate::AggRow for #name #ty_generics #where_clause {
+            const FIELDS: &'static [(&'static str, ::std::option::Option<::sqlx_struct_enhanced::aggregate::AggCast>)] = &[#(#entries),*];
+        }
+    })
+}
+
+/// The `AggCast` variant for a field type, if any.
+fn cast_for(ty: &Type) -> Option<&'static str> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident == "Option" {
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return cast_for(inner);
+            }
+        }
+        return None;
+    }
+    match segment.ident.to_string().as_str() {
+        "String" => Some("Text"),
+        "i64" => Some("Integer"),
+        "f64" => Some("Float"),
+        "Decimal" => Some("Decimal"),
+        _ => None,
+    }
+}
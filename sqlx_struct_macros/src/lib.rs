@@ -7,12 +7,24 @@ mod struct_schema_parser;
 // DECIMAL 辅助方法生成模块
 mod decimal_helpers;
 
+// Projection derive (partial DTOs for select_as)
+mod projection;
+
+// AggRow derive (typed rows for fetch_all_as)
+mod agg_row;
+
+// belongs_to / has_many relation loaders
+mod relations;
+
+// descendants / ancestors walks for #[crud(tree(..))] entities
+mod tree;
+
 // Advanced SQL parser module (based on sqlparser-rs)
 mod parser;
 
 use proc_macro::TokenStream;
 use proc_macro2::{TokenStream as TokenStream2};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{parse_macro_input, DeriveInput, Ident};
 
 // Prevent simultaneous activation of multiple database features
@@ -31,6 +43,14 @@ compile_error!("Cannot enable both 'mysql' and 'sqlite' features simultaneously.
 pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident.clone();
+    let relations = match relations::extract_relations(&input) {
+        Ok(relations) => relations,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let tree_parent = match tree::extract_tree_parent(&input) {
+        Ok(parent) => parent,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     // Generate EnhancedCrud implementation
     let sql_builder = SqlBuilder::new(Schema::new(&input));
@@ -38,6 +58,12 @@ pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
     let gen_scheme_code = sql_builder.gen_scheme_code();
     let gen_fill_id = sql_builder.fill_id_param();
     let gen_join_field_extraction = sql_builder.gen_join_field_extraction(&name);
+    let column_constants = sql_builder.gen_column_constants(&name);
+    let id_column = sql_builder.id_column_const();
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    let gen_update_sql = sql_builder.gen_update_sql();
+    let entity_impl = sql_builder.gen_entity_impl(&name);
+    let foreign_key_joins = relations::gen_foreign_key_joins(&name, &input);
 
     // Extract DECIMAL fields and generate helper methods
     use decimal_helpers;
@@ -74,10 +100,12 @@ pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
         gen_scheme_code,
         gen_fill_insert_pg,
         gen_fill_update_pg,
+        gen_update_sql.clone(),
         gen_fill_id,
         gen_fill_bulk_insert_pg,
         gen_fill_bulk_update_pg,
         gen_join_field_extraction.clone(),
+        id_column.clone(),
     );
 
     #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
@@ -87,10 +115,12 @@ pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
         gen_scheme_code,
         gen_fill_insert_mysql,
         gen_fill_update_mysql,
+        gen_update_sql.clone(),
         gen_fill_id,
         gen_fill_bulk_insert_mysql,
         gen_fill_bulk_update_mysql,
         gen_join_field_extraction.clone(),
+        id_column.clone(),
     );
 
     #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
@@ -100,10 +130,12 @@ pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
         gen_scheme_code,
         gen_fill_insert_sqlite,
         gen_fill_update_sqlite,
+        gen_update_sql.clone(),
         gen_fill_id,
         gen_fill_bulk_insert_sqlite,
         gen_fill_bulk_update_sqlite,
         gen_join_field_extraction.clone(),
+        id_column.clone(),
     );
 
     #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
@@ -111,10 +143,43 @@ pub fn enhanced_crud_derive(input: TokenStream) -> TokenStream {
         compile_error!("You must enable one of the database features: postgres, mysql, or sqlite");
     };
 
+    #[cfg(feature = "postgres")]
+    let lazy_loaders = sql_builder.gen_lazy_loaders(&name, &quote!(::sqlx::Postgres));
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    let lazy_loaders = sql_builder.gen_lazy_loaders(&name, &quote!(::sqlx::MySql));
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    let lazy_loaders = sql_builder.gen_lazy_loaders(&name, &quote!(::sqlx::Sqlite));
+    #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+    let lazy_loaders = quote! {};
+
+    #[cfg(feature = "postgres")]
+    let relation_loaders = relations::gen_relation_loaders(&name, &relations, &sql_builder.scheme.id_field, &quote!(::sqlx::Postgres));
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    let relation_loaders = relations::gen_relation_loaders(&name, &relations, &sql_builder.scheme.id_field, &quote!(::sqlx::MySql));
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    let relation_loaders = relations::gen_relation_loaders(&name, &relations, &sql_builder.scheme.id_field, &quote!(::sqlx::Sqlite));
+    #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+    let relation_loaders = { let _ = &relations; quote! {} };
+
+    #[cfg(feature = "postgres")]
+    let tree_walks = tree::gen_tree_walks(&name, tree_parent.as_deref(), &quote!(::sqlx::Postgres));
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    let tree_walks = tree::gen_tree_walks(&name, tree_parent.as_deref(), &quote!(::sqlx::MySql));
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    let tree_walks = tree::gen_tree_walks(&name, tree_parent.as_deref(), &quote!(::sqlx::Sqlite));
+    #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+    let tree_walks = { let _ = &tree_parent; quote! {} };
+
     // Combine EnhancedCrud impl with DECIMAL helpers impl
     let output_token = quote! {
         #enhanced_crud_impl
         #decimal_helpers_impl
+        #column_constants
+        #lazy_loaders
+        #entity_impl
+        #relation_loaders
+        #tree_walks
+        #foreign_key_joins
     };
 
     output_token.into()
@@ -128,10 +193,12 @@ fn postgres_impl(
     gen_scheme_code: TokenStream2,
     gen_fill_insert: TokenStream2,
     gen_fill_update: TokenStream2,
+    gen_update_sql: TokenStream2,
     gen_fill_id: TokenStream2,
     gen_fill_bulk_insert: TokenStream2,
     gen_fill_bulk_update: TokenStream2,
     gen_join_field_extraction: TokenStream2,
+    id_column: Ident,
 ) -> TokenStream2 {
     quote! {
         impl EnhancedCrud for #name {
@@ -146,7 +213,7 @@ fn postgres_impl(
             }
             fn update_bind(&mut self) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> {
                 #gen_scheme_code
-                let sql = scheme.gen_update_by_id_sql_static();
+                #gen_update_sql
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] UPDATE SQL: {}", sql);
                 let query = sqlx::query::<Postgres>(sql);
@@ -188,28 +255,40 @@ fn postgres_impl(
                 let query = sqlx::query::<Postgres>(leaked_sql);
                 query
             }
-            fn where_query(statement: &str) -> QueryAs<'_, Postgres, Self, <Postgres as HasArguments<'_>>::Arguments>{
+            fn where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Postgres>>(statement: W) -> QueryAs<'q, Postgres, Self, <Postgres as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_select_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_select_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] WHERE QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<Postgres, Self>(sql);
+                let mut query = sqlx::query_as::<Postgres, Self>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn count_query(statement: &str) -> QueryAs<'_, Postgres, (i64,), <Postgres as HasArguments<'_>>::Arguments>{
+            fn count_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Postgres>>(statement: W) -> QueryAs<'q, Postgres, (i64,), <Postgres as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_count_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_count_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] COUNT QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<Postgres, (i64,)>(sql);
+                let mut query = sqlx::query_as::<Postgres, (i64,)>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn delete_where_query(statement: &str) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments>{
+            fn delete_where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Postgres>>(statement: W) -> Query<'q, Postgres, <Postgres as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_delete_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_delete_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] DELETE WHERE SQL: {}", sql);
-                let query = sqlx::query::<Postgres>(sql);
+                let mut query = sqlx::query::<Postgres>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
             fn bulk_delete(ids: &[String]) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized {
@@ -253,6 +332,26 @@ fn postgres_impl(
             fn agg_query() -> ::sqlx_struct_enhanced::aggregate::AggQueryBuilder<'static, Postgres> where Self: Sized {
                 ::sqlx_struct_enhanced::aggregate::AggQueryBuilder::new(#table_name.to_string())
             }
+            fn paginate<W: ::sqlx_struct_enhanced::filter::IntoWhere<Postgres>>(filter: W, page: u64, per_page: u64) -> ::sqlx_struct_enhanced::pagination::Paginate<Self, Postgres> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Paginate::new(scheme, filter, page, per_page)
+            }
+            fn after<C: Into<Option<::sqlx_struct_enhanced::pagination::Cursor>>>(cursor: C) -> ::sqlx_struct_enhanced::pagination::Keyset<Self, Postgres> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Keyset::new(scheme, Self::#id_column, cursor.into())
+            }
+            fn chunked(chunk_size: u64) -> ::sqlx_struct_enhanced::pagination::Chunked<Self, Postgres> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Chunked::new(scheme, Self::#id_column, chunk_size)
+            }
+            fn select() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<Self, Postgres> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::new(scheme)
+            }
+            fn select_as<P: ::sqlx_struct_enhanced::select::Projection<Entity = Self>>() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<P, Postgres> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::projected(scheme, P::COLUMNS)
+            }
 
             #[cfg(feature = "join_queries")]
             fn join_inner<T>(condition: &str) -> ::sqlx_struct_enhanced::join::JoinQueryBuilder<'static, Self, T, Postgres>
@@ -319,7 +418,7 @@ fn postgres_impl(
             }
 
             #[cfg(feature = "postgres")]
-            fn decode_from_qualified_row_pg(row: &::sqlx::postgres::PgRow) -> Result<Option<Self>, ::sqlx::Error>
+            fn decode_from_qualified_row_pg(row: &::sqlx::postgres::PgRow, qualifier: &str) -> Result<Option<Self>, ::sqlx::Error>
             where
                 Self: Sized
             {
@@ -337,10 +436,12 @@ fn mysql_impl(
     gen_scheme_code: TokenStream2,
     gen_fill_insert: TokenStream2,
     gen_fill_update: TokenStream2,
+    gen_update_sql: TokenStream2,
     gen_fill_id: TokenStream2,
     gen_fill_bulk_insert: TokenStream2,
     gen_fill_bulk_update: TokenStream2,
     gen_join_field_extraction: TokenStream2,
+    id_column: Ident,
 ) -> TokenStream2 {
     quote! {
         impl EnhancedCrud for #name {
@@ -355,7 +456,7 @@ fn mysql_impl(
             }
             fn update_bind(&mut self) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> {
                 #gen_scheme_code
-                let sql = scheme.gen_update_by_id_sql_static();
+                #gen_update_sql
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] UPDATE SQL: {}", sql);
                 let query = sqlx::query::<MySql>(sql);
@@ -397,28 +498,40 @@ fn mysql_impl(
                 let query = sqlx::query::<MySql>(leaked_sql);
                 query
             }
-            fn where_query(statement: &str) -> QueryAs<'_, MySql, Self, <MySql as HasArguments<'_>>::Arguments>{
+            fn where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<MySql>>(statement: W) -> QueryAs<'q, MySql, Self, <MySql as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_select_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_select_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] WHERE QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<MySql, Self>(sql);
+                let mut query = sqlx::query_as::<MySql, Self>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn count_query(statement: &str) -> QueryAs<'_, MySql, (i64,), <MySql as HasArguments<'_>>::Arguments>{
+            fn count_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<MySql>>(statement: W) -> QueryAs<'q, MySql, (i64,), <MySql as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_count_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_count_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] COUNT QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<MySql, (i64,)>(sql);
+                let mut query = sqlx::query_as::<MySql, (i64,)>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn delete_where_query(statement: &str) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments>{
+            fn delete_where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<MySql>>(statement: W) -> Query<'q, MySql, <MySql as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_delete_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_delete_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] DELETE WHERE SQL: {}", sql);
-                let query = sqlx::query::<MySql>(sql);
+                let mut query = sqlx::query::<MySql>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
             fn bulk_delete(ids: &[String]) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized {
@@ -462,6 +575,26 @@ fn mysql_impl(
             fn agg_query() -> ::sqlx_struct_enhanced::aggregate::AggQueryBuilder<'static, MySql> where Self: Sized {
                 ::sqlx_struct_enhanced::aggregate::AggQueryBuilder::new(#table_name.to_string())
             }
+            fn paginate<W: ::sqlx_struct_enhanced::filter::IntoWhere<MySql>>(filter: W, page: u64, per_page: u64) -> ::sqlx_struct_enhanced::pagination::Paginate<Self, MySql> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Paginate::new(scheme, filter, page, per_page)
+            }
+            fn after<C: Into<Option<::sqlx_struct_enhanced::pagination::Cursor>>>(cursor: C) -> ::sqlx_struct_enhanced::pagination::Keyset<Self, MySql> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Keyset::new(scheme, Self::#id_column, cursor.into())
+            }
+            fn chunked(chunk_size: u64) -> ::sqlx_struct_enhanced::pagination::Chunked<Self, MySql> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Chunked::new(scheme, Self::#id_column, chunk_size)
+            }
+            fn select() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<Self, MySql> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::new(scheme)
+            }
+            fn select_as<P: ::sqlx_struct_enhanced::select::Projection<Entity = Self>>() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<P, MySql> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::projected(scheme, P::COLUMNS)
+            }
 
             #[cfg(feature = "join_queries")]
             fn join_inner<T>(condition: &str) -> ::sqlx_struct_enhanced::join::JoinQueryBuilder<'static, Self, T, MySql>
@@ -528,7 +661,7 @@ fn mysql_impl(
             }
 
             #[cfg(feature = "mysql")]
-            fn decode_from_qualified_row_mysql(row: &::sqlx::mysql::MySqlRow) -> Result<Option<Self>, ::sqlx::Error>
+            fn decode_from_qualified_row_mysql(row: &::sqlx::mysql::MySqlRow, qualifier: &str) -> Result<Option<Self>, ::sqlx::Error>
             where
                 Self: Sized
             {
@@ -546,10 +679,12 @@ fn sqlite_impl(
     gen_scheme_code: TokenStream2,
     gen_fill_insert: TokenStream2,
     gen_fill_update: TokenStream2,
+    gen_update_sql: TokenStream2,
     gen_fill_id: TokenStream2,
     gen_fill_bulk_insert: TokenStream2,
     gen_fill_bulk_update: TokenStream2,
     gen_join_field_extraction: TokenStream2,
+    id_column: Ident,
 ) -> TokenStream2 {
     quote! {
         impl EnhancedCrud for #name {
@@ -564,7 +699,7 @@ fn sqlite_impl(
             }
             fn update_bind(&mut self) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> {
                 #gen_scheme_code
-                let sql = scheme.gen_update_by_id_sql_static();
+                #gen_update_sql
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] UPDATE SQL: {}", sql);
                 let query = sqlx::query::<Sqlite>(sql);
@@ -606,28 +741,40 @@ fn sqlite_impl(
                 let query = sqlx::query::<Sqlite>(leaked_sql);
                 query
             }
-            fn where_query(statement: &str) -> QueryAs<'_, Sqlite, Self, <Sqlite as HasArguments<'_>>::Arguments>{
+            fn where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Sqlite>>(statement: W) -> QueryAs<'q, Sqlite, Self, <Sqlite as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_select_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_select_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] WHERE QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<Sqlite, Self>(sql);
+                let mut query = sqlx::query_as::<Sqlite, Self>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn count_query(statement: &str) -> QueryAs<'_, Sqlite, (i64,), <Sqlite as HasArguments<'_>>::Arguments>{
+            fn count_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Sqlite>>(statement: W) -> QueryAs<'q, Sqlite, (i64,), <Sqlite as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_count_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_count_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] COUNT QUERY SQL: {}", sql);
-                let query = sqlx::query_as::<Sqlite, (i64,)>(sql);
+                let mut query = sqlx::query_as::<Sqlite, (i64,)>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
-            fn delete_where_query(statement: &str) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments>{
+            fn delete_where_query<'q, W: ::sqlx_struct_enhanced::filter::IntoWhere<Sqlite>>(statement: W) -> Query<'q, Sqlite, <Sqlite as HasArguments<'q>>::Arguments>{
                 #gen_scheme_code
-                let sql = scheme.gen_delete_where_sql_static(statement);
+                let (statement, params) = statement.into_where();
+                let sql = scheme.gen_delete_where_sql_static(&statement);
                 #[cfg(feature = "log_sql")]
                 eprintln!("[SQLxEnhanced] DELETE WHERE SQL: {}", sql);
-                let query = sqlx::query::<Sqlite>(sql);
+                let mut query = sqlx::query::<Sqlite>(sql);
+                for param in params {
+                    query = param.bind_to(query);
+                }
                 query
             }
             fn bulk_delete(ids: &[String]) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized {
@@ -671,6 +818,26 @@ fn sqlite_impl(
             fn agg_query() -> ::sqlx_struct_enhanced::aggregate::AggQueryBuilder<'static, Sqlite> where Self: Sized {
                 ::sqlx_struct_enhanced::aggregate::AggQueryBuilder::new(#table_name.to_string())
             }
+            fn paginate<W: ::sqlx_struct_enhanced::filter::IntoWhere<Sqlite>>(filter: W, page: u64, per_page: u64) -> ::sqlx_struct_enhanced::pagination::Paginate<Self, Sqlite> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Paginate::new(scheme, filter, page, per_page)
+            }
+            fn after<C: Into<Option<::sqlx_struct_enhanced::pagination::Cursor>>>(cursor: C) -> ::sqlx_struct_enhanced::pagination::Keyset<Self, Sqlite> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Keyset::new(scheme, Self::#id_column, cursor.into())
+            }
+            fn chunked(chunk_size: u64) -> ::sqlx_struct_enhanced::pagination::Chunked<Self, Sqlite> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::pagination::Chunked::new(scheme, Self::#id_column, chunk_size)
+            }
+            fn select() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<Self, Sqlite> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::new(scheme)
+            }
+            fn select_as<P: ::sqlx_struct_enhanced::select::Projection<Entity = Self>>() -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<P, Sqlite> where Self: Sized {
+                #gen_scheme_code
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::projected(scheme, P::COLUMNS)
+            }
 
             #[cfg(feature = "join_queries")]
             fn join_inner<T>(condition: &str) -> ::sqlx_struct_enhanced::join::JoinQueryBuilder<'static, Self, T, Sqlite>
@@ -737,7 +904,7 @@ fn sqlite_impl(
             }
 
             #[cfg(feature = "sqlite")]
-            fn decode_from_qualified_row_sqlite(row: &::sqlx::sqlite::SqliteRow) -> Result<Option<Self>, ::sqlx::Error>
+            fn decode_from_qualified_row_sqlite(row: &::sqlx::sqlite::SqliteRow, qualifier: &str) -> Result<Option<Self>, ::sqlx::Error>
             where
                 Self: Sized
             {
@@ -769,6 +936,56 @@ fn to_snake_case(s: &str) -> String {
     result
 }
 
+/// Returns true if `word` appears as a whole identifier in attribute tokens.
+fn has_word(tokens: &str, word: &str) -> bool {
+    tokens.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|w| w == word)
+}
+
+/// Database type of a field's column, derived from its Rust type (looking
+/// through `Option`). `None` for types without an obvious mapping.
+fn column_sql_type(ty: &syn::Type, is_decimal: bool, is_uuid: bool) -> Option<&'static str> {
+    if is_decimal {
+        return Some("NUMERIC");
+    }
+    if is_uuid {
+        return Some("UUID");
+    }
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                return column_sql_type(inner, false, false);
+            }
+        }
+        return None;
+    }
+    match segment.ident.to_string().as_str() {
+        "i8" | "i16" => Some("SMALLINT"),
+        "i32" => Some("INTEGER"),
+        "i64" => Some("BIGINT"),
+        "f32" => Some("REAL"),
+        "f64" => Some("DOUBLE PRECISION"),
+        "bool" => Some("BOOLEAN"),
+        "String" => Some("TEXT"),
+        "DateTime" => Some("TIMESTAMPTZ"),
+        "NaiveDateTime" => Some("TIMESTAMP"),
+        "NaiveDate" => Some("DATE"),
+        "NaiveTime" => Some("TIME"),
+        "Uuid" => Some("UUID"),
+        "Decimal" | "BigDecimal" => Some("NUMERIC"),
+        _ => None,
+    }
+}
+
+/// Name of the `Column` constant generated for a field (`email` -> `EMAIL`).
+fn column_const_name(field: &Ident) -> Ident {
+    let column = field.to_string().trim_start_matches("r#").to_uppercase();
+    Ident::new(&column, field.span())
+}
+
 /// Types that require BindProxy conversion for database binding
 ///
 /// NOTE: The following types are NOT in this list because they already implement
@@ -825,6 +1042,16 @@ fn is_option_type(ty: &syn::Type) -> bool {
     false
 }
 
+/// Check if the field holds the `LazyDirty` marks of lazy fields (not a column)
+fn is_lazy_dirty_field(field: &syn::Field) -> bool {
+    if let syn::Type::Path(type_path) = &field.ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "LazyDirty";
+        }
+    }
+    false
+}
+
 /// Generate BindProxy conversion code for a field in bulk operations.
 ///
 /// This function creates the TokenStream for converting a field value using BindProxy,
@@ -911,6 +1138,11 @@ struct ColumnDefinition {
     cast_as: Option<String>,
     is_decimal: bool,
     is_uuid: bool,
+    is_lazy: bool,
+    sql_type: Option<&'static str>,
+    // Whether the field also carries #[sqlx(default)] (required for lazy fields)
+    #[cfg_attr(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")), allow(dead_code))]
+    has_sqlx_default: bool,
 }
 
 struct Schema {
@@ -919,6 +1151,8 @@ struct Schema {
     id_field: Ident,
     column_definitions: Vec<ColumnDefinition>,
     field_types: Vec<syn::Type>,  // Store field type information for BindProxy detection
+    relation_fields: Vec<Ident>,  // #[crud(belongs_to/has_many)] fields, not columns
+    dirty_field: Option<syn::Field>,  // LazyDirty field tracking set_<field> of lazy fields
 }
 
 impl Schema {
@@ -949,6 +1183,16 @@ impl Schema {
             syn::Data::Struct(data) => data.fields,
             _ => panic!("Only structs are supported"),
         };
+        // Relation fields hold related rows rather than a column
+        let (relation_fields, fields): (Vec<syn::Field>, Vec<syn::Field>) = fields.into_iter()
+            .partition(relations::is_relation_field);
+        let relation_fields = relation_fields.into_iter()
+            .map(|field| field.ident.unwrap())
+            .collect();
+        // The LazyDirty field holds the marks of lazy fields rather than a column
+        let (dirty_fields, fields): (Vec<syn::Field>, Vec<syn::Field>) = fields.into_iter()
+            .partition(is_lazy_dirty_field);
+        let dirty_field = dirty_fields.into_iter().next();
         let fields_name: Vec<Ident> = fields.iter().map(|field| {
             field.ident.as_ref().unwrap().clone()
         }).collect();
@@ -966,6 +1210,8 @@ impl Schema {
                 let mut cast_as = None;
                 let mut is_decimal = false;
                 let mut is_uuid = false;
+                let mut is_lazy = false;
+                let mut has_sqlx_default = false;
 
                 // Detect field type for UUID
                 let type_str = quote::quote!(#field.ty).to_string();
@@ -977,9 +1223,17 @@ impl Schema {
                 // Parse #[crud(...)] attributes
                 for attr in &field.attrs {
                     let path_str = quote::quote!(#attr).to_string();
+                    if attr.path.is_ident("sqlx") && has_word(&attr.tokens.to_string(), "default") {
+                        has_sqlx_default = true;
+                    }
                     if path_str.contains("crud") {
                         let tokens = attr.tokens.to_string();
 
+                        // #[crud(lazy)]: loaded on demand, left out of default SELECT/UPDATE
+                        if has_word(&tokens, "lazy") {
+                            is_lazy = true;
+                        }
+
                         // First: Check if this is a decimal field
                         if tokens.contains("decimal") {
                             is_decimal = true;
@@ -1033,7 +1287,8 @@ impl Schema {
                 // is_uuid is used for bulk operations (::uuid cast in WHERE IN clauses)
                 // cast_as is used for SELECT (output type conversion)
 
-                ColumnDefinition { name, cast_as, is_decimal, is_uuid }
+                let sql_type = column_sql_type(&field.ty, is_decimal, is_uuid);
+                ColumnDefinition { name, cast_as, is_decimal, is_uuid, is_lazy, sql_type, has_sqlx_default }
             })
             .collect();
 
@@ -1043,6 +1298,8 @@ impl Schema {
             id_field,
             column_definitions,
             field_types,
+            relation_fields,
+            dirty_field,
         }
     }
 }
@@ -1064,7 +1321,8 @@ impl SqlBuilder {
                 stringify!(#f).to_string()
             }
         });
-        let append_update_stmt = self.scheme.fields[1..].iter().map(|f|{
+        let append_update_stmt = self.update_field_indices().map(|i|{
+            let f = &self.scheme.fields[i];
             quote!{
                 stringify!(#f).to_string()
             }
@@ -1076,6 +1334,7 @@ impl SqlBuilder {
             let cast_as = &col.cast_as;
             let is_decimal = &col.is_decimal;
             let is_uuid = &col.is_uuid;
+            let is_lazy = &col.is_lazy;
             match cast_as {
                 Some(cast_type) => {
                     quote! {
@@ -1084,6 +1343,7 @@ impl SqlBuilder {
                             cast_as: Some(#cast_type.to_string()),
                             is_decimal: #is_decimal,
                             is_uuid: #is_uuid,
+                            is_lazy: #is_lazy,
                         }
                     }
                 }
@@ -1094,6 +1354,7 @@ impl SqlBuilder {
                             cast_as: None,
                             is_decimal: #is_decimal,
                             is_uuid: #is_uuid,
+                            is_lazy: #is_lazy,
                         }
                     }
                 }
@@ -1192,8 +1453,8 @@ impl SqlBuilder {
     }
 
     fn fill_update_param(&self, db_type: TokenStream2) -> TokenStream2 {
-        let bind_stmts = self.scheme.fields[1..].iter().enumerate().map(|(i, field)| {
-            let actual_index = i + 1; // Skip first field (id)
+        let bind_stmts = self.update_field_indices().map(|actual_index| {
+            let field = &self.scheme.fields[actual_index];
             let ty = &self.scheme.field_types[actual_index];
             let type_name = get_base_type_name(ty);
             let needs_proxy = TYPE_NEEDS_PROXY.contains(&type_name.as_str());
@@ -1261,8 +1522,178 @@ impl SqlBuilder {
                 }
             }
         });
+        // Lazy fields marked by set_<field> follow the regular update fields,
+        // matching the SQL chosen by gen_update_sql()
+        let lazy_bind_stmts = self.lazy_field_indices().filter(|_| self.scheme.dirty_field.is_some()).map(|i| {
+            let field = &self.scheme.fields[i];
+            let flag = format_ident!("lazy_dirty_{}", field);
+            let type_name = get_base_type_name(&self.scheme.field_types[i]);
+            if TYPE_NEEDS_PROXY.contains(&type_name.as_str()) {
+                quote! {
+                    let query = match &self.#field {
+                        Some(v) if #flag => ::sqlx_struct_enhanced::proxy::BindProxy::<#db_type>::into_bind_value(v.clone()).bind_to(query),
+                        None if #flag => query.bind::<Option<String>>(None),
+                        _ => query,
+                    };
+                }
+            } else {
+                quote! {
+                    let query = if #flag { query.bind(&self.#field) } else { query };
+                }
+            }
+        });
         quote! {
             #(#bind_stmts)*
+            #(#lazy_bind_stmts)*
+        }
+    }
+
+    /// Indices of the fields written by UPDATE (all but the ID and lazy fields).
+    fn update_field_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (1..self.scheme.fields.len()).filter(move |&i| !self.scheme.column_definitions[i].is_lazy)
+    }
+
+    /// Indices of the `#[crud(lazy)]` fields.
+    fn lazy_field_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.scheme.fields.len()).filter(move |&i| self.scheme.column_definitions[i].is_lazy)
+    }
+
+    /// Generates the statements binding `sql` to the UPDATE-by-ID SQL. Lazy
+    /// fields are only written while `set_<field>` marked them in the row's
+    /// `LazyDirty`, each mark being read into a `lazy_dirty_<field>` flag used
+    /// by fill_update_param().
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn gen_update_sql(&self) -> TokenStream2 {
+        let Some(dirty) = self.scheme.dirty_field.as_ref().and_then(|field| field.ident.as_ref()) else {
+            return quote! { let sql = scheme.gen_update_by_id_sql_static(); };
+        };
+        let mut flags = Vec::new();
+        let mut checks = Vec::new();
+        for (bit, i) in self.lazy_field_indices().enumerate() {
+            let bit = bit as u32;
+            let flag = format_ident!("lazy_dirty_{}", self.scheme.fields[i]);
+            let column = &self.scheme.column_definitions[i].name;
+            flags.push(quote! {
+                let #flag = self.#dirty.is_marked(#bit);
+            });
+            checks.push(quote! {
+                if #flag {
+                    lazy_fields.push(#column);
+                }
+            });
+        }
+        if flags.is_empty() {
+            return quote! { let sql = scheme.gen_update_by_id_sql_static(); };
+        }
+        quote! {
+            #(#flags)*
+            let sql = {
+                let mut lazy_fields: Vec<&'static str> = Vec::new();
+                #(#checks)*
+                if lazy_fields.is_empty() {
+                    scheme.gen_update_by_id_sql_static()
+                } else {
+                    scheme.gen_update_by_id_with_sql_static(&lazy_fields)
+                }
+            };
+        }
+    }
+
+    /// Generates `load_<field>` and `set_<field>` methods for `#[crud(lazy)]`
+    /// fields, and `save` clearing their marks after a successful UPDATE.
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn gen_lazy_loaders(&self, name: &Ident, db_type: &TokenStream2) -> TokenStream2 {
+        let gen_scheme_code = self.gen_scheme_code();
+        let id_field = &self.scheme.id_field;
+        let lazy_count = self.lazy_field_indices().count();
+        if lazy_count == 0 {
+            return quote! {};
+        }
+        let Some(dirty_field) = &self.scheme.dirty_field else {
+            let first = &self.scheme.fields[self.lazy_field_indices().next().unwrap()];
+            return syn::Error::new(
+                first.span(),
+                "#[crud(lazy)] fields need a #[sqlx(skip)] field of type LazyDirty to track set_<field> calls",
+            ).to_compile_error();
+        };
+        let dirty = dirty_field.ident.as_ref().unwrap();
+        let has_sqlx_skip = dirty_field.attrs.iter()
+            .any(|attr| attr.path.is_ident("sqlx") && has_word(&attr.tokens.to_string(), "skip"));
+        if !has_sqlx_skip {
+            return syn::Error::new(dirty.span(), "the LazyDirty field is not a column and needs #[sqlx(skip)]")
+                .to_compile_error();
+        }
+        if lazy_count > 64 {
+            return syn::Error::new(dirty.span(), "LazyDirty tracks at most 64 #[crud(lazy)] fields")
+                .to_compile_error();
+        }
+        let loaders = self.lazy_field_indices().enumerate().map(|(bit, i)| {
+            let bit = bit as u32;
+            let field = &self.scheme.fields[i];
+            let ty = &self.scheme.field_types[i];
+            let col = &self.scheme.column_definitions[i];
+            if i == 0 {
+                return syn::Error::new(field.span(), "the primary key cannot be #[crud(lazy)]").to_compile_error();
+            }
+            if !is_option_type(ty) {
+                return syn::Error::new(field.span(), "#[crud(lazy)] fields must be Option<T>").to_compile_error();
+            }
+            if !col.has_sqlx_default {
+                return syn::Error::new(
+                    field.span(),
+                    "#[crud(lazy)] fields need #[sqlx(default)] so rows without the column decode to None",
+                ).to_compile_error();
+            }
+            let column = &col.name;
+            let method = Ident::new(&format!("load_{}", column), field.span());
+            let doc = format!("Loads the lazy `{}` column from the database into this row.", column);
+            let setter = Ident::new(&format!("set_{}", column), field.span());
+            let setter_doc = format!(
+                "Sets the lazy `{}` column; `update_bind` writes it (`None` as NULL) until `save` succeeds.",
+                column
+            );
+            quote! {
+                #[doc = #setter_doc]
+                pub fn #setter(&mut self, value: #ty) {
+                    self.#field = value;
+                    self.#dirty.mark(#bit);
+                }
+
+                #[doc = #doc]
+                pub async fn #method<'e, E>(&mut self, executor: E) -> Result<(), ::sqlx::Error>
+                where
+                    E: ::sqlx::Executor<'e, Database = #db_type>,
+                {
+                    #gen_scheme_code
+                    let sql = scheme.gen_select_column_by_id_sql_static(#column);
+                    #[cfg(feature = "log_sql")]
+                    eprintln!("[SQLxEnhanced] LOAD LAZY SQL: {}", sql);
+                    let (value,) = ::sqlx::query_as::<#db_type, (#ty,)>(sql)
+                        .bind(self.#id_field.clone())
+                        .fetch_one(executor)
+                        .await?;
+                    self.#field = value;
+                    self.#dirty.clear(#bit);
+                    Ok(())
+                }
+            }
+        }).collect::<Vec<_>>();
+        quote! {
+            #[allow(dead_code)]
+            impl #name {
+                #(#loaders)*
+
+                /// Runs `update_bind` and, once the UPDATE succeeded, clears the
+                /// marks left by the `set_<field>` methods.
+                pub async fn save<'e, E>(&mut self, executor: E) -> Result<<#db_type as ::sqlx::Database>::QueryResult, ::sqlx::Error>
+                where
+                    E: ::sqlx::Executor<'e, Database = #db_type>,
+                {
+                    let result = ::sqlx_struct_enhanced::EnhancedCrud::update_bind(self).execute(executor).await?;
+                    self.#dirty.clear_all();
+                    Ok(result)
+                }
+            }
         }
     }
 
@@ -1292,16 +1723,14 @@ impl SqlBuilder {
 
     fn fill_bulk_update_param(&self, db_type: &TokenStream2) -> TokenStream2 {
         let id_field = &self.scheme.id_field;
-        let update_fields = &self.scheme.fields[1..];
-        let update_types = &self.scheme.field_types[1..];
         let id_type = &self.scheme.field_types[0];
 
         // Generate BindProxy conversion for id field
         let id_conversion = gen_bind_proxy_conversion_for_item(id_field, id_type, db_type);
 
         // Generate BindProxy conversions for update fields
-        let update_conversions = update_fields.iter().enumerate().map(|(i, field)| {
-            gen_bind_proxy_conversion_for_item(field, &update_types[i], db_type)
+        let update_conversions = self.update_field_indices().map(|i| {
+            gen_bind_proxy_conversion_for_item(&self.scheme.fields[i], &self.scheme.field_types[i], db_type)
         });
 
         quote! {
@@ -1321,13 +1750,12 @@ impl SqlBuilder {
 
     /// Generate code to extract fields from qualified column names for JOIN queries.
     fn gen_join_field_extraction(&self, name: &Ident) -> TokenStream2 {
-        let table_name = &self.scheme.table_name;
         let fields = &self.scheme.fields;
 
         // Generate code to extract each field using qualified column names
         let field_extractions = fields.iter().map(|field| {
             quote! {
-                let column_name = format!("{}.{}", #table_name, stringify!(#field));
+                let column_name = format!("{}.{}", qualifier, stringify!(#field));
                 let #field: _ = match row.try_get(column_name.as_str()) {
                     Ok(val) => val,
                     Err(::sqlx::Error::ColumnNotFound(_)) => return Ok(None),
@@ -1339,6 +1767,8 @@ impl SqlBuilder {
 
         // Generate code to construct the entity
         let field_names = fields.iter().clone();
+        let relation_fields = &self.scheme.relation_fields;
+        let dirty_field = self.scheme.dirty_field.iter().map(|field| &field.ident);
 
         // FIXED: Added block wrapper to fix "expected expression, found `let` statement" error
         quote! {
@@ -1346,11 +1776,101 @@ impl SqlBuilder {
                 #(#field_extractions)*
 
                 Ok(Some(#name {
-                    #(#field_names),*
+                    #(#field_names,)*
+                    #(#relation_fields: ::std::default::Default::default(),)*
+                    #(#dirty_field: ::std::default::Default::default(),)*
                 }))
             }
         }
     }
+
+    /// Implements `relation::Entity`, giving relation loaders of other
+    /// entities access to this one's scheme and primary key.
+    fn gen_entity_impl(&self, name: &Ident) -> TokenStream2 {
+        let gen_scheme_code = self.gen_scheme_code();
+        let id_field = &self.scheme.id_field;
+        let id_type = &self.scheme.field_types[0];
+        quote! {
+            impl ::sqlx_struct_enhanced::relation::Entity for #name {
+                type Key = #id_type;
+
+                fn scheme() -> &'static ::sqlx_struct_enhanced::Scheme {
+                    #gen_scheme_code
+                    scheme
+                }
+
+                fn primary_key(&self) -> &#id_type {
+                    &self.#id_field
+                }
+            }
+        }
+    }
+
+    /// Name of the `Column` constant of the primary key.
+    fn id_column_const(&self) -> Ident {
+        column_const_name(&self.scheme.id_field)
+    }
+
+    /// Generates one `Column` constant per field for the filter DSL
+    /// (`email` -> `User::EMAIL`).
+    fn gen_column_constants(&self, name: &Ident) -> TokenStream2 {
+        let table_name = &self.scheme.table_name;
+        let constants = self.scheme.fields.iter().zip(&self.scheme.column_definitions).map(|(field, col)| {
+            let column = field.to_string().trim_start_matches("r#").to_string();
+            let const_name = column_const_name(field);
+            let doc = format!("The `{}` column, for use in filter expressions.", column);
+            let typed = col.sql_type.map(|sql_type| quote! { .typed(#sql_type) });
+            quote! {
+                #[doc = #doc]
+                pub const #const_name: ::sqlx_struct_enhanced::filter::Column =
+                    ::sqlx_struct_enhanced::filter::Column::new(#table_name, #column)#typed;
+            }
+        });
+
+        quote! {
+            #[allow(dead_code)]
+            impl #name {
+                #(#constants)*
+            }
+        }
+    }
+}
+
+/// Derive `Projection` for a struct holding a subset of an entity's columns.
+///
+/// ```ignore
+/// #[derive(FromRow, Projection)]
+/// #[projection(User)]
+/// struct UserSummary { id: String, name: String }
+///
+/// let rows = User::select_as::<UserSummary>().fetch_all(&pool).await?;
+/// ```
+#[proc_macro_derive(Projection, attributes(projection))]
+pub fn projection_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    projection::derive_projection(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derive `AggRow` for a struct receiving the rows of an aggregation query.
+///
+/// ```ignore
+/// #[derive(FromRow, AggRow)]
+/// struct RevenueByCategory { category: String, total: Decimal }
+///
+/// let rows = Order::agg_query()
+///     .group_by("category")
+///     .sum_as("amount", "total")
+///     .fetch_all_as::<RevenueByCategory>(&pool)
+///     .await?;
+/// ```
+#[proc_macro_derive(AggRow)]
+pub fn agg_row_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    agg_row::derive_agg_row(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
 }
 
 // 编译期查询分析属性宏
@@ -0,0 +1,50 @@
+//! Code generation for the `Projection` derive macro.
+//!
+//! A projection names its entity with `#[projection(Entity)]` and lists a
+//! subset of the entity's columns as fields. Each field is checked against
+//! the column constants generated by `EnhancedCrud`, so a typo or a column
+//! that does not exist fails at compile time, pointing at the field.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{DeriveInput, Ident, Path};
+
+pub fn derive_projection(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let attr = input.attrs.iter()
+        .find(|attr| attr.path.is_ident("projection"))
+        .ok_or_else(|| syn::Error::new(name.span(), "missing #[projection(Entity)] attribute"))?;
+    let entity: Path = attr.parse_args()?;
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new(name.span(), "Projection can only be derived for structs")),
+    };
+    let columns: Vec<(String, proc_macro2::Span)> = fields.iter()
+        .map(|field| {
+            let ident = field.ident.as_ref()
+                .ok_or_else(|| syn::Error::new(field.span(), "Projection requires named fields"))?;
+            Ok((ident.to_string().trim_start_matches("r#").to_string(), ident.span()))
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let column_names = columns.iter().map(|(column, _)| column);
+    let checks = columns.iter().map(|(column, span)| {
+        let const_name = Ident::new(&column.to_uppercase(), *span);
+        quote_spanned! {*span=> let _ = #entity::#const_name; }
+    });
+
+    Ok(quote! {
+        impl ::sqlx_struct_enhanced::select::Projection for #name {
+            type Entity = #entity;
+            const COLUMNS: &'static [&'static str] = &[#(#column_names),*];
+        }
+
+        // Every field must be a column of the entity
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
@@ -0,0 +1,618 @@
+//! Relation declarations (`#[crud(belongs_to(..))]`, `#[crud(has_many(..))]`,
+//! `#[crud(many_to_many(..))]`) and generation of their loader methods.
+//!
+//! A relation is declared on a field holding the related rows. That field is
+//! not a column: it is excluded from the scheme and must carry `#[sqlx(skip)]`.
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use proc_macro2::Span;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Field, Ident, Lit, LitStr, Meta, NestedMeta, Path};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RelationKind {
+    BelongsTo,
+    HasMany,
+    ManyToMany,
+}
+
+impl RelationKind {
+    fn from_ident(ident: &str) -> Option<Self> {
+        match ident {
+            "belongs_to" => Some(RelationKind::BelongsTo),
+            "has_many" => Some(RelationKind::HasMany),
+            "many_to_many" => Some(RelationKind::ManyToMany),
+            _ => None,
+        }
+    }
+}
+
+/// A relation declared on a field of the deriving struct.
+#[cfg_attr(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")), allow(dead_code))]
+pub struct Relation {
+    pub kind: RelationKind,
+    /// The field holding the related rows
+    pub field: Ident,
+    pub field_ty: syn::Type,
+    /// The related entity
+    pub target: Path,
+    /// Foreign-key column: on this struct for `belongs_to`, on the target for
+    /// `has_many`; `None` for `many_to_many`
+    pub fk: Option<LitStr>,
+    /// Join table and its (left, right) columns for `many_to_many`
+    pub through: Option<(String, String, String)>,
+    has_sqlx_skip: bool,
+}
+
+/// Returns true if the field declares a relation rather than a column.
+pub fn is_relation_field(field: &Field) -> bool {
+    field.attrs.iter()
+        .filter(|attr| attr.path.is_ident("crud"))
+        .any(|attr| relation_meta(attr).is_some())
+}
+
+fn relation_meta(attr: &syn::Attribute) -> Option<(RelationKind, syn::MetaList)> {
+    let Ok(Meta::List(list)) = attr.parse_meta() else { return None };
+    list.nested.into_iter().find_map(|nested| match nested {
+        NestedMeta::Meta(Meta::List(inner)) => {
+            let kind = RelationKind::from_ident(&inner.path.get_ident()?.to_string())?;
+            Some((kind, inner))
+        }
+        _ => None,
+    })
+}
+
+/// Collects the relations declared on the struct's fields.
+pub fn extract_relations(input: &syn::DeriveInput) -> syn::Result<Vec<Relation>> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return Ok(Vec::new()),
+    };
+    let mut relations = Vec::new();
+    for field in fields {
+        let Some(ident) = &field.ident else { continue };
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("crud")) {
+            let Some((kind, list)) = relation_meta(attr) else { continue };
+            let keys: &[&str] = match kind {
+                RelationKind::ManyToMany => &["through", "left", "right"],
+                RelationKind::BelongsTo | RelationKind::HasMany => &["fk"],
+            };
+            let mut target = None;
+            let mut values: Vec<Option<LitStr>> = vec![None; keys.len()];
+            for nested in &list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) => target = Some(path.clone()),
+                    NestedMeta::Meta(Meta::NameValue(nv)) => {
+                        let Some(pos) = keys.iter().position(|key| nv.path.is_ident(key)) else {
+                            return Err(syn::Error::new(nv.path.span(), format!("expected one of: {}", keys.join(", "))));
+                        };
+                        match &nv.lit {
+                            Lit::Str(value) => values[pos] = Some(value.clone()),
+                            other => return Err(syn::Error::new(other.span(), format!("{} must be a string literal", keys[pos]))),
+                        }
+                    }
+                    other => return Err(syn::Error::new(other.span(), "expected the related entity or `key = \"value\"`")),
+                }
+            }
+            let target = target.ok_or_else(|| syn::Error::new(list.span(), "missing related entity type"))?;
+            let mut values = keys.iter().zip(values).map(|(key, value)| {
+                value.ok_or_else(|| syn::Error::new(list.span(), format!("missing `{} = \"column\"`", key)))
+            }).collect::<syn::Result<Vec<_>>>()?.into_iter();
+            let (fk, through) = match kind {
+                RelationKind::ManyToMany => {
+                    let mut next = || values.next().unwrap().value();
+                    let (table, left, right) = (next(), next(), next());
+                    (None, Some((table, left, right)))
+                }
+                RelationKind::BelongsTo | RelationKind::HasMany => (values.next(), None),
+            };
+            let has_sqlx_skip = field.attrs.iter().any(|attr| {
+                attr.path.is_ident("sqlx") && crate::has_word(&attr.tokens.to_string(), "skip")
+            });
+            relations.push(Relation {
+                kind,
+                field: ident.clone(),
+                field_ty: field.ty.clone(),
+                target,
+                fk,
+                through,
+                has_sqlx_skip,
+            });
+        }
+    }
+    Ok(relations)
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn last_segment_is(ty: &syn::Type, name: &str) -> bool {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().is_some_and(|seg| seg.ident == name),
+        _ => false,
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn method_ident(prefix: &str, field: &Ident, suffix: &str) -> Ident {
+    let field = field.to_string().trim_start_matches("r#").to_string();
+    Ident::new(&format!("{}_{}{}", prefix, field, suffix), Span::call_site())
+}
+
+/// The field named by `fk = "..."`, or an error spanned on the literal.
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn fk_field(relation: &Relation) -> syn::Result<Ident> {
+    let fk = relation.fk.as_ref().expect("belongs_to and has_many relations have an fk");
+    syn::parse_str::<Ident>(&fk.value())
+        .map_err(|_| syn::Error::new(fk.span(), format!("fk = \"{}\" is not a field name", fk.value())))
+}
+
+/// Generates `load_<field>` and `preload_<field>s` (belongs_to),
+/// `load_<field>` and `preload_<field>` (has_many), plus `attach_<field>`,
+/// `detach_<field>` and `sync_<field>` (many_to_many) for each relation.
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub fn gen_relation_loaders(name: &Ident, relations: &[Relation], id_field: &Ident, db_type: &TokenStream2) -> TokenStream2 {
+    let methods = relations.iter().map(|relation| {
+        let field = &relation.field;
+        let target = &relation.target;
+
+        if !relation.has_sqlx_skip {
+            return syn::Error::new(field.span(), "relation fields are not columns and need #[sqlx(skip)]").to_compile_error();
+        }
+
+        match relation.kind {
+            RelationKind::BelongsTo => {
+                if !last_segment_is(&relation.field_ty, "Option") {
+                    return syn::Error::new(relation.field_ty.span(), "belongs_to fields must be Option<Entity>").to_compile_error();
+                }
+                let fk_field = match fk_field(relation) {
+                    Ok(fk_field) => fk_field,
+                    Err(err) => return err.to_compile_error(),
+                };
+                let load = method_ident("load", field, "");
+                let preload = method_ident("preload", field, "s");
+                let load_doc = format!("Loads the `{}` this row belongs to (one query).", field);
+                let preload_doc = format!(
+                    "Loads the `{}` of every row with one IN query per chunk of keys below the bind-parameter limit.",
+                    field
+                );
+                quote! {
+                    #[doc = #load_doc]
+                    pub async fn #load<'e, E>(&mut self, executor: E) -> Result<(), ::sqlx::Error>
+                    where
+                        E: ::sqlx::Executor<'e, Database = #db_type>,
+                    {
+                        use ::sqlx_struct_enhanced::relation::{Entity, ForeignKey};
+                        let key = match ForeignKey::<<#target as Entity>::Key>::key(&self.#fk_field) {
+                            Some(key) => key.clone(),
+                            None => {
+                                self.#field = None;
+                                return Ok(());
+                            }
+                        };
+                        let sql = <#target as Entity>::scheme().gen_select_by_id_sql_static();
+                        self.#field = ::sqlx::query_as::<#db_type, #target>(sql)
+                            .bind(key)
+                            .fetch_optional(executor)
+                            .await?;
+                        Ok(())
+                    }
+
+                    #[doc = #preload_doc]
+                    pub async fn #preload<'a, A>(rows: &mut [Self], conn: A) -> Result<(), ::sqlx::Error>
+                    where
+                        A: ::sqlx::Acquire<'a, Database = #db_type>,
+                    {
+                        use ::sqlx_struct_enhanced::relation::{Entity, ForeignKey};
+                        let mut keys = Vec::new();
+                        {
+                            let mut seen = ::std::collections::HashSet::new();
+                            for row in rows.iter() {
+                                if let Some(key) = ForeignKey::<<#target as Entity>::Key>::key(&row.#fk_field) {
+                                    if seen.insert(key) {
+                                        keys.push(key.clone());
+                                    }
+                                }
+                            }
+                        }
+                        let mut parents = ::std::collections::HashMap::new();
+                        if !keys.is_empty() {
+                            let mut conn = conn.acquire().await?;
+                            for chunk in keys.chunks(::sqlx_struct_enhanced::Dialect::current().max_bind_params()) {
+                                let sql = <#target as Entity>::scheme().gen_bulk_select_sql_static(chunk.len());
+                                let mut query = ::sqlx::query_as::<#db_type, #target>(sql);
+                                for key in chunk {
+                                    query = query.bind(key.clone());
+                                }
+                                for parent in query.fetch_all(&mut *conn).await? {
+                                    parents.insert(parent.primary_key().clone(), parent);
+                                }
+                            }
+                        }
+                        for row in rows.iter_mut() {
+                            row.#field = ForeignKey::<<#target as Entity>::Key>::key(&row.#fk_field)
+                                .and_then(|key| parents.get(key))
+                                .cloned();
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            RelationKind::HasMany => {
+                if !last_segment_is(&relation.field_ty, "Vec") {
+                    return syn::Error::new(relation.field_ty.span(), "has_many fields must be Vec<Entity>").to_compile_error();
+                }
+                let fk_field = match fk_field(relation) {
+                    Ok(fk_field) => fk_field,
+                    Err(err) => return err.to_compile_error(),
+                };
+                let fk = fk_field.to_string().trim_start_matches("r#").to_string();
+                let load = method_ident("load", field, "");
+                let preload = method_ident("preload", field, "");
+                let load_doc = format!("Loads the `{}` of this row (one query).", field);
+                let preload_doc = format!(
+                    "Loads the `{}` of every row with one IN query per chunk of keys below the bind-parameter limit.",
+                    field
+                );
+                quote! {
+                    #[doc = #load_doc]
+                    pub async fn #load<'e, E>(&mut self, executor: E) -> Result<(), ::sqlx::Error>
+                    where
+                        E: ::sqlx::Executor<'e, Database = #db_type>,
+                    {
+                        use ::sqlx_struct_enhanced::relation::Entity;
+                        let sql = <#target as Entity>::scheme().gen_select_in_sql_static(#fk, 1);
+                        self.#field = ::sqlx::query_as::<#db_type, #target>(sql)
+                            .bind(self.#id_field.clone())
+                            .fetch_all(executor)
+                            .await?;
+                        Ok(())
+                    }
+
+                    #[doc = #preload_doc]
+                    pub async fn #preload<'a, A>(rows: &mut [Self], conn: A) -> Result<(), ::sqlx::Error>
+                    where
+                        A: ::sqlx::Acquire<'a, Database = #db_type>,
+                    {
+                        use ::sqlx_struct_enhanced::relation::{Entity, ForeignKey};
+                        if rows.is_empty() {
+                            return Ok(());
+                        }
+                        let mut conn = conn.acquire().await?;
+                        let mut children: ::std::collections::HashMap<<Self as Entity>::Key, Vec<#target>> =
+                            ::std::collections::HashMap::new();
+                        for chunk in rows.chunks(::sqlx_struct_enhanced::Dialect::current().max_bind_params()) {
+                            let sql = <#target as Entity>::scheme().gen_select_in_sql_static(#fk, chunk.len());
+                            let mut query = ::sqlx::query_as::<#db_type, #target>(sql);
+                            for row in chunk {
+                                query = query.bind(row.#id_field.clone());
+                            }
+                            for child in query.fetch_all(&mut *conn).await? {
+                                if let Some(key) = ForeignKey::<<Self as Entity>::Key>::key(&child.#fk_field) {
+                                    children.entry(key.clone()).or_default().push(child);
+                                }
+                            }
+                        }
+                        for row in rows.iter_mut() {
+                            row.#field = children.remove(&row.#id_field).unwrap_or_default();
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            RelationKind::ManyToMany => {
+                if !last_segment_is(&relation.field_ty, "Vec") {
+                    return syn::Error::new(relation.field_ty.span(), "many_to_many fields must be Vec<Entity>").to_compile_error();
+                }
+                let (table, left, right) = relation.through.as_ref().unwrap();
+                gen_many_to_many(field, target, (table, left, right), id_field, db_type)
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    if methods.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#methods)*
+        }
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn gen_many_to_many(field: &Ident, target: &Path, (table, left, right): (&str, &str, &str), id_field: &Ident, db_type: &TokenStream2) -> TokenStream2 {
+    let load = method_ident("load", field, "");
+    let preload = method_ident("preload", field, "");
+    let attach = method_ident("attach", field, "");
+    let detach = method_ident("detach", field, "");
+    let sync = method_ident("sync", field, "");
+    let load_doc = format!("Loads the `{}` of this row through `{}` (one query).", field, table);
+    let preload_doc = format!(
+        "Loads the `{}` of every row through `{}` with one query per chunk of keys below the bind-parameter limit.",
+        field, table
+    );
+    let attach_doc = format!(
+        "Inserts `{}` rows linking this row to `keys`, in chunks below the bind-parameter limit; existing links are ignored. Returns the rows inserted.",
+        table
+    );
+    let detach_doc = format!(
+        "Deletes the `{}` rows linking this row to `keys`, in chunks below the bind-parameter limit. Returns the rows deleted.",
+        table
+    );
+    let sync_doc = format!(
+        "Makes `keys` the exact set of `{}` linked to this row, inserting and deleting only the differing `{}` rows inside one transaction.",
+        field, table
+    );
+    let through = quote! {
+        ::sqlx_struct_enhanced::relation::Through::new(#table, #left, #right)
+    };
+    quote! {
+        #[doc = #load_doc]
+        pub async fn #load<'e, E>(&mut self, executor: E) -> Result<(), ::sqlx::Error>
+        where
+            E: ::sqlx::Executor<'e, Database = #db_type>,
+        {
+            use ::sqlx_struct_enhanced::relation::Entity;
+            let sql = #through.gen_preload_sql_static(<#target as Entity>::scheme(), 1);
+            let rows = ::sqlx::query::<#db_type>(sql)
+                .bind(self.#id_field.clone())
+                .fetch_all(executor)
+                .await?;
+            self.#field = rows.iter()
+                .map(<#target as ::sqlx::FromRow<_>>::from_row)
+                .collect::<Result<_, _>>()?;
+            Ok(())
+        }
+
+        #[doc = #preload_doc]
+        pub async fn #preload<'a, A>(rows: &mut [Self], conn: A) -> Result<(), ::sqlx::Error>
+        where
+            A: ::sqlx::Acquire<'a, Database = #db_type>,
+        {
+            use ::sqlx_struct_enhanced::relation::Entity;
+            if rows.is_empty() {
+                return Ok(());
+            }
+            let mut conn = conn.acquire().await?;
+            let mut related: ::std::collections::HashMap<<Self as Entity>::Key, Vec<#target>> =
+                ::std::collections::HashMap::new();
+            for chunk in rows.chunks(::sqlx_struct_enhanced::Dialect::current().max_bind_params()) {
+                let sql = #through.gen_preload_sql_static(<#target as Entity>::scheme(), chunk.len());
+                let mut query = ::sqlx::query::<#db_type>(sql);
+                for row in chunk {
+                    query = query.bind(row.#id_field.clone());
+                }
+                for row in query.fetch_all(&mut *conn).await? {
+                    let key: <Self as Entity>::Key = ::sqlx::Row::try_get(&row, "__left")?;
+                    let item = <#target as ::sqlx::FromRow<_>>::from_row(&row)?;
+                    related.entry(key).or_default().push(item);
+                }
+            }
+            for row in rows.iter_mut() {
+                row.#field = related.remove(&row.#id_field).unwrap_or_default();
+            }
+            Ok(())
+        }
+
+        #[doc = #attach_doc]
+        pub async fn #attach<'a, A>(&self, conn: A, keys: &[<#target as ::sqlx_struct_enhanced::relation::Entity>::Key]) -> Result<u64, ::sqlx::Error>
+        where
+            A: ::sqlx::Acquire<'a, Database = #db_type>,
+        {
+            if keys.is_empty() {
+                return Ok(0);
+            }
+            let mut conn = conn.acquire().await?;
+            let mut inserted = 0;
+            // Two binds per link
+            for chunk in keys.chunks(::sqlx_struct_enhanced::Dialect::current().max_bind_params() / 2) {
+                let sql = #through.gen_attach_sql_static(chunk.len());
+                let mut query = ::sqlx::query::<#db_type>(sql);
+                for key in chunk {
+                    query = query.bind(self.#id_field.clone()).bind(key.clone());
+                }
+                inserted += query.execute(&mut *conn).await?.rows_affected();
+            }
+            Ok(inserted)
+        }
+
+        #[doc = #detach_doc]
+        pub async fn #detach<'a, A>(&self, conn: A, keys: &[<#target as ::sqlx_struct_enhanced::relation::Entity>::Key]) -> Result<u64, ::sqlx::Error>
+        where
+            A: ::sqlx::Acquire<'a, Database = #db_type>,
+        {
+            if keys.is_empty() {
+                return Ok(0);
+            }
+            let mut conn = conn.acquire().await?;
+            let mut deleted = 0;
+            // The owning key takes one bind
+            for chunk in keys.chunks(::sqlx_struct_enhanced::Dialect::current().max_bind_params() - 1) {
+                let sql = #through.gen_detach_sql_static(chunk.len());
+                let mut query = ::sqlx::query::<#db_type>(sql).bind(self.#id_field.clone());
+                for key in chunk {
+                    query = query.bind(key.clone());
+                }
+                deleted += query.execute(&mut *conn).await?.rows_affected();
+            }
+            Ok(deleted)
+        }
+
+        #[doc = #sync_doc]
+        pub async fn #sync<'a, A>(&self, conn: A, keys: &[<#target as ::sqlx_struct_enhanced::relation::Entity>::Key]) -> Result<::sqlx_struct_enhanced::relation::SyncResult, ::sqlx::Error>
+        where
+            A: ::sqlx::Acquire<'a, Database = #db_type>,
+        {
+            use ::sqlx_struct_enhanced::relation::Entity;
+            let mut tx = conn.begin().await?;
+            let existing: Vec<<#target as Entity>::Key> = ::sqlx::query_scalar::<#db_type, _>(#through.gen_select_right_keys_sql_static())
+                .bind(self.#id_field.clone())
+                .fetch_all(&mut *tx)
+                .await?;
+            let wanted: ::std::collections::HashSet<_> = keys.iter().collect();
+            let current: ::std::collections::HashSet<_> = existing.iter().collect();
+            let removed: Vec<_> = existing.iter().filter(|key| !wanted.contains(key)).cloned().collect();
+            let mut added = Vec::new();
+            {
+                let mut seen = ::std::collections::HashSet::new();
+                for key in keys {
+                    if !current.contains(key) && seen.insert(key) {
+                        added.push(key.clone());
+                    }
+                }
+            }
+            let detached = self.#detach(&mut *tx, &removed).await?;
+            let attached = self.#attach(&mut *tx, &added).await?;
+            tx.commit().await?;
+            Ok(::sqlx_struct_enhanced::relation::SyncResult { attached, detached })
+        }
+    }
+}
+
+/// Implements `join::ForeignKeyJoin` in both directions for each
+/// `#[crud(references(Target))]` field, so `join_*_fk` can infer the ON clause.
+///
+/// Each pair of entities may be linked by one field only, since the JOIN
+/// condition would be ambiguous otherwise. A second field referencing the same
+/// target is rejected here; a reference declared by the other entity as well
+/// conflicts with the impls generated for this one.
+pub fn gen_foreign_key_joins(name: &Ident, input: &syn::DeriveInput) -> TokenStream2 {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return quote! {},
+    };
+    let struct_name = name.to_string();
+    // Target of each `references` field seen so far, with the field
+    let mut targets: Vec<(String, &Ident)> = Vec::new();
+
+    let mut impls = Vec::new();
+    for field in fields {
+        let Some(ident) = &field.ident else { continue };
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("crud")) {
+            let Ok(Meta::List(list)) = attr.parse_meta() else { continue };
+            for nested in &list.nested {
+                let NestedMeta::Meta(Meta::List(inner)) = nested else { continue };
+                if !inner.path.is_ident("references") {
+                    continue;
+                }
+                let target = match inner.nested.iter().collect::<Vec<_>>().as_slice() {
+                    [NestedMeta::Meta(Meta::Path(path))] => path.clone(),
+                    _ => return syn::Error::new(inner.span(), "expected #[crud(references(Entity))]").to_compile_error(),
+                };
+                let target_name = match target.segments.last() {
+                    Some(segment) if segment.ident != "Self" => segment.ident.to_string(),
+                    _ => struct_name.clone(),
+                };
+                if let Some((_, other_field)) = targets.iter().find(|(seen, _)| *seen == target_name) {
+                    let message = format!(
+                        "ambiguous foreign key: `{}::{}` already references {}; declare #[crud(references(..))] on one field only and pass an explicit JOIN condition for other joins",
+                        struct_name, other_field, target_name
+                    );
+                    return syn::Error::new(inner.span(), message).to_compile_error();
+                }
+                targets.push((target_name.clone(), ident));
+
+                let column = ident.to_string().trim_start_matches("r#").to_string();
+                impls.push(quote! {
+                    #[cfg(feature = "join_queries")]
+                    impl ::sqlx_struct_enhanced::join::ForeignKeyJoin<#target> for #name {
+                        fn join_columns() -> (&'static str, &'static str) {
+                            let target = <#target as ::sqlx_struct_enhanced::join::SchemeAccessor>::get_scheme();
+                            (#column, &target.id_field)
+                        }
+                    }
+                });
+                // A self-reference would implement the same trait twice
+                if target_name != struct_name {
+                    impls.push(quote! {
+                        #[cfg(feature = "join_queries")]
+                        impl ::sqlx_struct_enhanced::join::ForeignKeyJoin<#name> for #target {
+                            fn join_columns() -> (&'static str, &'static str) {
+                                let target = <#target as ::sqlx_struct_enhanced::join::SchemeAccessor>::get_scheme();
+                                (&target.id_field, #column)
+                            }
+                        }
+                    });
+                }
+            }
+        }
+    }
+    quote! { #(#impls)* }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlx_skip_matches_whole_word() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Order {
+                id: String,
+                #[sqlx(skip)]
+                #[crud(belongs_to(Customer, fk = "customer_id"))]
+                customer: Option<Customer>,
+                #[sqlx(rename = "skip_count")]
+                #[crud(has_many(OrderLine, fk = "order_id"))]
+                lines: Vec<OrderLine>,
+            }
+        };
+        let relations = extract_relations(&input).unwrap();
+        assert!(relations[0].has_sqlx_skip);
+        assert!(!relations[1].has_sqlx_skip);
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    #[test]
+    fn test_invalid_fk_is_a_compile_error() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Order {
+                id: String,
+                #[sqlx(skip)]
+                #[crud(belongs_to(Customer, fk = "customer id"))]
+                customer: Option<Customer>,
+                #[sqlx(skip)]
+                #[crud(has_many(OrderLine, fk = "order_id"))]
+                lines: Vec<OrderLine>,
+            }
+        };
+        let relations = extract_relations(&input).unwrap();
+        let id: Ident = syn::parse_quote!(id);
+        let tokens = gen_relation_loaders(&input.ident, &relations, &id, &quote!(::sqlx::Postgres)).to_string();
+        assert!(tokens.contains("compile_error"));
+        assert!(tokens.contains(r#"fk = \"customer id\" is not a field name"#));
+        assert!(tokens.contains("load_lines"));
+    }
+
+    fn fk_joins(input: syn::DeriveInput) -> String {
+        gen_foreign_key_joins(&input.ident.clone(), &input).to_string()
+    }
+
+    #[test]
+    fn test_foreign_key_target_is_referenced_once() {
+        let order = fk_joins(syn::parse_quote! {
+            struct FkOrder {
+                id: String,
+                #[crud(references(FkCustomer))]
+                customer_id: String,
+                #[crud(references(FkWarehouse))]
+                warehouse_id: String,
+            }
+        });
+        assert!(!order.contains("compile_error"));
+
+        let duplicate = fk_joins(syn::parse_quote! {
+            struct FkInvoice {
+                id: String,
+                #[crud(references(FkCustomer))]
+                billed_to: String,
+                #[crud(references(models::FkCustomer))]
+                shipped_to: String,
+            }
+        });
+        assert!(duplicate.contains("compile_error"));
+        assert!(duplicate.contains("`FkInvoice::billed_to` already references FkCustomer"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Tree declarations (`#[crud(tree(parent = "parent_id"))]` on the struct)
+//! and generation of the `descendants` / `ancestors` walks.
+//!
+//! The parent column must be a field of the struct; the walk itself is
+//! rendered at runtime by `SelectQueryBuilder::tree` for the active dialect.
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use proc_macro2::TokenStream as TokenStream2;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use quote::quote;
+use syn::spanned::Spanned;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use syn::Ident;
+use syn::{DeriveInput, Lit, Meta, NestedMeta};
+
+/// Returns the parent column of a `#[crud(tree(parent = "..."))]` struct.
+pub fn extract_tree_parent(input: &DeriveInput) -> syn::Result<Option<String>> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("crud")) {
+        let Meta::List(list) = attr.parse_meta()? else { continue };
+        for nested in &list.nested {
+            let NestedMeta::Meta(Meta::List(inner)) = nested else { continue };
+            if !inner.path.is_ident("tree") {
+                continue;
+            }
+            let parent = match inner.nested.iter().collect::<Vec<_>>().as_slice() {
+                [NestedMeta::Meta(Meta::NameValue(nv))] if nv.path.is_ident("parent") => match &nv.lit {
+                    Lit::Str(value) => value.value(),
+                    other => return Err(syn::Error::new(other.span(), "parent must be a string literal")),
+                },
+                _ => return Err(syn::Error::new(inner.span(), "expected #[crud(tree(parent = \"column\"))]")),
+            };
+            let is_field = match &input.data {
+                syn::Data::Struct(data) => data.fields.iter().any(|field| {
+                    field.ident.as_ref().is_some_and(|ident| ident.to_string().trim_start_matches("r#") == parent)
+                }),
+                _ => false,
+            };
+            if !is_field {
+                return Err(syn::Error::new(inner.span(), format!("tree parent `{}` is not a field of the struct", parent)));
+            }
+            return Ok(Some(parent));
+        }
+    }
+    Ok(None)
+}
+
+/// Generates `descendants(id)` and `ancestors(id)` for a tree entity.
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub fn gen_tree_walks(name: &Ident, parent: Option<&str>, db_type: &TokenStream2) -> TokenStream2 {
+    let Some(parent) = parent else { return quote! {} };
+    let descendants_doc = format!(
+        "Selects every row below `id`, following `{}` down to the leaves (recursive CTE).",
+        parent
+    );
+    let ancestors_doc = format!(
+        "Selects every row above `id`, following `{}` up to the root (recursive CTE).",
+        parent
+    );
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #[doc = #descendants_doc]
+            pub fn descendants<V>(id: V) -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<Self, #db_type>
+            where
+                V: ::sqlx_struct_enhanced::proxy::BindProxy<#db_type>,
+            {
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::tree(
+                    <Self as ::sqlx_struct_enhanced::relation::Entity>::scheme(),
+                    #parent,
+                    ::sqlx_struct_enhanced::select::TreeWalk::Descendants,
+                    id,
+                )
+            }
+
+            #[doc = #ancestors_doc]
+            pub fn ancestors<V>(id: V) -> ::sqlx_struct_enhanced::select::SelectQueryBuilder<Self, #db_type>
+            where
+                V: ::sqlx_struct_enhanced::proxy::BindProxy<#db_type>,
+            {
+                ::sqlx_struct_enhanced::select::SelectQueryBuilder::tree(
+                    <Self as ::sqlx_struct_enhanced::relation::Entity>::scheme(),
+                    #parent,
+                    ::sqlx_struct_enhanced::select::TreeWalk::Ancestors,
+                    id,
+                )
+            }
+        }
+    }
+}
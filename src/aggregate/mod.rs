@@ -2,8 +2,15 @@
 //!
 //! This module provides a fluent query builder for SQL aggregation operations
 //! including SUM, AVG, COUNT, MIN, MAX with GROUP BY, HAVING, ORDER BY,
-//! LIMIT/OFFSET, and JOIN support.
+//! LIMIT/OFFSET, JOIN, window function and time bucket support, and typed
+//! result rows.
 
 mod query_builder;
+mod row;
+mod time_bucket;
+pub mod window;
 
 pub use query_builder::{AggQueryBuilder, Join, JoinType};
+pub use row::{AggCast, AggRow};
+pub use time_bucket::{fill_gaps, TimeBucket};
+pub use window::{FrameBound, WindowFunction};
@@ -2,12 +2,18 @@
 //!
 //! This module provides a type-safe builder pattern for constructing
 //! aggregation queries with SUM, AVG, COUNT, MIN, MAX, GROUP BY, HAVING,
-//! ORDER BY, LIMIT/OFFSET, and JOIN support.
+//! ORDER BY, LIMIT/OFFSET, JOIN and window function support.
 
+use chrono::NaiveDateTime;
 use sqlx::Database;
 use std::marker::PhantomData;
 
-use crate::{get_or_insert_sql, prepare_where};
+use super::row::{AggCast, AggRow};
+use super::time_bucket::TimeBucket;
+use super::window::WindowFunction;
+use crate::filter::{Expr, IntoSubquery, Subquery, With};
+use crate::proxy::{BindParam, BindValue};
+use crate::{get_or_insert_sql, Dialect};
 
 /// Type of SQL join.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +44,9 @@ pub struct Join {
 }
 
 /// Represents an aggregate function to apply to a column with optional alias.
+///
+/// Plain and table-qualified column names are quoted for the dialect; any
+/// other argument (e.g. `price * qty`) is used verbatim as an expression.
 #[derive(Debug, Clone, PartialEq)]
 pub enum AggregateFunction {
     Sum(String, Option<String>),      // (column, alias)
@@ -45,6 +54,188 @@ pub enum AggregateFunction {
     Count(Option<String>, Option<String>), // (column, alias) - None means COUNT(*)
     Min(String, Option<String>),      // (column, alias)
     Max(String, Option<String>),      // (column, alias)
+    CountDistinct(String, Option<String>), // (column, alias)
+    StringAgg(String, String, Option<String>), // (column, separator, alias)
+    ArrayAgg(String, Option<String>), // (column, alias)
+    JsonAgg(String, Option<String>),  // (column, alias)
+    BoolAnd(String, Option<String>),  // (column, alias)
+    BoolOr(String, Option<String>),   // (column, alias)
+    Stddev(String, Option<String>),   // (column, alias) - sample standard deviation
+    Variance(String, Option<String>), // (column, alias) - sample variance
+    Expr(String, Option<String>),     // (expression, alias) - rendered verbatim
+    Filtered(Box<AggregateFunction>, String), // (aggregate, condition with "{}" placeholders)
+}
+
+impl AggregateFunction {
+    fn alias(&self) -> Option<&str> {
+        match self {
+            AggregateFunction::Sum(_, alias)
+            | AggregateFunction::Avg(_, alias)
+            | AggregateFunction::Count(_, alias)
+            | AggregateFunction::Min(_, alias)
+            | AggregateFunction::Max(_, alias)
+            | AggregateFunction::CountDistinct(_, alias)
+            | AggregateFunction::StringAgg(_, _, alias)
+            | AggregateFunction::ArrayAgg(_, alias)
+            | AggregateFunction::JsonAgg(_, alias)
+            | AggregateFunction::BoolAnd(_, alias)
+            | AggregateFunction::BoolOr(_, alias)
+            | AggregateFunction::Stddev(_, alias)
+            | AggregateFunction::Variance(_, alias)
+            | AggregateFunction::Expr(_, alias) => alias.as_deref(),
+            AggregateFunction::Filtered(aggregate, _) => aggregate.alias(),
+        }
+    }
+
+    /// Renders the select-list entry, with "{}" for the FILTER condition's
+    /// parameters (once per occurrence of the condition).
+    fn to_sql(&self, dialect: Dialect) -> String {
+        let expr = self.expr_sql(dialect);
+        match self.alias() {
+            Some(alias) => format!("{} AS {}", expr, alias),
+            None => expr,
+        }
+    }
+
+    /// Renders the select-list entry without its alias.
+    fn expr_sql(&self, dialect: Dialect) -> String {
+        match self {
+            AggregateFunction::Filtered(aggregate, condition) => aggregate.call_sql(dialect, Some(condition)),
+            aggregate => aggregate.call_sql(dialect, None),
+        }
+    }
+
+    /// Renders the aggregate call, restricted to the rows matching
+    /// `condition` if any: `FILTER (WHERE ...)` where supported, a `CASE`
+    /// around the argument otherwise.
+    fn call_sql(&self, dialect: Dialect, condition: Option<&str>) -> String {
+        let call = |func: &str, distinct: bool, arg: &str, tail: &str| {
+            let arg = match condition {
+                Some(cond) if !dialect.supports_aggregate_filter() => {
+                    format!("CASE WHEN {} THEN {} END", cond, if arg == "*" { "1" } else { arg })
+                }
+                _ => arg.to_string(),
+            };
+            let mut sql = format!("{}({}{}{})", func, if distinct { "DISTINCT " } else { "" }, arg, tail);
+            if let Some(cond) = condition.filter(|_| dialect.supports_aggregate_filter()) {
+                sql.push_str(&format!(" FILTER (WHERE {})", cond));
+            }
+            sql
+        };
+        let col = |column: &str| dialect.quote_column(column);
+        match self {
+            AggregateFunction::Sum(c, _) => call("SUM", false, &col(c), ""),
+            AggregateFunction::Avg(c, _) => call("AVG", false, &col(c), ""),
+            AggregateFunction::Count(None, _) => call("COUNT", false, "*", ""),
+            AggregateFunction::Count(Some(c), _) => call("COUNT", false, &col(c), ""),
+            AggregateFunction::Min(c, _) => call("MIN", false, &col(c), ""),
+            AggregateFunction::Max(c, _) => call("MAX", false, &col(c), ""),
+            AggregateFunction::CountDistinct(c, _) => call("COUNT", true, &col(c), ""),
+            AggregateFunction::StringAgg(c, separator, _) => {
+                let separator = format!("'{}'", separator.replace('\'', "''"));
+                match dialect {
+                    Dialect::PostgreSQL => call("STRING_AGG", false, &dialect.cast(&col(c), "TEXT"), &format!(", {}", separator)),
+                    Dialect::MySQL => call("GROUP_CONCAT", false, &col(c), &format!(" SEPARATOR {}", separator)),
+                    Dialect::SQLite => call("GROUP_CONCAT", false, &col(c), &format!(", {}", separator)),
+                }
+            }
+            AggregateFunction::ArrayAgg(c, _) if dialect == Dialect::PostgreSQL => call("ARRAY_AGG", false, &col(c), ""),
+            AggregateFunction::ArrayAgg(c, _) | AggregateFunction::JsonAgg(c, _) => match dialect {
+                Dialect::PostgreSQL => call("JSON_AGG", false, &col(c), ""),
+                Dialect::MySQL => call("JSON_ARRAYAGG", false, &col(c), ""),
+                Dialect::SQLite => call("JSON_GROUP_ARRAY", false, &col(c), ""),
+            },
+            // Booleans are stored as 0/1 on MySQL and SQLite
+            AggregateFunction::BoolAnd(c, _) if dialect == Dialect::PostgreSQL => call("BOOL_AND", false, &col(c), ""),
+            AggregateFunction::BoolAnd(c, _) => call("MIN", false, &col(c), ""),
+            AggregateFunction::BoolOr(c, _) if dialect == Dialect::PostgreSQL => call("BOOL_OR", false, &col(c), ""),
+            AggregateFunction::BoolOr(c, _) => call("MAX", false, &col(c), ""),
+            AggregateFunction::Stddev(c, _) | AggregateFunction::Variance(c, _) if dialect != Dialect::SQLite => {
+                let func = if matches!(self, AggregateFunction::Stddev(..)) { "STDDEV_SAMP" } else { "VAR_SAMP" };
+                call(func, false, &col(c), "")
+            }
+            // SQLite has no variance functions: computed from sums
+            AggregateFunction::Stddev(c, _) | AggregateFunction::Variance(c, _) => {
+                let c = col(c);
+                let variance = format!(
+                    "(({} - {} * {} * 1.0 / {}) / ({} - 1))",
+                    call("SUM", false, &format!("{} * {}", c, c), ""),
+                    call("SUM", false, &c, ""),
+                    call("SUM", false, &c, ""),
+                    call("COUNT", false, &c, ""),
+                    call("COUNT", false, &c, ""),
+                );
+                if matches!(self, AggregateFunction::Stddev(..)) {
+                    format!("SQRT{}", variance)
+                } else {
+                    variance
+                }
+            }
+            AggregateFunction::Expr(expr, _) => expr.clone(),
+            AggregateFunction::Filtered(aggregate, _) => aggregate.call_sql(dialect, condition),
+        }
+    }
+}
+
+/// The name of a selected column in the result: `orders.amount` is `amount`.
+fn output_name(column: &str) -> &str {
+    column.rsplit('.').next().unwrap_or(column)
+}
+
+/// A ROLLUP, CUBE or GROUPING SETS clause of GROUP BY.
+#[derive(Debug, Clone, PartialEq)]
+enum GroupingSets {
+    Rollup(Vec<String>),
+    Cube(Vec<String>),
+    Sets(Vec<Vec<String>>),
+}
+
+impl GroupingSets {
+    /// The distinct columns of all sets, in order of appearance.
+    fn columns(&self) -> Vec<&String> {
+        let mut columns = Vec::new();
+        let all: Vec<&String> = match self {
+            GroupingSets::Rollup(cols) | GroupingSets::Cube(cols) => cols.iter().collect(),
+            GroupingSets::Sets(sets) => sets.iter().flatten().collect(),
+        };
+        for col in all {
+            if !columns.contains(&col) {
+                columns.push(col);
+            }
+        }
+        columns
+    }
+
+    /// The individual grouping sets, most detailed first.
+    fn sets(&self) -> Vec<Vec<String>> {
+        match self {
+            GroupingSets::Rollup(cols) => (0..=cols.len()).rev().map(|n| cols[..n].to_vec()).collect(),
+            GroupingSets::Cube(cols) => (0..1usize << cols.len())
+                .rev()
+                .map(|mask| {
+                    cols.iter()
+                        .enumerate()
+                        .filter(|(i, _)| mask & (1 << (cols.len() - 1 - i)) != 0)
+                        .map(|(_, col)| col.clone())
+                        .collect()
+                })
+                .collect(),
+            GroupingSets::Sets(sets) => sets.clone(),
+        }
+    }
+
+    /// Renders the native GROUP BY element.
+    fn to_sql(&self, dialect: Dialect) -> String {
+        let list = |cols: &[String]| cols.iter().map(|col| dialect.quote_column(col)).collect::<Vec<_>>().join(", ");
+        match self {
+            GroupingSets::Rollup(cols) => format!("ROLLUP ({})", list(cols)),
+            GroupingSets::Cube(cols) => format!("CUBE ({})", list(cols)),
+            GroupingSets::Sets(sets) => {
+                let sets: Vec<String> = sets.iter().map(|set| format!("({})", list(set))).collect();
+                format!("GROUPING SETS ({})", sets.join(", "))
+            }
+        }
+    }
 }
 
 /// Fluent query builder for aggregation queries.
@@ -70,7 +261,7 @@ pub enum AggregateFunction {
 ///
 /// // GROUP BY with HAVING, ORDER BY, LIMIT
 /// let results: Vec<(String, i64)> = Order::agg_query()
-///     .where_("status = {}", &["active"])
+///     .where_("status = {}", &[&"active"])
 ///     .group_by("category")
 ///     .sum_as("amount", "total")
 ///     .having("total > {}", &[&1000i64])
@@ -91,16 +282,34 @@ pub enum AggregateFunction {
 /// ```
 pub struct AggQueryBuilder<'a, DB: Database> {
     table_name: String,
+    /// Parameters of a `from_subquery` source
+    from_params: Vec<BindValue<DB>>,
+    with: With<DB>,
     joins: Vec<Join>,
     aggregates: Vec<AggregateFunction>,
     group_by_columns: Vec<String>,
+    /// (column, bucket, time zone) of `group_by_time`
+    time_bucket: Option<(String, TimeBucket, String)>,
+    fill_gaps: bool,
+    grouping_sets: Option<GroupingSets>,
+    grouping_indicators: Vec<(String, String)>,
+    columns: Vec<String>,
+    windows: Vec<(String, WindowFunction)>,
     where_clause: Option<String>,
-    where_params: Vec<String>,
+    where_params: Vec<BindValue<DB>>,
     having_clause: Option<String>,
-    having_params: Vec<String>,
+    having_params: Vec<BindValue<DB>>,
+    /// FILTER parameters by aggregate index
+    filter_params: Vec<(usize, Vec<BindValue<DB>>)>,
+    window_where_clause: Option<String>,
+    window_where_params: Vec<BindValue<DB>>,
+    /// Casts of the `AggRow` fields being fetched into
+    row_casts: Vec<(String, AggCast)>,
     order_by_clause: Option<String>,
     limit: Option<usize>,
     offset: Option<usize>,
+    /// Why the query cannot be run, reported by `check` and `fetch_*`
+    problem: Option<String>,
     _phantom: PhantomData<&'a DB>,
 }
 
@@ -109,20 +318,85 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
     pub fn new(table_name: String) -> Self {
         Self {
             table_name,
+            from_params: Vec::new(),
+            with: With::default(),
             joins: Vec::new(),
             aggregates: Vec::new(),
             group_by_columns: Vec::new(),
+            time_bucket: None,
+            fill_gaps: false,
+            grouping_sets: None,
+            grouping_indicators: Vec::new(),
+            columns: Vec::new(),
+            windows: Vec::new(),
             where_clause: None,
             where_params: Vec::new(),
             having_clause: None,
             having_params: Vec::new(),
+            filter_params: Vec::new(),
+            window_where_clause: None,
+            window_where_params: Vec::new(),
+            row_casts: Vec::new(),
             order_by_clause: None,
             limit: None,
             offset: None,
+            problem: None,
             _phantom: PhantomData,
         }
     }
 
+    /// Creates an aggregation over the rows of `query`, a derived table
+    /// named `alias`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Average number of orders per customer
+    /// let per_customer = Order::agg_query().group_by("customer_id").count_as("orders");
+    /// let (avg,): (Option<f64>,) = AggQueryBuilder::from_subquery("per_customer", per_customer)
+    ///     .avg("orders")
+    ///     .fetch_one(&pool)
+    ///     .await?;
+    /// ```
+    pub fn from_subquery<Q: IntoSubquery<DB>>(alias: &str, query: Q) -> Self {
+        let query = query.into_subquery();
+        let problem = query.problem().map(|problem| format!("invalid subquery: {}", problem));
+        let (sql, params) = query.into_parts();
+        let table_name = format!("({}) AS {}", sql, Dialect::current().quote_identifier(alias));
+        Self { from_params: params, problem, ..Self::new(table_name) }
+    }
+
+    /// Adds a common table expression `name AS (query)` to the WITH clause,
+    /// to aggregate over or join.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recent = Sale::select().filter(Sale::CREATED_AT.gt(since));
+    /// let totals: Vec<(String, i64)> = AggQueryBuilder::new("recent".to_string())
+    ///     .with("recent", recent)
+    ///     .group_by("category")
+    ///     .count()
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn with<Q: IntoSubquery<DB>>(mut self, name: &str, query: Q) -> Self {
+        self.with.push(name, query.into_subquery());
+        self
+    }
+
+    /// Adds a recursive common table expression `name AS (anchor UNION step)`,
+    /// where `step` joins the table to `name`. Rows already produced are not
+    /// expanded again, so cycles end the recursion.
+    pub fn with_recursive<A, S>(mut self, name: &str, anchor: A, step: S) -> Self
+    where
+        A: IntoSubquery<DB>,
+        S: IntoSubquery<DB>,
+    {
+        self.with.push_recursive(name, anchor.into_subquery(), step.into_subquery());
+        self
+    }
+
     /// Adds an INNER JOIN with the specified table and condition.
     ///
     /// # Arguments
@@ -267,24 +541,337 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
         self
     }
 
+    /// Adds a COUNT(DISTINCT column) aggregation.
+    pub fn count_distinct(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::CountDistinct(column.to_string(), None));
+        self
+    }
+
+    /// Adds a COUNT(DISTINCT column) aggregation with a custom alias.
+    pub fn count_distinct_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::CountDistinct(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds a string concatenation of the column's values, joined by
+    /// `separator`: `STRING_AGG` on PostgreSQL, `GROUP_CONCAT` on MySQL and
+    /// SQLite.
+    pub fn string_agg(mut self, column: &str, separator: &str) -> Self {
+        self.aggregates.push(AggregateFunction::StringAgg(column.to_string(), separator.to_string(), None));
+        self
+    }
+
+    /// Adds a string concatenation with a custom alias.
+    pub fn string_agg_as(mut self, column: &str, separator: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::StringAgg(column.to_string(), separator.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds an `ARRAY_AGG` of the column's values.
+    ///
+    /// MySQL and SQLite have no array type and return a JSON array, like
+    /// [`json_agg`](Self::json_agg).
+    pub fn array_agg(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::ArrayAgg(column.to_string(), None));
+        self
+    }
+
+    /// Adds an `ARRAY_AGG` with a custom alias.
+    pub fn array_agg_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::ArrayAgg(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds a JSON array of the column's values: `JSON_AGG` on PostgreSQL,
+    /// `JSON_ARRAYAGG` on MySQL, `JSON_GROUP_ARRAY` on SQLite.
+    pub fn json_agg(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::JsonAgg(column.to_string(), None));
+        self
+    }
+
+    /// Adds a JSON array aggregation with a custom alias.
+    pub fn json_agg_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::JsonAgg(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds a `BOOL_AND`: true if the column is true on every row.
+    ///
+    /// Computed as `MIN` on MySQL and SQLite, which return 0/1.
+    pub fn bool_and(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::BoolAnd(column.to_string(), None));
+        self
+    }
+
+    /// Adds a `BOOL_AND` with a custom alias.
+    pub fn bool_and_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::BoolAnd(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds a `BOOL_OR`: true if the column is true on any row.
+    ///
+    /// Computed as `MAX` on MySQL and SQLite, which return 0/1.
+    pub fn bool_or(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::BoolOr(column.to_string(), None));
+        self
+    }
+
+    /// Adds a `BOOL_OR` with a custom alias.
+    pub fn bool_or_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::BoolOr(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds the sample standard deviation of the column (`STDDEV_SAMP`).
+    ///
+    /// SQLite computes it from sums and needs its math functions for `SQRT`.
+    pub fn stddev(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::Stddev(column.to_string(), None));
+        self
+    }
+
+    /// Adds a sample standard deviation with a custom alias.
+    pub fn stddev_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::Stddev(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds the sample variance of the column (`VAR_SAMP`, computed from sums
+    /// on SQLite).
+    pub fn variance(mut self, column: &str) -> Self {
+        self.aggregates.push(AggregateFunction::Variance(column.to_string(), None));
+        self
+    }
+
+    /// Adds a sample variance with a custom alias.
+    pub fn variance_as(mut self, column: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::Variance(column.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Adds an arbitrary SQL expression to the select list, used verbatim.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .expr_as("SUM(price * qty) / COUNT(DISTINCT order_id)", "avg_basket")
+    /// ```
+    pub fn expr_as(mut self, expression: &str, alias: &str) -> Self {
+        self.aggregates.push(AggregateFunction::Expr(expression.to_string(), Some(alias.to_string())));
+        self
+    }
+
+    /// Restricts the most recently added aggregate to the rows matching
+    /// `clause`, with "{}" placeholders bound like [`where_`](Self::where_).
+    ///
+    /// Renders `FILTER (WHERE ...)` on PostgreSQL and SQLite and a `CASE`
+    /// around the argument on MySQL. Calling it again on the same aggregate
+    /// ANDs the conditions.
+    ///
+    /// If no aggregate function was added yet, or the last one is an
+    /// [`expr_as`](Self::expr_as) expression, the clause is dropped and
+    /// [`check`](Self::check) and `fetch_*` fail with `Error::Configuration`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .sum_as("amount", "total")
+    /// .sum_as("amount", "refunded")
+    /// .filter_where("status = {}", &[&"refunded"])
+    /// // PostgreSQL: SUM("amount") FILTER (WHERE status = $1) AS refunded
+    /// // MySQL:      SUM(CASE WHEN status = ? THEN `amount` END) AS refunded
+    /// ```
+    pub fn filter_where(mut self, clause: &str, params: &[&dyn BindParam<DB>]) -> Self {
+        let Some(aggregate) = self.aggregates.pop() else {
+            self.record_problem("filter_where() must follow an aggregate function".to_string());
+            return self;
+        };
+        let idx = self.aggregates.len();
+        let filtered = match aggregate {
+            AggregateFunction::Expr(..) => {
+                self.aggregates.push(aggregate);
+                self.record_problem("filter_where() applies to aggregate functions, not expr_as() expressions".to_string());
+                return self;
+            }
+            AggregateFunction::Filtered(aggregate, condition) => {
+                AggregateFunction::Filtered(aggregate, format!("({}) AND ({})", condition, clause))
+            }
+            aggregate => AggregateFunction::Filtered(Box::new(aggregate), clause.to_string()),
+        };
+        self.aggregates.push(filtered);
+        let params = params.iter().map(|p| p.to_bind_value());
+        match self.filter_params.last_mut() {
+            Some((last, existing)) if *last == idx => existing.extend(params),
+            _ => self.filter_params.push((idx, params.collect())),
+        }
+        self
+    }
+
     /// Adds a GROUP BY clause for the specified column.
     pub fn group_by(mut self, column: &str) -> Self {
         self.group_by_columns.push(column.to_string());
         self
     }
 
+    /// Groups by the start of the time bucket containing `column`, selected
+    /// first as `bucket`.
+    ///
+    /// `column` holds UTC timestamps without time zone and is converted to
+    /// `tz` before bucketing: a zone name such as `Europe/Berlin` on
+    /// PostgreSQL and MySQL, a modifier such as `+02:00` or `localtime` on
+    /// SQLite. The bucket is a plain timestamp (`NaiveDateTime`); pass
+    /// `"col AT TIME ZONE 'UTC'"` for a PostgreSQL `TIMESTAMPTZ` column. Renders
+    /// `date_trunc` on PostgreSQL, `DATE()`/`DATE_FORMAT` on MySQL and
+    /// `strftime` on SQLite; weeks start on Monday.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .group_by_time("created_at", TimeBucket::Day, "UTC")
+    /// .sum_as("amount", "revenue")
+    /// ```
+    pub fn group_by_time(mut self, column: &str, bucket: TimeBucket, tz: &str) -> Self {
+        self.time_bucket = Some((column.to_string(), bucket, tz.to_string()));
+        self
+    }
+
+    /// Returns a row for every bucket between the first and last one of a
+    /// [`group_by_time`](Self::group_by_time) query, with zero counts and sums
+    /// (other aggregates NULL) where no rows fell into a bucket.
+    ///
+    /// PostgreSQL fills the gaps in SQL with `generate_series`, ordered by
+    /// bucket unless [`order_by`](Self::order_by) is set. Other databases fill
+    /// them in `fetch_series`, with `V::default()` as the empty value.
+    pub fn fill_gaps(mut self) -> Self {
+        self.fill_gaps = true;
+        self
+    }
+
+    /// Groups by `ROLLUP (columns)`: one row per prefix of `columns`, down
+    /// to a grand total, with NULL in the rolled-up columns.
+    ///
+    /// Renders `WITH ROLLUP` on MySQL and a UNION ALL of GROUP BYs on SQLite.
+    /// Columns from [`group_by`](Self::group_by) are kept in every set.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .group_by_rollup(&["region", "category"])
+    /// .sum_as("amount", "total")
+    /// // (region, category), (region) subtotals and the grand total
+    /// ```
+    pub fn group_by_rollup(mut self, columns: &[&str]) -> Self {
+        self.grouping_sets = Some(GroupingSets::Rollup(columns.iter().map(|c| c.to_string()).collect()));
+        self
+    }
+
+    /// Groups by `CUBE (columns)`: one row per subset of `columns`.
+    ///
+    /// Emulated with a UNION ALL of GROUP BYs on MySQL and SQLite.
+    pub fn group_by_cube(mut self, columns: &[&str]) -> Self {
+        self.grouping_sets = Some(GroupingSets::Cube(columns.iter().map(|c| c.to_string()).collect()));
+        self
+    }
+
+    /// Groups by the given `GROUPING SETS`; an empty set is the grand total.
+    ///
+    /// Emulated with a UNION ALL of GROUP BYs on MySQL and SQLite.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .grouping_sets(&[&["region"], &["category"], &[]])
+    /// ```
+    pub fn grouping_sets(mut self, sets: &[&[&str]]) -> Self {
+        let sets = sets.iter().map(|set| set.iter().map(|c| c.to_string()).collect()).collect();
+        self.grouping_sets = Some(GroupingSets::Sets(sets));
+        self
+    }
+
+    /// Selects `GROUPING(column) AS alias`: 1 on subtotal rows where `column`
+    /// was rolled up, 0 otherwise.
+    ///
+    /// Tells a subtotal apart from a group whose value is NULL.
+    pub fn grouping_as(mut self, column: &str, alias: &str) -> Self {
+        self.grouping_indicators.push((column.to_string(), alias.to_string()));
+        self
+    }
+
+    /// Selects a plain column without grouping by it, for window queries
+    /// over individual rows.
+    pub fn column(mut self, column: &str) -> Self {
+        self.columns.push(column.to_string());
+        self
+    }
+
+    /// Adds a window function, selected as `alias`.
+    ///
+    /// The alias is quoted, so names such as `rank` work on MySQL too.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use sqlx_struct_enhanced::aggregate::window::row_number;
+    ///
+    /// .window("rank", row_number().partition_by("customer_id").order_by("amount", Order::Desc))
+    /// ```
+    pub fn window(mut self, alias: &str, function: WindowFunction) -> Self {
+        self.windows.push((alias.to_string(), function));
+        self
+    }
+
+    /// Filters on window function results, with "{}" placeholders bound like
+    /// [`where_`](Self::where_).
+    ///
+    /// Window functions can't appear in WHERE or HAVING, so the query is
+    /// wrapped in a derived table and filtered outside it; ORDER BY, LIMIT and
+    /// OFFSET then apply to the outer query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .window("rn", row_number().partition_by("customer_id").order_by("amount", Order::Desc))
+    /// .where_window("rn <= {}", &[&3i64])
+    /// // SELECT * FROM (SELECT ..., ROW_NUMBER() OVER (...) AS "rn" FROM orders) AS windowed
+    /// // WHERE rn <= $1
+    /// ```
+    pub fn where_window(mut self, clause: &str, params: &[&dyn BindParam<DB>]) -> Self {
+        self.window_where_clause = Some(clause.to_string());
+        self.window_where_params = params.iter().map(|p| p.to_bind_value()).collect();
+        self
+    }
+
     /// Adds a WHERE clause with the given statement and parameters.
     ///
-    /// The statement should use "{}" as parameter placeholders.
+    /// The statement should use "{}" as parameter placeholders. Parameters are
+    /// bound with their native types, so numbers compare as numbers.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// .where_("status = {} AND amount > {}", &["active", "100"])
+    /// .where_("status = {} AND amount > {}", &[&"active", &100i64])
     /// ```
-    pub fn where_(mut self, clause: &str, params: &[&str]) -> Self {
+    pub fn where_(mut self, clause: &str, params: &[&dyn BindParam<DB>]) -> Self {
         self.where_clause = Some(clause.to_string());
-        self.where_params = params.iter().map(|s| s.to_string()).collect();
+        self.where_params = params.iter().map(|p| p.to_bind_value()).collect();
+        self
+    }
+
+    /// Sets the WHERE clause from a filter expression, binding its values natively.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .filter(Order::STATUS.eq("active").and(Order::AMOUNT.gt(100)))
+    /// ```
+    pub fn filter(mut self, expr: Expr<DB>) -> Self {
+        if let Some(problem) = expr.problem() {
+            self.record_problem(format!("invalid subquery: {}", problem));
+        }
+        let (clause, params) = expr.into_parts();
+        self.where_clause = Some(clause);
+        self.where_params = params;
         self
     }
 
@@ -299,9 +886,9 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
     /// .having("SUM(amount) > {}", &[&1000i64])
     /// .having("total > {}", &[&1000i64])  // When using sum_as("amount", "total")
     /// ```
-    pub fn having(mut self, clause: &str, params: &[&dyn std::fmt::Display]) -> Self {
+    pub fn having(mut self, clause: &str, params: &[&dyn BindParam<DB>]) -> Self {
         self.having_clause = Some(clause.to_string());
-        self.having_params = params.iter().map(|p| p.to_string()).collect();
+        self.having_params = params.iter().map(|p| p.to_bind_value()).collect();
         self
     }
 
@@ -352,70 +939,125 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
         self
     }
 
-    /// Builds and returns the SQL query as a string.
-    fn build_sql(&self) -> String {
+    /// Builds and returns the SQL query as a string for `dialect`.
+    fn build_sql(&self, dialect: Dialect) -> String {
+        dialect.placeholders(&self.template_sql(dialect), 1)
+    }
+
+    /// Builds the SQL query for `dialect` with "{}" placeholders, which
+    /// appear in the order of [`params`](Self::params) followed by LIMIT and
+    /// OFFSET.
+    fn template_sql(&self, dialect: Dialect) -> String {
+        // Grouping sets the dialect lacks become one SELECT per set
+        let mut sql = match self.emulated_grouping_sets(dialect) {
+            Some(sets) => {
+                let branches: Vec<String> = sets.iter().map(|set| self.select_sql(dialect, Some(set))).collect();
+                branches.join(" UNION ALL ")
+            }
+            None => self.select_sql(dialect, None),
+        };
+
+        let fill_gaps = match self.time_bucket {
+            Some((_, bucket, _)) if self.fill_gaps && dialect == Dialect::PostgreSQL => Some(bucket),
+            _ => None,
+        };
+        let mut fill_gaps_cte = None;
+        if let Some(bucket) = fill_gaps {
+            let (cte, filled) = self.fill_gaps_sql(dialect, &sql, bucket);
+            fill_gaps_cte = Some(cte);
+            sql = filled;
+        }
+
+        // Build ORDER BY clause
+        let order_by_clause = if let Some(ref clause) = self.order_by_clause {
+            format!("ORDER BY {}", clause)
+        } else if fill_gaps.is_some() {
+            format!("ORDER BY {}", dialect.quote_identifier("bucket"))
+        } else {
+            String::new()
+        };
+
+        // The window filter is bound after the HAVING parameters
+        if let Some(ref clause) = self.window_where_clause {
+            sql = format!("SELECT * FROM ({}) AS windowed WHERE {}", sql, clause);
+        }
+        if !order_by_clause.is_empty() {
+            sql.push_str(" ");
+            sql.push_str(&order_by_clause);
+        }
+
+        // LIMIT/OFFSET are bound after all other parameters
+        sql.push_str(dialect.limit_offset(self.limit.is_some(), self.offset.is_some()));
+
+        // The WITH clause comes first, with the gap-filling CTE after the user's
+        format!("{}{}", self.with.sql(fill_gaps_cte.as_deref()), sql)
+    }
+
+    /// Builds the SELECT ... HAVING part.
+    ///
+    /// `branch` is the grouping set of one SELECT in an emulated ROLLUP, CUBE
+    /// or GROUPING SETS query; columns outside it are selected as NULL.
+    fn select_sql(&self, dialect: Dialect, branch: Option<&[String]>) -> String {
         // Build SELECT clause
         let mut select_parts = Vec::new();
 
+        // Casts requested by fetch_all_as wrap an expression and keep its name
+        let select = |expr: String, name: &str, alias: Option<&str>| {
+            let cast = self.row_casts.iter().find(|(field, _)| field == name).and_then(|(_, cast)| cast.sql_type(dialect));
+            match (cast, alias) {
+                (Some(sql_type), _) => {
+                    let alias = alias.map_or_else(|| dialect.quote_identifier(name), str::to_string);
+                    format!("{} AS {}", dialect.cast(&format!("({})", expr), sql_type), alias)
+                }
+                (None, Some(alias)) => format!("{} AS {}", expr, alias),
+                (None, None) => expr,
+            }
+        };
+
         // Add GROUP BY columns first
+        let bucket = self.time_bucket.as_ref().map(|(col, bucket, tz)| bucket.to_sql(dialect, col, tz));
+        if let Some(ref bucket) = bucket {
+            select_parts.push(select(bucket.clone(), "bucket", Some(&dialect.quote_identifier("bucket"))));
+        }
         for col in &self.group_by_columns {
-            select_parts.push(col.clone());
+            select_parts.push(select(dialect.quote_column(col), output_name(col), None));
+        }
+        if let Some(ref grouping_sets) = self.grouping_sets {
+            for col in grouping_sets.columns() {
+                let name = output_name(col);
+                match branch {
+                    Some(set) if !set.contains(col) => {
+                        select_parts.push(select("NULL".to_string(), name, Some(&dialect.quote_identifier(name))));
+                    }
+                    _ => select_parts.push(select(dialect.quote_column(col), name, None)),
+                }
+            }
+        }
+
+        for col in &self.columns {
+            select_parts.push(select(dialect.quote_column(col), output_name(col), None));
+        }
+
+        // GROUPING() is 1 on rows where the column was rolled up
+        for (col, alias) in &self.grouping_indicators {
+            let expr = match branch {
+                Some(set) => if set.contains(col) { "0" } else { "1" }.to_string(),
+                None => format!("GROUPING({})", dialect.quote_column(col)),
+            };
+            select_parts.push(select(expr, alias, Some(alias)));
         }
 
         // Add aggregate functions
         for agg in &self.aggregates {
-            match agg {
-                AggregateFunction::Sum(col, alias) => {
-                    let expr = format!("SUM({})", col);
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-                AggregateFunction::Avg(col, alias) => {
-                    let expr = format!("AVG({})", col);
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-                AggregateFunction::Count(None, alias) => {
-                    let expr = "COUNT(*)".to_string();
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-                AggregateFunction::Count(Some(col), alias) => {
-                    let expr = format!("COUNT({})", col);
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-                AggregateFunction::Min(col, alias) => {
-                    let expr = format!("MIN({})", col);
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-                AggregateFunction::Max(col, alias) => {
-                    let expr = format!("MAX({})", col);
-                    select_parts.push(if let Some(a) = alias {
-                        format!("{} AS {}", expr, a)
-                    } else {
-                        expr
-                    });
-                }
-            }
+            select_parts.push(select(agg.expr_sql(dialect), agg.alias().unwrap_or_default(), agg.alias()));
+        }
+
+        // Window functions are evaluated after grouping
+        for (alias, window) in &self.windows {
+            select_parts.push(select(window.to_sql(dialect), alias, Some(&dialect.quote_identifier(alias))));
         }
 
+        // FILTER parameters come first, then the FROM subquery, WHERE and HAVING
         let select_clause = select_parts.join(", ");
 
         // Build FROM and JOIN clauses
@@ -426,51 +1068,33 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
 
         // Build WHERE clause
         let where_clause = if let Some(ref clause) = self.where_clause {
-            let prepared = prepare_where(clause, 1);
-            format!("WHERE {}", prepared)
+            format!("WHERE {}", clause)
         } else {
             String::new()
         };
 
         // Build GROUP BY clause
-        let group_by_clause = if !self.group_by_columns.is_empty() {
-            format!("GROUP BY {}", self.group_by_columns.join(", "))
+        let mut group_by: Vec<String> = bucket.into_iter().collect();
+        group_by.extend(self.group_by_columns.iter().map(|col| dialect.quote_column(col)));
+        let mut with_rollup = false;
+        match (&self.grouping_sets, branch) {
+            (Some(_), Some(set)) => group_by.extend(set.iter().map(|col| dialect.quote_column(col))),
+            (Some(GroupingSets::Rollup(columns)), None) if dialect == Dialect::MySQL => {
+                group_by.extend(columns.iter().map(|col| dialect.quote_column(col)));
+                with_rollup = true;
+            }
+            (Some(grouping_sets), None) => group_by.push(grouping_sets.to_sql(dialect)),
+            (None, _) => {}
+        }
+        let group_by_clause = if !group_by.is_empty() {
+            format!("GROUP BY {}{}", group_by.join(", "), if with_rollup { " WITH ROLLUP" } else { "" })
         } else {
             String::new()
         };
 
         // Build HAVING clause
-        let mut param_offset = 1 + self.where_params.len();
         let having_clause = if let Some(ref clause) = self.having_clause {
-            let prepared = prepare_where(clause, param_offset as i32);
-            format!("HAVING {}", prepared)
-        } else {
-            String::new()
-        };
-
-        // Build ORDER BY clause
-        let order_by_clause = if let Some(ref clause) = self.order_by_clause {
-            format!("ORDER BY {}", clause)
-        } else {
-            String::new()
-        };
-
-        // Build LIMIT clause
-        let limit_clause = if let Some(_n) = self.limit {
-            param_offset += self.having_params.len();
-            format!("LIMIT ${}", param_offset)
-        } else {
-            String::new()
-        };
-
-        // Build OFFSET clause
-        let offset_clause = if let Some(_n) = self.offset {
-            if self.limit.is_some() {
-                param_offset += 1;
-            } else {
-                param_offset += self.having_params.len();
-            }
-            format!("OFFSET ${}", param_offset)
+            format!("HAVING {}", clause)
         } else {
             String::new()
         };
@@ -485,42 +1109,199 @@ impl<'a, DB: Database> AggQueryBuilder<'a, DB> {
             sql.push_str(" ");
             sql.push_str(&group_by_clause);
         }
-        if !having_clause.is_empty() {
-            sql.push_str(" ");
-            sql.push_str(&having_clause);
+        if !having_clause.is_empty() {
+            sql.push_str(" ");
+            sql.push_str(&having_clause);
+        }
+
+        sql
+    }
+
+    /// Joins the grouped query `sql` to every bucket between its first and
+    /// last one, selecting the same columns with zero counts and sums for the
+    /// empty buckets. Returns the CTE holding `sql` and the joining SELECT.
+    fn fill_gaps_sql(&self, dialect: Dialect, sql: &str, bucket: TimeBucket) -> (String, String) {
+        let q = |column: &str| format!("q.{}", dialect.quote_identifier(column));
+        let mut cte_columns = vec![dialect.quote_identifier("bucket")];
+        let mut outer = vec![format!("buckets.{} AS {}", cte_columns[0], cte_columns[0])];
+        let mut next_column = |outer_sql: &dyn Fn(String) -> String| {
+            let column = format!("c{}", cte_columns.len());
+            cte_columns.push(dialect.quote_identifier(&column));
+            outer.push(outer_sql(q(&column)));
+        };
+
+        let grouping_columns = self.grouping_sets.as_ref().map(|sets| sets.columns()).unwrap_or_default();
+        for col in self.group_by_columns.iter().chain(grouping_columns).chain(&self.columns) {
+            let name = dialect.quote_identifier(output_name(col));
+            next_column(&|value| format!("{} AS {}", value, name));
+        }
+        for (_, alias) in &self.grouping_indicators {
+            next_column(&|value| format!("{} AS {}", value, alias));
+        }
+        for agg in &self.aggregates {
+            let zero_when_empty = matches!(
+                agg,
+                AggregateFunction::Sum(..) | AggregateFunction::Count(..) | AggregateFunction::CountDistinct(..)
+            );
+            next_column(&|value| {
+                let value = if zero_when_empty { format!("COALESCE({}, 0)", value) } else { value };
+                match agg.alias() {
+                    Some(alias) => format!("{} AS {}", value, alias),
+                    None => value,
+                }
+            });
+        }
+        for (alias, _) in &self.windows {
+            next_column(&|value| format!("{} AS {}", value, dialect.quote_identifier(alias)));
+        }
+
+        let bucket_column = dialect.quote_identifier("bucket");
+        let cte = format!("q ({}) AS ({})", cte_columns.join(", "), sql);
+        let filled = format!(
+            "SELECT {} FROM generate_series((SELECT MIN({}) FROM q), (SELECT MAX({}) FROM q), {}) AS buckets ({}) LEFT JOIN q ON q.{} = buckets.{}",
+            outer.join(", "),
+            bucket_column,
+            bucket_column,
+            bucket.interval(),
+            bucket_column,
+            bucket_column,
+            bucket_column,
+        );
+        (cte, filled)
+    }
+
+    /// The grouping sets to emulate with UNION ALL, if `dialect` can't
+    /// express the requested grouping natively: SQLite has none of ROLLUP,
+    /// CUBE and GROUPING SETS, MySQL only `WITH ROLLUP`.
+    fn emulated_grouping_sets(&self, dialect: Dialect) -> Option<Vec<Vec<String>>> {
+        match (&self.grouping_sets, dialect) {
+            (None, _) | (_, Dialect::PostgreSQL) | (Some(GroupingSets::Rollup(_)), Dialect::MySQL) => None,
+            (Some(grouping_sets), _) => Some(grouping_sets.sets()),
+        }
+    }
+
+    /// The parameters in binding order: the WITH clause, then aggregate FILTER
+    /// conditions (repeated wherever `dialect` renders a condition more than
+    /// once), the FROM subquery, WHERE and HAVING for each SELECT, then the
+    /// window filter.
+    fn params(&self, dialect: Dialect) -> Vec<BindValue<DB>> {
+        let mut select_params = Vec::new();
+        for (idx, filter_params) in &self.filter_params {
+            if filter_params.is_empty() {
+                continue;
+            }
+            let occurrences = self.aggregates[*idx].to_sql(dialect).matches("{}").count() / filter_params.len();
+            for _ in 0..occurrences {
+                select_params.extend(filter_params.iter().cloned());
+            }
         }
-        if !order_by_clause.is_empty() {
-            sql.push_str(" ");
-            sql.push_str(&order_by_clause);
+        select_params.extend(self.from_params.iter().cloned());
+        select_params.extend(self.where_params.iter().cloned());
+        select_params.extend(self.having_params.iter().cloned());
+
+        let selects = self.emulated_grouping_sets(dialect).map_or(1, |sets| sets.len());
+        let mut params = self.with.params().to_vec();
+        for _ in 0..selects {
+            params.extend(select_params.iter().cloned());
         }
-        if !limit_clause.is_empty() {
-            sql.push_str(" ");
-            sql.push_str(&limit_clause);
+        params.extend(self.window_where_params.iter().cloned());
+        params
+    }
+
+    /// The names of the selected expressions that `T`'s fields map to.
+    fn output_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        if self.time_bucket.is_some() {
+            names.push("bucket");
         }
-        if !offset_clause.is_empty() {
-            sql.push_str(" ");
-            sql.push_str(&offset_clause);
+        let grouping_columns = self.grouping_sets.as_ref().map(|sets| sets.columns()).unwrap_or_default();
+        for col in self.group_by_columns.iter().chain(grouping_columns).chain(&self.columns) {
+            names.push(output_name(col));
+        }
+        names.extend(self.grouping_indicators.iter().map(|(_, alias)| alias.as_str()));
+        names.extend(self.aggregates.iter().filter_map(|agg| agg.alias()));
+        names.extend(self.windows.iter().map(|(alias, _)| alias.as_str()));
+        names
+    }
+
+    /// Prepares the query for fetching into `T`, casting the matched
+    /// expressions to the fields' types.
+    ///
+    /// Fails if a field of `T` has no select expression of the same name.
+    fn for_row<T: AggRow>(mut self) -> Result<Self, sqlx::Error> {
+        let names = self.output_names();
+        if let Some((field, _)) = T::FIELDS.iter().find(|(field, _)| !names.contains(field)) {
+            return Err(sqlx::Error::Configuration(
+                format!("field `{}` has no matching column or alias in the aggregation query", field).into(),
+            ));
+        }
+        self.row_casts = T::FIELDS
+            .iter()
+            .filter_map(|(field, cast)| cast.map(|cast| (field.to_string(), cast)))
+            .collect();
+        Ok(self)
+    }
+
+    /// Fails with `Error::Configuration` if the query cannot be run, e.g.
+    /// because a nested query is invalid. `fetch_*` check this before
+    /// running the query; call it before executing the SQL of
+    /// [`build`](Self::build) yourself.
+    pub fn check(&self) -> Result<(), sqlx::Error> {
+        match self.problem() {
+            Some(problem) => Err(sqlx::Error::Configuration(problem.into())),
+            None => Ok(()),
         }
+    }
 
-        sql
+    fn problem(&self) -> Option<String> {
+        self.problem
+            .clone()
+            .or_else(|| self.with.problem().map(|problem| format!("invalid subquery: {}", problem)))
+    }
+
+    /// Keeps the first problem recorded, which `check` reports.
+    fn record_problem(&mut self, problem: String) {
+        if self.problem.is_none() {
+            self.problem = Some(problem);
+        }
     }
 
     /// Builds the query and returns a cached SQL string.
     pub fn build(&self) -> &'static str {
         let cache_key = format!(
-            "{}-agg-joins-{:?}-{:?}-groupby-{:?}-where-{:?}-having-{:?}-orderby-{:?}-limit-{:?}-offset-{:?}",
+            "{}{}-agg-joins-{:?}-{:?}-groupby-{:?}-time-{:?}-{:?}-{:?}-grouping-{:?}-columns-{:?}-windows-{:?}-where-{:?}-having-{:?}-window-where-{:?}-casts-{:?}-orderby-{:?}-limit-{:?}-offset-{:?}",
+            self.with.sql(None),
             self.table_name,
             self.joins,
             self.aggregates,
             self.group_by_columns,
+            self.time_bucket,
+            self.fill_gaps,
+            self.grouping_sets,
+            self.grouping_indicators,
+            self.columns,
+            self.windows,
             self.where_clause,
             self.having_clause,
+            self.window_where_clause,
+            self.row_casts,
             self.order_by_clause,
             self.limit,
             self.offset
         );
 
-        get_or_insert_sql(cache_key, || self.build_sql())
+        get_or_insert_sql(cache_key, || self.build_sql(Dialect::current()))
+    }
+}
+
+/// Nests the query with its LIMIT and OFFSET bound as values.
+impl<'a, DB: Database> IntoSubquery<DB> for AggQueryBuilder<'a, DB> {
+    fn into_subquery(self) -> Subquery<DB> {
+        let dialect = Dialect::current();
+        let problem = self.problem();
+        let mut params = self.params(dialect);
+        params.extend(self.limit.into_iter().chain(self.offset).map(|n| BindValue::I64(n as i64)));
+        Subquery::raw(&self.template_sql(dialect), params).with_problem(problem)
     }
 }
 
@@ -549,17 +1330,13 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        // Bind WHERE parameters
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        // Bind HAVING parameters
-        for param in &self.having_params {
-            query = query.bind(param);
+        // Bind aggregate FILTER, WHERE and HAVING parameters
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         // Bind LIMIT parameter
@@ -593,15 +1370,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -615,6 +1389,79 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
         query.fetch_all(pool).await
     }
 
+    /// Execute query and return all rows as `T`, whose fields are matched to
+    /// the select expressions by name.
+    ///
+    /// Fails with [`sqlx::Error::Configuration`] before running the query if
+    /// a field has no matching column or alias.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(FromRow, AggRow)]
+    /// struct RevenueByCategory {
+    ///     category: String,
+    ///     total: Decimal,
+    /// }
+    ///
+    /// let rows = Order::agg_query()
+    ///     .group_by("category")
+    ///     .sum_as("amount", "total")
+    ///     .fetch_all_as::<RevenueByCategory>(&pool)
+    ///     .await?;
+    /// ```
+    pub async fn fetch_all_as<'e, T>(
+        self,
+        executor: impl sqlx::Executor<'e, Database = sqlx::Postgres>
+    ) -> Result<Vec<T>, sqlx::Error>
+    where
+        T: AggRow + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send + 'e,
+    {
+        self.check()?;
+        let builder = self.for_row::<T>()?;
+        let sql = builder.build();
+        let mut query = sqlx::query_as::<_, T>(sql);
+
+        for param in builder.params(Dialect::current()) {
+            query = param.bind_to(query);
+        }
+
+        if let Some(n) = builder.limit {
+            query = query.bind(n as i64);
+        }
+
+        if let Some(n) = builder.offset {
+            query = query.bind(n as i64);
+        }
+
+        query.fetch_all(executor).await
+    }
+
+    /// Execute a [`group_by_time`](Self::group_by_time) query and return its
+    /// buckets with the first value column.
+    ///
+    /// Gaps requested with [`fill_gaps`](Self::fill_gaps) are filled in SQL.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let revenue: Vec<(NaiveDateTime, i64)> = Order::agg_query()
+    ///     .group_by_time("created_at", TimeBucket::Day, "UTC")
+    ///     .sum_as("amount", "revenue")
+    ///     .fill_gaps()
+    ///     .fetch_series(&pool)
+    ///     .await?;
+    /// ```
+    pub async fn fetch_series<V>(
+        self,
+        pool: &sqlx::PgPool
+    ) -> Result<Vec<(NaiveDateTime, V)>, sqlx::Error>
+    where
+        V: for<'r> sqlx::Decode<'r, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Default + Unpin + Send,
+    {
+        self.fetch_all(pool).await
+    }
+
     /// Execute query and return optional result.
     ///
     /// # Example
@@ -633,15 +1480,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -669,15 +1513,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
         self,
         pool: &sqlx::PgPool
     ) -> Result<i64, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (i64,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -706,15 +1547,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
         self,
         pool: &sqlx::PgPool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -743,15 +1581,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Postgres> {
         self,
         pool: &sqlx::PgPool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -779,15 +1614,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -809,15 +1641,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -831,6 +1660,53 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
         query.fetch_all(pool).await
     }
 
+    /// Execute query and return all rows as `T`, whose fields are matched to
+    /// the select expressions by name.
+    pub async fn fetch_all_as<'e, T>(
+        self,
+        executor: impl sqlx::Executor<'e, Database = sqlx::MySql>
+    ) -> Result<Vec<T>, sqlx::Error>
+    where
+        T: AggRow + for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Unpin + Send + 'e,
+    {
+        self.check()?;
+        let builder = self.for_row::<T>()?;
+        let sql = builder.build();
+        let mut query = sqlx::query_as::<_, T>(sql);
+
+        for param in builder.params(Dialect::current()) {
+            query = param.bind_to(query);
+        }
+
+        if let Some(n) = builder.limit {
+            query = query.bind(n as u64);
+        }
+
+        if let Some(n) = builder.offset {
+            query = query.bind(n as u64);
+        }
+
+        query.fetch_all(executor).await
+    }
+
+    /// Execute a [`group_by_time`](Self::group_by_time) query and return its
+    /// buckets with the first value column, filling gaps requested with
+    /// [`fill_gaps`](Self::fill_gaps) with `V::default()`.
+    pub async fn fetch_series<V>(
+        self,
+        pool: &sqlx::MySqlPool
+    ) -> Result<Vec<(NaiveDateTime, V)>, sqlx::Error>
+    where
+        V: for<'r> sqlx::Decode<'r, sqlx::MySql> + sqlx::Type<sqlx::MySql> + Default + Unpin + Send,
+    {
+        let fill = self.time_bucket.as_ref().filter(|_| self.fill_gaps).map(|(_, bucket, _)| *bucket);
+        let rows = self.fetch_all(pool).await?;
+        Ok(match fill {
+            Some(bucket) => super::time_bucket::fill_gaps(rows, bucket),
+            None => rows,
+        })
+    }
+
     /// Execute query and return optional result.
     pub async fn fetch_optional<T>(
         self,
@@ -839,15 +1715,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -866,15 +1739,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
         self,
         pool: &sqlx::MySqlPool
     ) -> Result<i64, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (i64,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -894,15 +1764,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
         self,
         pool: &sqlx::MySqlPool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -922,15 +1789,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::MySql> {
         self,
         pool: &sqlx::MySqlPool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -958,15 +1822,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -988,15 +1849,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -1010,6 +1868,53 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
         query.fetch_all(pool).await
     }
 
+    /// Execute query and return all rows as `T`, whose fields are matched to
+    /// the select expressions by name.
+    pub async fn fetch_all_as<'e, T>(
+        self,
+        executor: impl sqlx::Executor<'e, Database = sqlx::Sqlite>
+    ) -> Result<Vec<T>, sqlx::Error>
+    where
+        T: AggRow + for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Unpin + Send + 'e,
+    {
+        self.check()?;
+        let builder = self.for_row::<T>()?;
+        let sql = builder.build();
+        let mut query = sqlx::query_as::<_, T>(sql);
+
+        for param in builder.params(Dialect::current()) {
+            query = param.bind_to(query);
+        }
+
+        if let Some(n) = builder.limit {
+            query = query.bind(n as i64);
+        }
+
+        if let Some(n) = builder.offset {
+            query = query.bind(n as i64);
+        }
+
+        query.fetch_all(executor).await
+    }
+
+    /// Execute a [`group_by_time`](Self::group_by_time) query and return its
+    /// buckets with the first value column, filling gaps requested with
+    /// [`fill_gaps`](Self::fill_gaps) with `V::default()`.
+    pub async fn fetch_series<V>(
+        self,
+        pool: &sqlx::SqlitePool
+    ) -> Result<Vec<(NaiveDateTime, V)>, sqlx::Error>
+    where
+        V: for<'r> sqlx::Decode<'r, sqlx::Sqlite> + sqlx::Type<sqlx::Sqlite> + Default + Unpin + Send,
+    {
+        let fill = self.time_bucket.as_ref().filter(|_| self.fill_gaps).map(|(_, bucket, _)| *bucket);
+        let rows = self.fetch_all(pool).await?;
+        Ok(match fill {
+            Some(bucket) => super::time_bucket::fill_gaps(rows, bucket),
+            None => rows,
+        })
+    }
+
     /// Execute query and return optional result.
     pub async fn fetch_optional<T>(
         self,
@@ -1018,15 +1923,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Unpin + Send,
     {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, T>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -1045,15 +1947,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
         self,
         pool: &sqlx::SqlitePool
     ) -> Result<i64, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (i64,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -1073,15 +1972,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
         self,
         pool: &sqlx::SqlitePool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -1101,15 +1997,12 @@ impl<'a> AggQueryBuilder<'a, sqlx::Sqlite> {
         self,
         pool: &sqlx::SqlitePool
     ) -> Result<Option<f64>, sqlx::Error> {
+        self.check()?;
         let sql = self.build();
         let mut query = sqlx::query_as::<_, (Option<f64>,)>(sql);
 
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        for param in &self.having_params {
-            query = query.bind(param);
+        for param in self.params(Dialect::current()) {
+            query = param.bind_to(query);
         }
 
         if let Some(n) = self.limit {
@@ -1135,8 +2028,8 @@ mod tests {
             .join("customers", "orders.customer_id = customers.id")
             .sum("orders.amount");
 
-        let sql = builder.build();
-        assert!(sql.contains("SELECT SUM(orders.amount) FROM orders INNER JOIN customers ON orders.customer_id = customers.id"));
+        let sql = builder.build_sql(Dialect::PostgreSQL);
+        assert!(sql.contains(r#"SELECT SUM("orders"."amount") FROM orders INNER JOIN customers ON orders.customer_id = customers.id"#));
     }
 
     #[test]
@@ -1146,8 +2039,8 @@ mod tests {
             .group_by("customers.region")
             .sum("orders.amount");
 
-        let sql = builder.build();
-        assert!(sql.contains("SELECT customers.region, SUM(orders.amount) FROM orders INNER JOIN customers ON orders.customer_id = customers.id GROUP BY customers.region"));
+        let sql = builder.build_sql(Dialect::PostgreSQL);
+        assert!(sql.contains(r#"SELECT "customers"."region", SUM("orders"."amount") FROM orders INNER JOIN customers ON orders.customer_id = customers.id GROUP BY "customers"."region""#));
     }
 
     #[test]
@@ -1170,17 +2063,17 @@ mod tests {
             .group_by("products.category")
             .sum("orders.amount");
 
-        let sql = builder.build();
+        let sql = builder.build_sql(Dialect::PostgreSQL);
         assert!(sql.contains("INNER JOIN customers"));
         assert!(sql.contains("INNER JOIN products"));
-        assert!(sql.contains("GROUP BY customers.region, products.category"));
+        assert!(sql.contains(r#"GROUP BY "customers"."region", "products"."category""#));
     }
 
     #[test]
     fn test_join_with_where() {
         let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
             .join("customers", "orders.customer_id = customers.id")
-            .where_("customers.status = {}", &["active"])
+            .where_("customers.status = {}", &[&"active"])
             .group_by("customers.region")
             .sum("orders.amount");
 
@@ -1209,7 +2102,7 @@ mod tests {
         let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
             .join("customers", "orders.customer_id = customers.id")
             .join_left("products", "orders.product_id = products.id")
-            .where_("customers.status = {} AND orders.amount > {}", &["active", "100"])
+            .where_("customers.status = {} AND orders.amount > {}", &[&"active", &100i64])
             .group_by("customers.region")
             .group_by("products.category")
             .sum_as("orders.amount", "total")
@@ -1223,13 +2116,423 @@ mod tests {
         assert!(sql.contains("INNER JOIN customers"));
         assert!(sql.contains("LEFT JOIN products"));
         assert!(sql.contains("WHERE customers.status = $1 AND orders.amount > $2"));
-        assert!(sql.contains("GROUP BY customers.region, products.category"));
+        assert!(sql.contains(r#"GROUP BY "customers"."region", "products"."category""#));
         assert!(sql.contains("HAVING total > $3"));
         assert!(sql.contains("ORDER BY total DESC"));
         assert!(sql.contains("LIMIT $4"));
         assert!(sql.contains("OFFSET $5"));
     }
 
+    #[test]
+    fn test_where_and_having_params_keep_native_types() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .where_("status = {} AND amount > {}", &[&"active", &100i64])
+            .group_by("status")
+            .sum_as("amount", "total")
+            .having("SUM(amount) > {} AND COUNT(*) > {}", &[&500i64, &2i32]);
+
+        assert!(matches!(builder.where_params[0], BindValue::String(ref s) if s == "active"));
+        assert!(matches!(builder.where_params[1], BindValue::I64(100)));
+        assert!(matches!(builder.having_params[0], BindValue::I64(500)));
+        assert!(matches!(builder.having_params[1], BindValue::I32(2)));
+        assert!(builder.build_sql(Dialect::PostgreSQL).contains("HAVING SUM(amount) > $3 AND COUNT(*) > $4"));
+    }
+
+    #[test]
+    fn test_placeholders_follow_the_dialect() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .where_("status = {}", &[&"active"])
+            .group_by("status")
+            .sum_as("amount", "total")
+            .having("SUM(amount) > {}", &[&500i64])
+            .limit(10)
+            .offset(20);
+        let tail = " WHERE status = {} GROUP BY {} HAVING SUM(amount) > {} LIMIT {} OFFSET {}";
+        for (dialect, params) in [
+            (Dialect::PostgreSQL, ["$1", "\"status\"", "$2", "$3", "$4"]),
+            (Dialect::MySQL, ["?", "`status`", "?", "?", "?"]),
+            (Dialect::SQLite, ["?", "status", "?", "?", "?"]),
+        ] {
+            let expected = params.iter().fold(tail.to_string(), |sql, p| sql.replacen("{}", p, 1));
+            assert_eq!(
+                builder.build_sql(dialect),
+                format!(
+                    "SELECT {}, SUM({}) AS total FROM orders{}",
+                    dialect.quote_identifier("status"),
+                    dialect.quote_identifier("amount"),
+                    expected
+                )
+            );
+        }
+
+        let offset_only = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .count()
+            .offset(5);
+        assert_eq!(offset_only.build_sql(Dialect::PostgreSQL), "SELECT COUNT(*) FROM orders OFFSET $1");
+        assert_eq!(offset_only.build_sql(Dialect::MySQL), "SELECT COUNT(*) FROM orders LIMIT 18446744073709551615 OFFSET ?");
+        assert_eq!(offset_only.build_sql(Dialect::SQLite), "SELECT COUNT(*) FROM orders LIMIT -1 OFFSET ?");
+    }
+
+    #[test]
+    fn test_extended_aggregates_per_dialect() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .count_distinct_as("customer_id", "customers")
+            .string_agg_as("sku", ", ", "skus")
+            .array_agg_as("sku", "sku_list")
+            .bool_or_as("refunded", "any_refunded")
+            .stddev_as("amount", "spread");
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT COUNT(DISTINCT "customer_id") AS customers, STRING_AGG("sku"::TEXT, ', ') AS skus, ARRAY_AGG("sku") AS sku_list, BOOL_OR("refunded") AS any_refunded, STDDEV_SAMP("amount") AS spread FROM orders"#
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::MySQL),
+            "SELECT COUNT(DISTINCT `customer_id`) AS customers, GROUP_CONCAT(`sku` SEPARATOR ', ') AS skus, JSON_ARRAYAGG(`sku`) AS sku_list, MAX(`refunded`) AS any_refunded, STDDEV_SAMP(`amount`) AS spread FROM orders"
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::SQLite),
+            "SELECT COUNT(DISTINCT customer_id) AS customers, GROUP_CONCAT(sku, ', ') AS skus, JSON_GROUP_ARRAY(sku) AS sku_list, MAX(refunded) AS any_refunded, SQRT((SUM(amount * amount) - SUM(amount) * SUM(amount) * 1.0 / COUNT(amount)) / (COUNT(amount) - 1)) AS spread FROM orders"
+        );
+    }
+
+    #[test]
+    fn test_expressions_are_used_verbatim() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("order_items".to_string())
+            .group_by("order_items.order_id")
+            .sum_as("price * qty", "total")
+            .expr_as("SUM(price * qty) / COUNT(*)", "avg_line");
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT "order_items"."order_id", SUM(price * qty) AS total, SUM(price * qty) / COUNT(*) AS avg_line FROM order_items GROUP BY "order_items"."order_id""#
+        );
+    }
+
+    #[test]
+    fn test_filter_where_per_dialect() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .count_as("orders")
+            .filter_where("status = {}", &[&"paid"])
+            .sum_as("amount", "refunded")
+            .filter_where("status = {}", &[&"refunded"])
+            .where_("region = {}", &[&"eu"]);
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT COUNT(*) FILTER (WHERE status = $1) AS orders, SUM("amount") FILTER (WHERE status = $2) AS refunded FROM orders WHERE region = $3"#
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::MySQL),
+            "SELECT COUNT(CASE WHEN status = ? THEN 1 END) AS orders, SUM(CASE WHEN status = ? THEN `amount` END) AS refunded FROM orders WHERE region = ?"
+        );
+
+        let params = builder.params(Dialect::MySQL);
+        assert!(matches!(params[0], BindValue::String(ref s) if s == "paid"));
+        assert!(matches!(params[1], BindValue::String(ref s) if s == "refunded"));
+        assert!(matches!(params[2], BindValue::String(ref s) if s == "eu"));
+    }
+
+    #[test]
+    fn test_filter_params_repeat_with_the_condition() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .variance_as("amount", "var")
+            .filter_where("status = {}", &[&"paid"])
+            .filter_where("amount > {}", &[&0i64]);
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT VAR_SAMP("amount") FILTER (WHERE (status = $1) AND (amount > $2)) AS var FROM orders"#
+        );
+        // SQLite renders the condition once per SUM/COUNT in the emulation
+        assert_eq!(builder.params(Dialect::PostgreSQL).len(), 2);
+        assert_eq!(builder.params(Dialect::SQLite).len(), 10);
+    }
+
+    #[test]
+    fn test_filter_where_misuse_is_reported() {
+        let is_misuse = |builder: AggQueryBuilder<sqlx::Postgres>, expected: &str| {
+            matches!(builder.check(), Err(sqlx::Error::Configuration(err)) if err.to_string().contains(expected))
+        };
+        let orders = || AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string());
+        assert!(is_misuse(orders().filter_where("status = {}", &[&"paid"]).count(), "must follow an aggregate"));
+        let expr = orders().expr_as("SUM(amount) / COUNT(*)", "mean").filter_where("status = {}", &[&"paid"]);
+        assert_eq!(expr.build_sql(Dialect::PostgreSQL), "SELECT SUM(amount) / COUNT(*) AS mean FROM orders");
+        assert!(is_misuse(expr, "not expr_as()"));
+        assert!(orders().count().filter_where("status = {}", &[&"paid"]).check().is_ok());
+    }
+
+    #[test]
+    fn test_window_functions_in_grouped_query() {
+        use crate::aggregate::window::rank;
+        use crate::filter::Order;
+
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .group_by("customer_id")
+            .sum_as("amount", "total")
+            .window("rank", rank().order_by("SUM(amount)", Order::Desc));
+
+        assert_eq!(
+            builder.build_sql(Dialect::MySQL),
+            "SELECT `customer_id`, SUM(`amount`) AS total, RANK() OVER (ORDER BY SUM(amount) DESC) AS `rank` FROM orders GROUP BY `customer_id`"
+        );
+    }
+
+    #[test]
+    fn test_where_window_wraps_the_query() {
+        use crate::aggregate::window::row_number;
+        use crate::filter::Order;
+
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .column("customer_id")
+            .column("amount")
+            .window("rn", row_number().partition_by("customer_id").order_by("amount", Order::Desc))
+            .where_("status = {}", &[&"paid"])
+            .where_window("rn <= {}", &[&3i64])
+            .order_by("customer_id", "ASC")
+            .limit(100);
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT * FROM (SELECT "customer_id", "amount", ROW_NUMBER() OVER (PARTITION BY "customer_id" ORDER BY "amount" DESC) AS "rn" FROM orders WHERE status = $1) AS windowed WHERE rn <= $2 ORDER BY customer_id ASC LIMIT $3"#
+        );
+        let params = builder.params(Dialect::PostgreSQL);
+        assert!(matches!(params[0], BindValue::String(ref s) if s == "paid"));
+        assert!(matches!(params[1], BindValue::I64(3)));
+    }
+
+    #[test]
+    fn test_rollup_per_dialect() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("sales".to_string())
+            .group_by_rollup(&["region", "category"])
+            .grouping_as("category", "category_total")
+            .sum_as("amount", "total");
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT "region", "category", GROUPING("category") AS category_total, SUM("amount") AS total FROM sales GROUP BY ROLLUP ("region", "category")"#
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::MySQL),
+            "SELECT `region`, `category`, GROUPING(`category`) AS category_total, SUM(`amount`) AS total FROM sales GROUP BY `region`, `category` WITH ROLLUP"
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::SQLite),
+            concat!(
+                "SELECT region, category, 0 AS category_total, SUM(amount) AS total FROM sales GROUP BY region, category",
+                " UNION ALL SELECT region, NULL AS category, 1 AS category_total, SUM(amount) AS total FROM sales GROUP BY region",
+                " UNION ALL SELECT NULL AS region, NULL AS category, 1 AS category_total, SUM(amount) AS total FROM sales",
+            )
+        );
+    }
+
+    #[test]
+    fn test_cube_and_grouping_sets() {
+        let cube = AggQueryBuilder::<sqlx::Postgres>::new("sales".to_string())
+            .group_by("year")
+            .group_by_cube(&["region", "category"])
+            .count();
+        assert_eq!(
+            cube.build_sql(Dialect::PostgreSQL),
+            r#"SELECT "year", "region", "category", COUNT(*) FROM sales GROUP BY "year", CUBE ("region", "category")"#
+        );
+        let sets = GroupingSets::Cube(vec!["a".to_string(), "b".to_string()]).sets();
+        assert_eq!(sets, vec![vec!["a", "b"], vec!["a"], vec!["b"], vec![]]);
+
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("sales".to_string())
+            .grouping_sets(&[&["region"], &["sales.category"], &[]])
+            .sum_as("amount", "total");
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT "region", "sales"."category", SUM("amount") AS total FROM sales GROUP BY GROUPING SETS (("region"), ("sales"."category"), ())"#
+        );
+    }
+
+    #[test]
+    fn test_emulated_grouping_sets_repeat_params() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("sales".to_string())
+            .where_("year = {}", &[&2024i32])
+            .group_by_cube(&["region"])
+            .sum_as("amount", "total")
+            .having("SUM(amount) > {}", &[&0i64])
+            .order_by("total", "DESC")
+            .limit(10);
+
+        assert_eq!(
+            builder.build_sql(Dialect::MySQL),
+            "SELECT `region`, SUM(`amount`) AS total FROM sales WHERE year = ? GROUP BY `region` HAVING SUM(amount) > ? UNION ALL SELECT NULL AS `region`, SUM(`amount`) AS total FROM sales WHERE year = ? HAVING SUM(amount) > ? ORDER BY total DESC LIMIT ?"
+        );
+        let params = builder.params(Dialect::MySQL);
+        assert_eq!(params.len(), 4);
+        assert!(matches!(params[2], BindValue::I32(2024)));
+        assert!(matches!(params[3], BindValue::I64(0)));
+        assert_eq!(builder.params(Dialect::PostgreSQL).len(), 2);
+    }
+
+    #[test]
+    fn test_group_by_time() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .group_by_time("created_at", TimeBucket::Month, "UTC")
+            .sum_as("amount", "revenue")
+            .order_by("bucket", "ASC");
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            r#"SELECT date_trunc('month', "created_at") AS "bucket", SUM("amount") AS revenue FROM orders GROUP BY date_trunc('month', "created_at") ORDER BY bucket ASC"#
+        );
+        assert_eq!(
+            builder.build_sql(Dialect::SQLite),
+            "SELECT strftime('%Y-%m-01 00:00:00', created_at) AS bucket, SUM(amount) AS revenue FROM orders GROUP BY strftime('%Y-%m-01 00:00:00', created_at) ORDER BY bucket ASC"
+        );
+    }
+
+    #[test]
+    fn test_fill_gaps_uses_generate_series_on_postgres() {
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .group_by_time("created_at", TimeBucket::Day, "UTC")
+            .where_("status = {}", &[&"paid"])
+            .sum_as("amount", "revenue")
+            .avg("amount")
+            .fill_gaps();
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            concat!(
+                r#"WITH q ("bucket", "c1", "c2") AS (SELECT date_trunc('day', "created_at") AS "bucket", SUM("amount") AS revenue, AVG("amount") FROM orders WHERE status = $1 GROUP BY date_trunc('day', "created_at"))"#,
+                r#" SELECT buckets."bucket" AS "bucket", COALESCE(q."c1", 0) AS revenue, q."c2" FROM generate_series((SELECT MIN("bucket") FROM q), (SELECT MAX("bucket") FROM q), INTERVAL '1 day') AS buckets ("bucket")"#,
+                r#" LEFT JOIN q ON q."bucket" = buckets."bucket" ORDER BY "bucket""#,
+            )
+        );
+        // Filled in Rust by fetch_series elsewhere
+        assert!(!builder.build_sql(Dialect::MySQL).contains("generate_series"));
+    }
+
+    // The nested query is rendered for the current dialect
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_from_subquery_binds_after_select_list() {
+        let paid = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .group_by("customer_id")
+            .where_("status = {}", &[&"paid"])
+            .sum_as("amount", "total")
+            .limit(100);
+        let builder = AggQueryBuilder::from_subquery("paid", paid)
+            .count_as("customers")
+            .filter_where("total > {}", &[&500i64])
+            .where_("total > {}", &[&10i64]);
+
+        assert_eq!(
+            builder.build_sql(Dialect::PostgreSQL),
+            concat!(
+                r#"SELECT COUNT(*) FILTER (WHERE total > $1) AS customers FROM (SELECT "customer_id", SUM("amount") AS total"#,
+                r#" FROM orders WHERE status = $2 GROUP BY "customer_id" LIMIT $3) AS "paid" WHERE total > $4"#,
+            )
+        );
+        let params = builder.params(Dialect::PostgreSQL);
+        assert_eq!(params.len(), 4);
+        assert!(matches!(params[2], BindValue::I64(100)));
+    }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn test_invalid_subqueries_fail_at_fetch() {
+        let invalid = || Subquery::raw("SELECT 1", Vec::new()).with_problem(Some("UNION: mismatched columns".to_string()));
+        let is_invalid_subquery = |result: Result<(), sqlx::Error>| {
+            matches!(result, Err(sqlx::Error::Configuration(err)) if err.to_string() == "invalid subquery: UNION: mismatched columns")
+        };
+        assert!(is_invalid_subquery(AggQueryBuilder::<sqlx::Postgres>::from_subquery("t", invalid()).count().check()));
+        assert!(is_invalid_subquery(AggQueryBuilder::<sqlx::Postgres>::new("t".to_string()).with("t", invalid()).check()));
+        let filtered = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .filter(Expr::exists(invalid()))
+            .count();
+        assert!(is_invalid_subquery(filtered.check()));
+        assert!(AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string()).count().check().is_ok());
+
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let result = AggQueryBuilder::<sqlx::Postgres>::from_subquery("t", invalid())
+            .count()
+            .fetch_all::<(i64,)>(&pool)
+            .await;
+        assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+    }
+
+    // The nested query is rendered for the current dialect
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_ctes_precede_the_gap_filling_cte() {
+        let paid = Subquery::raw("SELECT * FROM orders WHERE status = {}", vec![BindValue::String("paid".to_string())]);
+        let builder = AggQueryBuilder::<sqlx::Postgres>::new("paid".to_string())
+            .with("paid", paid)
+            .group_by_time("created_at", TimeBucket::Day, "UTC")
+            .where_("amount > {}", &[&10i64])
+            .count()
+            .fill_gaps();
+
+        let sql = builder.build_sql(Dialect::PostgreSQL);
+        assert!(sql.starts_with(concat!(
+            r#"WITH "paid" AS (SELECT * FROM orders WHERE status = $1), q ("bucket", "c1") AS (SELECT date_trunc('day', "created_at") AS "bucket","#,
+            r#" COUNT(*) FROM paid WHERE amount > $2 GROUP BY"#,
+        )));
+        assert!(sql.ends_with(r#"LEFT JOIN q ON q."bucket" = buckets."bucket" ORDER BY "bucket""#));
+        assert_eq!(builder.params(Dialect::PostgreSQL).len(), 2);
+    }
+
+    struct RevenueRow;
+
+    impl AggRow for RevenueRow {
+        const FIELDS: &'static [(&'static str, Option<AggCast>)] = &[
+            ("category", Some(AggCast::Text)),
+            ("total", Some(AggCast::Decimal)),
+            ("orders", Some(AggCast::Integer)),
+            ("rn", None),
+        ];
+    }
+
+    #[test]
+    fn test_row_fields_cast_their_expressions() {
+        let builder = || {
+            AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+                .group_by("orders.category")
+                .sum_as("amount", "total")
+                .count_as("orders")
+                .window("rn", crate::aggregate::window::row_number())
+        };
+
+        assert_eq!(
+            builder().for_row::<RevenueRow>().unwrap().build_sql(Dialect::PostgreSQL),
+            concat!(
+                r#"SELECT ("orders"."category")::TEXT AS "category", (SUM("amount"))::NUMERIC AS total, (COUNT(*))::BIGINT AS orders,"#,
+                r#" ROW_NUMBER() OVER () AS "rn" FROM orders GROUP BY "orders"."category""#,
+            )
+        );
+        assert_eq!(
+            builder().for_row::<RevenueRow>().unwrap().build_sql(Dialect::MySQL),
+            concat!(
+                "SELECT CAST((`orders`.`category`) AS CHAR) AS `category`, CAST((SUM(`amount`)) AS DECIMAL(65, 30)) AS total,",
+                " CAST((COUNT(*)) AS SIGNED) AS orders, ROW_NUMBER() OVER () AS `rn` FROM orders GROUP BY `orders`.`category`",
+            )
+        );
+        // SQLite has no decimal type, the value is decoded as is
+        assert!(builder()
+            .for_row::<RevenueRow>()
+            .unwrap()
+            .build_sql(Dialect::SQLite)
+            .contains("SUM(amount) AS total"));
+    }
+
+    #[test]
+    fn test_row_fields_must_match_the_query() {
+        let err = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
+            .group_by("category")
+            .sum_as("amount", "revenue")
+            .count_as("orders")
+            .for_row::<RevenueRow>()
+            .err()
+            .unwrap();
+
+        assert!(err.to_string().contains("field `total`"), "{}", err);
+    }
+
     #[test]
     fn test_right_join() {
         let builder = AggQueryBuilder::<sqlx::Postgres>::new("orders".to_string())
@@ -0,0 +1,68 @@
+//! Typed result rows for aggregation queries.
+
+use crate::Dialect;
+
+/// A struct receiving the rows of an aggregation query, generated by
+/// `#[derive(AggRow)]`.
+///
+/// ```ignore
+/// #[derive(FromRow, AggRow)]
+/// struct RevenueByCategory {
+///     category: String,
+///     total: i64,
+///     average: f64,
+/// }
+///
+/// let rows = Order::agg_query()
+///     .group_by("category")
+///     .sum_as("amount", "total")
+///     .avg_as("amount", "average")
+///     .fetch_all_as::<RevenueByCategory>(&pool)
+///     .await?;
+/// ```
+///
+/// Each field is matched by name to a select expression: a GROUP BY column,
+/// a [`column`](super::AggQueryBuilder::column) or the alias of an aggregate,
+/// window or grouping indicator. Fetching fails before running the query if a
+/// field has no match.
+pub trait AggRow {
+    /// The field names, in field order, with the cast their Rust type needs.
+    const FIELDS: &'static [(&'static str, Option<AggCast>)];
+}
+
+/// Conversion applied to a select expression so that it decodes into the
+/// field's Rust type whatever SQL type the database picks for it, e.g. the
+/// NUMERIC that PostgreSQL returns for `SUM` of a BIGINT or for `AVG`.
+///
+/// Inferred by `#[derive(AggRow)]` from the field type, ignoring `Option`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggCast {
+    /// `String` fields
+    Text,
+    /// `i64` fields
+    Integer,
+    /// `f64` fields
+    Float,
+    /// `rust_decimal::Decimal` fields
+    Decimal,
+}
+
+impl AggCast {
+    /// The SQL type to cast to on `dialect`, if any. SQLite has no decimal
+    /// type to cast to.
+    pub fn sql_type(&self, dialect: Dialect) -> Option<&'static str> {
+        match (self, dialect) {
+            (AggCast::Text, Dialect::MySQL) => Some("CHAR"),
+            (AggCast::Text, _) => Some("TEXT"),
+            (AggCast::Integer, Dialect::PostgreSQL) => Some("BIGINT"),
+            (AggCast::Integer, Dialect::MySQL) => Some("SIGNED"),
+            (AggCast::Integer, Dialect::SQLite) => Some("INTEGER"),
+            (AggCast::Float, Dialect::PostgreSQL) => Some("DOUBLE PRECISION"),
+            (AggCast::Float, Dialect::MySQL) => Some("DOUBLE"),
+            (AggCast::Float, Dialect::SQLite) => Some("REAL"),
+            (AggCast::Decimal, Dialect::PostgreSQL) => Some("NUMERIC"),
+            (AggCast::Decimal, Dialect::MySQL) => Some("DECIMAL(65, 30)"),
+            (AggCast::Decimal, Dialect::SQLite) => None,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Time buckets for [`AggQueryBuilder::group_by_time`](super::AggQueryBuilder::group_by_time).
+//!
+//! Every dialect yields the bucket as a timestamp at the start of the bucket
+//! (weeks start on Monday), so rows decode into `NaiveDateTime`:
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::TimeBucket;
+//!
+//! let revenue: Vec<(NaiveDateTime, i64)> = Order::agg_query()
+//!     .group_by_time("created_at", TimeBucket::Day, "UTC")
+//!     .sum_as("amount", "revenue")
+//!     .fill_gaps()
+//!     .fetch_series(&pool)
+//!     .await?;
+//! ```
+
+use chrono::NaiveDateTime;
+
+use crate::Dialect;
+
+/// Width of a time bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeBucket {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl TimeBucket {
+    /// The PostgreSQL `date_trunc` field name.
+    fn field(&self) -> &'static str {
+        match self {
+            TimeBucket::Hour => "hour",
+            TimeBucket::Day => "day",
+            TimeBucket::Week => "week",
+            TimeBucket::Month => "month",
+        }
+    }
+
+    /// The PostgreSQL interval between consecutive buckets.
+    pub(crate) fn interval(&self) -> String {
+        format!("INTERVAL '1 {}'", self.field())
+    }
+
+    /// Renders the start of the bucket containing `column`, converted from
+    /// UTC to `tz` first.
+    ///
+    /// `column` is a timestamp without time zone on every dialect. `tz` is a
+    /// time zone name on PostgreSQL and MySQL (which needs its time zone
+    /// tables loaded for names other than offsets) and a date modifier such
+    /// as `+02:00` or `localtime` on SQLite. `UTC` skips the conversion.
+    ///
+    /// PostgreSQL converts with `(column AT TIME ZONE 'UTC') AT TIME ZONE tz`:
+    /// the first step reads the timestamp as UTC, the second yields the wall
+    /// clock time in `tz`, again without time zone.
+    pub(crate) fn to_sql(self, dialect: Dialect, column: &str, tz: &str) -> String {
+        let column = dialect.quote_column(column);
+        let is_utc = tz.eq_ignore_ascii_case("UTC") || tz == "+00:00";
+        let tz = tz.replace('\'', "''");
+        match dialect {
+            Dialect::PostgreSQL => {
+                let local =
+                    if is_utc { column } else { format!("({} AT TIME ZONE 'UTC') AT TIME ZONE '{}'", column, tz) };
+                format!("date_trunc('{}', {})", self.field(), local)
+            }
+            Dialect::MySQL => {
+                let local = if is_utc { column } else { format!("CONVERT_TZ({}, '+00:00', '{}')", column, tz) };
+                let start = match self {
+                    TimeBucket::Hour => format!("DATE_FORMAT({}, '%Y-%m-%d %H:00:00')", local),
+                    TimeBucket::Day => format!("DATE({})", local),
+                    TimeBucket::Week => format!("DATE_SUB(DATE({}), INTERVAL WEEKDAY({}) DAY)", local, local),
+                    TimeBucket::Month => format!("DATE_FORMAT({}, '%Y-%m-01')", local),
+                };
+                dialect.cast(&start, "DATETIME")
+            }
+            Dialect::SQLite => {
+                let modifier = if is_utc { String::new() } else { format!(", '{}'", tz) };
+                match self {
+                    TimeBucket::Hour => format!("strftime('%Y-%m-%d %H:00:00', {}{})", column, modifier),
+                    TimeBucket::Day => format!("strftime('%Y-%m-%d 00:00:00', {}{})", column, modifier),
+                    TimeBucket::Week => {
+                        format!("strftime('%Y-%m-%d 00:00:00', {}{}, '-6 days', 'weekday 1')", column, modifier)
+                    }
+                    TimeBucket::Month => format!("strftime('%Y-%m-01 00:00:00', {}{})", column, modifier),
+                }
+            }
+        }
+    }
+
+    /// The start of the bucket following the one starting at `start`.
+    pub fn next(&self, start: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            TimeBucket::Hour => start + chrono::Duration::hours(1),
+            TimeBucket::Day => start + chrono::Duration::days(1),
+            TimeBucket::Week => start + chrono::Duration::weeks(1),
+            TimeBucket::Month => start + chrono::Months::new(1),
+        }
+    }
+}
+
+/// Inserts a `V::default()` row for every bucket missing between the first
+/// and the last row, returning the rows in bucket order.
+///
+/// This is the gap filling [`fill_gaps`](super::AggQueryBuilder::fill_gaps)
+/// applies on MySQL and SQLite; PostgreSQL fills in SQL.
+pub fn fill_gaps<V: Default>(
+    mut rows: Vec<(NaiveDateTime, V)>,
+    bucket: TimeBucket,
+) -> Vec<(NaiveDateTime, V)> {
+    rows.sort_by_key(|(start, _)| *start);
+    let mut filled = Vec::with_capacity(rows.len());
+    let mut expected = match rows.first() {
+        Some((start, _)) => *start,
+        None => return filled,
+    };
+    for (start, value) in rows {
+        while expected < start {
+            filled.push((expected, V::default()));
+            expected = bucket.next(expected);
+        }
+        filled.push((start, value));
+        expected = bucket.next(start);
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_sql_per_dialect() {
+        assert_eq!(
+            TimeBucket::Day.to_sql(Dialect::PostgreSQL, "created_at", "Europe/Berlin"),
+            r#"date_trunc('day', ("created_at" AT TIME ZONE 'UTC') AT TIME ZONE 'Europe/Berlin')"#
+        );
+        assert_eq!(
+            TimeBucket::Hour.to_sql(Dialect::PostgreSQL, "created_at", "UTC"),
+            r#"date_trunc('hour', "created_at")"#
+        );
+        assert_eq!(
+            TimeBucket::Week.to_sql(Dialect::MySQL, "created_at", "UTC"),
+            "CAST(DATE_SUB(DATE(`created_at`), INTERVAL WEEKDAY(`created_at`) DAY) AS DATETIME)"
+        );
+        assert_eq!(
+            TimeBucket::Hour.to_sql(Dialect::MySQL, "created_at", "Europe/Berlin"),
+            "CAST(DATE_FORMAT(CONVERT_TZ(`created_at`, '+00:00', 'Europe/Berlin'), '%Y-%m-%d %H:00:00') AS DATETIME)"
+        );
+        assert_eq!(
+            TimeBucket::Month.to_sql(Dialect::SQLite, "created_at", "+02:00"),
+            "strftime('%Y-%m-01 00:00:00', created_at, '+02:00')"
+        );
+        assert_eq!(
+            TimeBucket::Week.to_sql(Dialect::SQLite, "created_at", "UTC"),
+            "strftime('%Y-%m-%d 00:00:00', created_at, '-6 days', 'weekday 1')"
+        );
+    }
+
+    #[test]
+    fn test_fill_gaps() {
+        let day = |d: u32| chrono::NaiveDate::from_ymd_opt(2024, 1, d).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        let rows = vec![(day(4), 7i64), (day(1), 5i64)];
+        assert_eq!(
+            fill_gaps(rows, TimeBucket::Day),
+            vec![(day(1), 5), (day(2), 0), (day(3), 0), (day(4), 7)]
+        );
+        assert!(fill_gaps(Vec::<(NaiveDateTime, i64)>::new(), TimeBucket::Day).is_empty());
+
+        let jan = day(1);
+        assert_eq!(TimeBucket::Month.next(jan), chrono::NaiveDate::from_ymd_opt(2024, 2, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    }
+}
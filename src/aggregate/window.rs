@@ -0,0 +1,258 @@
+//! Window functions for [`AggQueryBuilder::window`](super::AggQueryBuilder::window).
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::aggregate::window::{lag, row_number, sum, FrameBound};
+//! use sqlx_struct_enhanced::Order;
+//!
+//! // Top 3 orders per customer
+//! let rows: Vec<(String, i64, i64)> = Sale::agg_query()
+//!     .column("customer_id")
+//!     .column("amount")
+//!     .window("rn", row_number().partition_by("customer_id").order_by("amount", Order::Desc))
+//!     .where_window("rn <= {}", &[&3i64])
+//!     .fetch_all(&pool)
+//!     .await?;
+//!
+//! // Running total and previous amount
+//! Sale::agg_query()
+//!     .column("created_at")
+//!     .window("running", sum("amount").order_by("created_at", Order::Asc)
+//!         .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow))
+//!     .window("previous", lag("amount", 1).order_by("created_at", Order::Asc));
+//! ```
+//!
+//! Columns are quoted like aggregate arguments: plain and table-qualified
+//! names are quoted for the dialect, anything else is used verbatim, so an
+//! aggregate such as `SUM(amount)` can be ranked in a grouped query.
+
+use crate::filter::Order;
+use crate::Dialect;
+
+/// A boundary of a window frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    fn to_sql(self) -> String {
+        match self {
+            FrameBound::UnboundedPreceding => "UNBOUNDED PRECEDING".to_string(),
+            FrameBound::Preceding(n) => format!("{} PRECEDING", n),
+            FrameBound::CurrentRow => "CURRENT ROW".to_string(),
+            FrameBound::Following(n) => format!("{} FOLLOWING", n),
+            FrameBound::UnboundedFollowing => "UNBOUNDED FOLLOWING".to_string(),
+        }
+    }
+}
+
+/// A window function call with its `OVER (...)` specification.
+///
+/// Created with [`row_number`], [`rank`], [`lag`], [`sum`] and the other
+/// functions of this module, then refined with the builder methods.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFunction {
+    function: &'static str,
+    column: Option<String>,
+    argument: Option<String>,
+    partition_by: Vec<String>,
+    order_by: Vec<(String, Order)>,
+    frame: Option<(&'static str, FrameBound, FrameBound)>,
+}
+
+impl WindowFunction {
+    fn new(function: &'static str, column: Option<&str>, argument: Option<String>) -> Self {
+        Self {
+            function,
+            column: column.map(str::to_string),
+            argument,
+            partition_by: Vec::new(),
+            order_by: Vec::new(),
+            frame: None,
+        }
+    }
+
+    /// Adds a PARTITION BY column.
+    pub fn partition_by(mut self, column: &str) -> Self {
+        self.partition_by.push(column.to_string());
+        self
+    }
+
+    /// Adds an ORDER BY key within each partition.
+    pub fn order_by(mut self, column: &str, order: Order) -> Self {
+        self.order_by.push((column.to_string(), order));
+        self
+    }
+
+    /// Sets a `ROWS BETWEEN start AND end` frame.
+    pub fn rows_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(("ROWS", start, end));
+        self
+    }
+
+    /// Sets a `RANGE BETWEEN start AND end` frame.
+    ///
+    /// MySQL and SQLite only accept numeric offsets with a single numeric
+    /// ORDER BY key.
+    pub fn range_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(("RANGE", start, end));
+        self
+    }
+
+    /// Renders `FUNC(args) OVER (...)`.
+    pub(crate) fn to_sql(&self, dialect: Dialect) -> String {
+        let args: Vec<String> = self
+            .column
+            .iter()
+            .map(|column| dialect.quote_column(column))
+            .chain(self.argument.iter().cloned())
+            .collect();
+
+        let mut over = Vec::new();
+        if !self.partition_by.is_empty() {
+            let columns: Vec<String> = self.partition_by.iter().map(|c| dialect.quote_column(c)).collect();
+            over.push(format!("PARTITION BY {}", columns.join(", ")));
+        }
+        if !self.order_by.is_empty() {
+            let keys: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(column, order)| format!("{} {}", dialect.quote_column(column), order))
+                .collect();
+            over.push(format!("ORDER BY {}", keys.join(", ")));
+        }
+        if let Some((unit, start, end)) = self.frame {
+            over.push(format!("{} BETWEEN {} AND {}", unit, start.to_sql(), end.to_sql()));
+        }
+
+        format!("{}({}) OVER ({})", self.function, args.join(", "), over.join(" "))
+    }
+}
+
+/// `ROW_NUMBER()`: the row's position within its partition.
+pub fn row_number() -> WindowFunction {
+    WindowFunction::new("ROW_NUMBER", None, None)
+}
+
+/// `RANK()`: the row's rank, with gaps after ties.
+pub fn rank() -> WindowFunction {
+    WindowFunction::new("RANK", None, None)
+}
+
+/// `DENSE_RANK()`: the row's rank, without gaps after ties.
+pub fn dense_rank() -> WindowFunction {
+    WindowFunction::new("DENSE_RANK", None, None)
+}
+
+/// `NTILE(buckets)`: the bucket number after splitting the partition into
+/// `buckets` groups.
+pub fn ntile(buckets: u64) -> WindowFunction {
+    WindowFunction::new("NTILE", None, Some(buckets.to_string()))
+}
+
+/// `LAG(column, offset)`: the value `offset` rows before the current one.
+pub fn lag(column: &str, offset: u64) -> WindowFunction {
+    WindowFunction::new("LAG", Some(column), Some(offset.to_string()))
+}
+
+/// `LEAD(column, offset)`: the value `offset` rows after the current one.
+pub fn lead(column: &str, offset: u64) -> WindowFunction {
+    WindowFunction::new("LEAD", Some(column), Some(offset.to_string()))
+}
+
+/// `FIRST_VALUE(column)` over the frame.
+pub fn first_value(column: &str) -> WindowFunction {
+    WindowFunction::new("FIRST_VALUE", Some(column), None)
+}
+
+/// `LAST_VALUE(column)` over the frame.
+///
+/// The default frame ends at the current row; use
+/// [`rows_between`](WindowFunction::rows_between) to see the whole partition.
+pub fn last_value(column: &str) -> WindowFunction {
+    WindowFunction::new("LAST_VALUE", Some(column), None)
+}
+
+/// `SUM(column) OVER (...)`, e.g. a running total when ordered.
+pub fn sum(column: &str) -> WindowFunction {
+    WindowFunction::new("SUM", Some(column), None)
+}
+
+/// `AVG(column) OVER (...)`, e.g. a moving average with a ROWS frame.
+pub fn avg(column: &str) -> WindowFunction {
+    WindowFunction::new("AVG", Some(column), None)
+}
+
+/// `COUNT(*) OVER (...)`.
+pub fn count() -> WindowFunction {
+    WindowFunction::new("COUNT", Some("*"), None)
+}
+
+/// `MIN(column) OVER (...)`.
+pub fn min(column: &str) -> WindowFunction {
+    WindowFunction::new("MIN", Some(column), None)
+}
+
+/// `MAX(column) OVER (...)`.
+pub fn max(column: &str) -> WindowFunction {
+    WindowFunction::new("MAX", Some(column), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranking_functions() {
+        let window = row_number().partition_by("customer_id").order_by("amount", Order::Desc);
+        assert_eq!(
+            window.to_sql(Dialect::PostgreSQL),
+            r#"ROW_NUMBER() OVER (PARTITION BY "customer_id" ORDER BY "amount" DESC)"#
+        );
+        assert_eq!(
+            window.to_sql(Dialect::MySQL),
+            "ROW_NUMBER() OVER (PARTITION BY `customer_id` ORDER BY `amount` DESC)"
+        );
+        assert_eq!(ntile(4).order_by("score", Order::Asc).to_sql(Dialect::SQLite), "NTILE(4) OVER (ORDER BY score ASC)");
+        assert_eq!(rank().to_sql(Dialect::PostgreSQL), "RANK() OVER ()");
+    }
+
+    #[test]
+    fn test_offset_and_aggregate_functions() {
+        assert_eq!(
+            lag("amount", 1).order_by("created_at", Order::Asc).to_sql(Dialect::PostgreSQL),
+            r#"LAG("amount", 1) OVER (ORDER BY "created_at" ASC)"#
+        );
+        assert_eq!(
+            lead("orders.amount", 2).to_sql(Dialect::MySQL),
+            "LEAD(`orders`.`amount`, 2) OVER ()"
+        );
+        assert_eq!(count().partition_by("region").to_sql(Dialect::SQLite), "COUNT(*) OVER (PARTITION BY region)");
+        assert_eq!(
+            rank().order_by("SUM(amount)", Order::Desc).to_sql(Dialect::PostgreSQL),
+            "RANK() OVER (ORDER BY SUM(amount) DESC)"
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        assert_eq!(
+            sum("amount")
+                .order_by("created_at", Order::Asc)
+                .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow)
+                .to_sql(Dialect::PostgreSQL),
+            r#"SUM("amount") OVER (ORDER BY "created_at" ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)"#
+        );
+        assert_eq!(
+            avg("amount")
+                .order_by("day", Order::Asc)
+                .range_between(FrameBound::Preceding(6), FrameBound::Following(0))
+                .to_sql(Dialect::MySQL),
+            "AVG(`amount`) OVER (ORDER BY `day` ASC RANGE BETWEEN 6 PRECEDING AND 0 FOLLOWING)"
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! SQL dialect differences between the supported databases.
+//!
+//! Every SQL builder in the crate (`Scheme`, `AggQueryBuilder`, the JOIN
+//! generator, `migration::SqlGenerator`, ...) renders the database specific
+//! parts of a statement through [`Dialect`]: parameter placeholders,
+//! identifier quoting, casts, LIMIT/OFFSET, upserts and RETURNING support.
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::Dialect;
+//!
+//! let dialect = Dialect::current();
+//! let sql = format!(
+//!     "SELECT * FROM {} WHERE {}",
+//!     dialect.quote_identifier("users"),
+//!     dialect.placeholders("age > {} AND status = {}", 1),
+//! );
+//! // PostgreSQL: SELECT * FROM "users" WHERE age > $1 AND status = $2
+//! // MySQL:      SELECT * FROM `users` WHERE age > ? AND status = ?
+//! ```
+
+/// The SQL dialect of a supported database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    PostgreSQL,
+    MySQL,
+    SQLite,
+}
+
+impl Dialect {
+    /// All supported dialects.
+    pub const ALL: [Dialect; 3] = [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite];
+
+    /// The dialect of the enabled database feature.
+    ///
+    /// When several database features are enabled, PostgreSQL takes
+    /// precedence, then MySQL.
+    #[cfg(feature = "postgres")]
+    pub fn current() -> Self {
+        Dialect::PostgreSQL
+    }
+
+    /// The dialect of the enabled database feature.
+    #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+    pub fn current() -> Self {
+        Dialect::MySQL
+    }
+
+    /// The dialect of the enabled database feature.
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    pub fn current() -> Self {
+        Dialect::SQLite
+    }
+
+    #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+    pub fn current() -> Self {
+        compile_error!("You must enable one of the database features: postgres, mysql, or sqlite")
+    }
+
+    /// The placeholder of the `n`-th (1-based) bound parameter: `$n` on
+    /// PostgreSQL, `?` on MySQL and SQLite.
+    pub fn placeholder(&self, n: usize) -> String {
+        match self {
+            Dialect::PostgreSQL => format!("${}", n),
+            Dialect::MySQL | Dialect::SQLite => "?".to_string(),
+        }
+    }
+
+    /// Replaces each `{}` in `template` with a placeholder, numbering them
+    /// from `start`.
+    pub fn placeholders(&self, template: &str, start: usize) -> String {
+        let mut parts = template.split("{}");
+        let mut sql = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            sql.push_str(&self.placeholder(start + i));
+            sql.push_str(part);
+        }
+        sql
+    }
+
+    /// Quotes an identifier: `"name"` on PostgreSQL, `` `name` `` on MySQL.
+    /// SQLite identifiers are left bare, so `schema.table` names keep
+    /// addressing attached databases.
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        match self {
+            Dialect::PostgreSQL => format!("\"{}\"", identifier),
+            Dialect::MySQL => format!("`{}`", identifier),
+            Dialect::SQLite => identifier.to_string(),
+        }
+    }
+
+    /// Quotes `column` when it is a plain (`amount`) or table-qualified
+    /// (`orders.amount`) identifier. Anything else, such as `*` or an
+    /// expression like `price * qty`, is returned unchanged.
+    pub fn quote_column(&self, column: &str) -> String {
+        let is_identifier = |part: &str| {
+            part.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        let parts: Vec<&str> = column.split('.').collect();
+        if parts.len() > 2 || !parts.iter().all(|part| is_identifier(part)) {
+            return column.to_string();
+        }
+        parts.iter().map(|part| self.quote_identifier(part)).collect::<Vec<_>>().join(".")
+    }
+
+    /// Casts `expr` to `sql_type`: `expr::type` on PostgreSQL,
+    /// `CAST(expr AS type)` elsewhere.
+    ///
+    /// MySQL only casts to a few target types, so column types are mapped
+    /// onto them there (`TEXT` to `CHAR`, `INTEGER` to `SIGNED`, ...).
+    pub fn cast(&self, expr: &str, sql_type: &str) -> String {
+        match self {
+            Dialect::PostgreSQL => format!("{}::{}", expr, sql_type),
+            Dialect::MySQL => format!("CAST({} AS {})", expr, mysql_cast_target(sql_type)),
+            Dialect::SQLite => format!("CAST({} AS {})", expr, sql_type),
+        }
+    }
+
+    /// A bound parameter hinted as `sql_type`, for values bound as text
+    /// (DECIMAL, UUID). Only PostgreSQL needs the hint: MySQL and SQLite
+    /// convert the text to the column type themselves.
+    pub fn typed_placeholder(&self, n: usize, sql_type: &str) -> String {
+        match self {
+            Dialect::PostgreSQL => self.cast(&self.placeholder(n), sql_type),
+            Dialect::MySQL | Dialect::SQLite => self.placeholder(n),
+        }
+    }
+
+    /// The LIMIT/OFFSET tail of a query with `{}` for each bound value.
+    ///
+    /// OFFSET without LIMIT is not valid on MySQL and SQLite, so an unbounded
+    /// LIMIT is emitted there.
+    pub fn limit_offset(&self, limit: bool, offset: bool) -> &'static str {
+        match (limit, offset, self) {
+            (true, true, _) => " LIMIT {} OFFSET {}",
+            (true, false, _) => " LIMIT {}",
+            (false, true, Dialect::PostgreSQL) => " OFFSET {}",
+            (false, true, Dialect::MySQL) => " LIMIT 18446744073709551615 OFFSET {}",
+            (false, true, Dialect::SQLite) => " LIMIT -1 OFFSET {}",
+            (false, false, _) => "",
+        }
+    }
+
+    /// Whether aggregate calls accept a `FILTER (WHERE ...)` clause (SQLite
+    /// since 3.30). MySQL needs a `CASE` expression instead.
+    pub fn supports_aggregate_filter(&self) -> bool {
+        match self {
+            Dialect::PostgreSQL | Dialect::SQLite => true,
+            Dialect::MySQL => false,
+        }
+    }
+
+    /// Whether INSERT/UPDATE/DELETE support a `RETURNING` clause (SQLite
+    /// since 3.35). MySQL has none.
+    pub fn supports_returning(&self) -> bool {
+        match self {
+            Dialect::PostgreSQL | Dialect::SQLite => true,
+            Dialect::MySQL => false,
+        }
+    }
+
+    /// The most parameters one statement may bind: 65535 on PostgreSQL and
+    /// MySQL, 32766 on SQLite (3.32 and later, as bundled with sqlx).
+    pub fn max_bind_params(&self) -> usize {
+        match self {
+            Dialect::PostgreSQL | Dialect::MySQL => 65535,
+            Dialect::SQLite => 32766,
+        }
+    }
+
+    /// Turns `insert` (an `INSERT INTO ... VALUES ...` statement) into an
+    /// upsert.
+    ///
+    /// Rows conflicting on the `conflict` columns get their `update` columns
+    /// overwritten with the inserted values; with no `update` columns they
+    /// are skipped. PostgreSQL and SQLite need `conflict` to name a unique
+    /// constraint when `update` is not empty; MySQL always uses whichever
+    /// unique key conflicts.
+    pub fn upsert(&self, insert: &str, conflict: &[&str], update: &[&str]) -> String {
+        let set = |value: &dyn Fn(&str) -> String| {
+            update
+                .iter()
+                .map(|col| format!("{}={}", self.quote_identifier(col), value(&self.quote_identifier(col))))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        match self {
+            Dialect::MySQL if update.is_empty() => insert.replacen("INSERT INTO", "INSERT IGNORE INTO", 1),
+            Dialect::MySQL => format!("{} ON DUPLICATE KEY UPDATE {}", insert, set(&|col| format!("VALUES({})", col))),
+            Dialect::PostgreSQL | Dialect::SQLite => {
+                let target = if conflict.is_empty() {
+                    String::new()
+                } else {
+                    let cols: Vec<String> = conflict.iter().map(|col| self.quote_identifier(col)).collect();
+                    format!(" ({})", cols.join(","))
+                };
+                if update.is_empty() {
+                    format!("{} ON CONFLICT{} DO NOTHING", insert, target)
+                } else {
+                    format!("{} ON CONFLICT{} DO UPDATE SET {}", insert, target, set(&|col| format!("excluded.{}", col)))
+                }
+            }
+        }
+    }
+}
+
+/// The MySQL `CAST` target for the column type `sql_type`. Types MySQL
+/// casts to directly (`CHAR`, `SIGNED`, `DECIMAL(10, 2)`, `DATETIME`, ...)
+/// are kept.
+fn mysql_cast_target(sql_type: &str) -> String {
+    let upper = sql_type.trim().to_ascii_uppercase();
+    let (base, args) = match upper.find('(') {
+        Some(i) => (upper[..i].trim(), &upper[i..]),
+        None => (upper.as_str(), ""),
+    };
+    match base {
+        "TEXT" | "VARCHAR" | "CHARACTER VARYING" | "UUID" => "CHAR".to_string(),
+        "INT" | "INTEGER" | "SMALLINT" | "BIGINT" | "TINYINT" | "MEDIUMINT" => "SIGNED".to_string(),
+        "REAL" | "FLOAT" | "DOUBLE PRECISION" => "DOUBLE".to_string(),
+        "NUMERIC" | "DECIMAL" if args.is_empty() => "DECIMAL(65, 30)".to_string(),
+        "NUMERIC" => format!("DECIMAL{}", args),
+        "TIMESTAMP" | "TIMESTAMPTZ" => "DATETIME".to_string(),
+        "BYTEA" | "BLOB" => "BINARY".to_string(),
+        _ => sql_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expect(cases: [(Dialect, &str); 3], render: impl Fn(Dialect) -> String) {
+        for (dialect, expected) in cases {
+            assert_eq!(render(dialect), expected, "{:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_placeholders() {
+        expect(
+            [
+                (Dialect::PostgreSQL, "a = $3 AND b IN ($4,$5)"),
+                (Dialect::MySQL, "a = ? AND b IN (?,?)"),
+                (Dialect::SQLite, "a = ? AND b IN (?,?)"),
+            ],
+            |d| d.placeholders("a = {} AND b IN ({},{})", 3),
+        );
+        for dialect in Dialect::ALL {
+            assert_eq!(dialect.placeholders("1=1", 1), "1=1");
+        }
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        expect(
+            [(Dialect::PostgreSQL, "\"user\""), (Dialect::MySQL, "`user`"), (Dialect::SQLite, "user")],
+            |d| d.quote_identifier("user"),
+        );
+    }
+
+    #[test]
+    fn test_quote_column() {
+        expect(
+            [
+                (Dialect::PostgreSQL, r#""orders"."amount""#),
+                (Dialect::MySQL, "`orders`.`amount`"),
+                (Dialect::SQLite, "orders.amount"),
+            ],
+            |d| d.quote_column("orders.amount"),
+        );
+        for dialect in Dialect::ALL {
+            for verbatim in ["*", "price * qty", "LOWER(name)", "1", "a.b.c", "\"amount\""] {
+                assert_eq!(dialect.quote_column(verbatim), verbatim);
+            }
+        }
+    }
+
+    #[test]
+    fn test_casts() {
+        expect(
+            [
+                (Dialect::PostgreSQL, "price::TEXT"),
+                (Dialect::MySQL, "CAST(price AS CHAR)"),
+                (Dialect::SQLite, "CAST(price AS TEXT)"),
+            ],
+            |d| d.cast("price", "TEXT"),
+        );
+        for (sql_type, target) in [
+            ("INTEGER", "SIGNED"),
+            ("bigint", "SIGNED"),
+            ("VARCHAR(255)", "CHAR"),
+            ("NUMERIC", "DECIMAL(65, 30)"),
+            ("NUMERIC(10, 2)", "DECIMAL(10, 2)"),
+            ("TIMESTAMP", "DATETIME"),
+            ("DATETIME", "DATETIME"),
+            ("SIGNED", "SIGNED"),
+            ("DECIMAL(10, 2)", "DECIMAL(10, 2)"),
+        ] {
+            assert_eq!(Dialect::MySQL.cast("x", sql_type), format!("CAST(x AS {})", target));
+        }
+        expect(
+            [(Dialect::PostgreSQL, "$2::numeric"), (Dialect::MySQL, "?"), (Dialect::SQLite, "?")],
+            |d| d.typed_placeholder(2, "numeric"),
+        );
+    }
+
+    #[test]
+    fn test_limit_offset() {
+        for dialect in Dialect::ALL {
+            assert_eq!(dialect.limit_offset(true, true), " LIMIT {} OFFSET {}");
+            assert_eq!(dialect.limit_offset(true, false), " LIMIT {}");
+            assert_eq!(dialect.limit_offset(false, false), "");
+        }
+        expect(
+            [
+                (Dialect::PostgreSQL, " OFFSET {}"),
+                (Dialect::MySQL, " LIMIT 18446744073709551615 OFFSET {}"),
+                (Dialect::SQLite, " LIMIT -1 OFFSET {}"),
+            ],
+            |d| d.limit_offset(false, true).to_string(),
+        );
+    }
+
+    #[test]
+    fn test_aggregate_filter() {
+        assert!(Dialect::PostgreSQL.supports_aggregate_filter());
+        assert!(!Dialect::MySQL.supports_aggregate_filter());
+        assert!(Dialect::SQLite.supports_aggregate_filter());
+    }
+
+    #[test]
+    fn test_returning() {
+        assert!(Dialect::PostgreSQL.supports_returning());
+        assert!(!Dialect::MySQL.supports_returning());
+        assert!(Dialect::SQLite.supports_returning());
+    }
+
+    #[test]
+    fn test_max_bind_params() {
+        expect(
+            [(Dialect::PostgreSQL, "65535"), (Dialect::MySQL, "65535"), (Dialect::SQLite, "32766")],
+            |d| d.max_bind_params().to_string(),
+        );
+    }
+
+    #[test]
+    fn test_upsert() {
+        let insert = |d: Dialect| format!("INSERT INTO t (id,name) VALUES ({},{})", d.placeholder(1), d.placeholder(2));
+        expect(
+            [
+                (Dialect::PostgreSQL, r#"INSERT INTO t (id,name) VALUES ($1,$2) ON CONFLICT ("id") DO UPDATE SET "name"=excluded."name""#),
+                (Dialect::MySQL, "INSERT INTO t (id,name) VALUES (?,?) ON DUPLICATE KEY UPDATE `name`=VALUES(`name`)"),
+                (Dialect::SQLite, "INSERT INTO t (id,name) VALUES (?,?) ON CONFLICT (id) DO UPDATE SET name=excluded.name"),
+            ],
+            |d| d.upsert(&insert(d), &["id"], &["name"]),
+        );
+        expect(
+            [
+                (Dialect::PostgreSQL, "INSERT INTO t (id,name) VALUES ($1,$2) ON CONFLICT DO NOTHING"),
+                (Dialect::MySQL, "INSERT IGNORE INTO t (id,name) VALUES (?,?)"),
+                (Dialect::SQLite, "INSERT INTO t (id,name) VALUES (?,?) ON CONFLICT DO NOTHING"),
+            ],
+            |d| d.upsert(&insert(d), &[], &[]),
+        );
+    }
+}
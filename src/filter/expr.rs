@@ -0,0 +1,462 @@
+//! Column handles and composable filter expressions.
+
+use sqlx::Database;
+
+use super::subquery::IntoSubquery;
+use crate::proxy::{BindProxy, BindValue};
+use crate::Dialect;
+
+/// A column of an entity's table, generated by `#[derive(EnhancedCrud)]`.
+///
+/// Each field `foo` of a derived struct gets an associated constant `FOO`:
+///
+/// ```ignore
+/// #[derive(EnhancedCrud)]
+/// struct User { id: String, email: String, age: i32 }
+///
+/// let expr = User::EMAIL.eq("alice@example.com").and(User::AGE.ge(18));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    table: &'static str,
+    name: &'static str,
+    qualified: bool,
+    sql_type: Option<&'static str>,
+}
+
+impl Column {
+    /// Creates a column handle for `table.name`.
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Self { table, name, qualified: false, sql_type: None }
+    }
+
+    /// Records the database type of the column (e.g. "BIGINT", "NUMERIC").
+    ///
+    /// Generated constants carry it when the field's Rust type determines it;
+    /// keyset cursors cast their bound text back to it on PostgreSQL.
+    pub const fn typed(self, sql_type: &'static str) -> Self {
+        Self { sql_type: Some(sql_type), ..self }
+    }
+
+    /// Returns the same column rendered as `table.column`.
+    ///
+    /// Needed when the column name is ambiguous, e.g. in JOIN queries.
+    pub const fn qualified(self) -> Self {
+        Self { qualified: true, ..self }
+    }
+
+    /// Returns the same column qualified by a table alias, as `alias.column`.
+    ///
+    /// Used with aliased and self-joins, where the table name is ambiguous.
+    pub const fn aliased(self, alias: &'static str) -> Self {
+        Self { table: alias, qualified: true, ..self }
+    }
+
+    /// The table this column belongs to.
+    pub fn table(&self) -> &'static str {
+        self.table
+    }
+
+    /// The column name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The database type of the column, if known.
+    pub fn sql_type(&self) -> Option<&'static str> {
+        self.sql_type
+    }
+
+    /// Renders the column as a quoted SQL identifier.
+    pub fn to_sql(self) -> String {
+        let db = Dialect::current();
+        if self.qualified {
+            format!("{}.{}", db.quote_identifier(self.table), db.quote_identifier(self.name))
+        } else {
+            db.quote_identifier(self.name)
+        }
+    }
+
+    fn compare<DB: Database, V: BindProxy<DB>>(self, op: &str, value: V) -> Expr<DB> {
+        let value = value.into_bind_value();
+        Expr::predicate(
+            format!("{} {} {}", self.to_sql(), op, placeholder(&value)),
+            vec![value],
+        )
+    }
+
+    /// `column = value`
+    pub fn eq<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare("=", value)
+    }
+
+    /// `column <> value`
+    pub fn ne<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare("<>", value)
+    }
+
+    /// `column > value`
+    pub fn gt<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare(">", value)
+    }
+
+    /// `column >= value`
+    pub fn ge<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare(">=", value)
+    }
+
+    /// `column < value`
+    pub fn lt<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare("<", value)
+    }
+
+    /// `column <= value`
+    pub fn le<DB: Database, V: BindProxy<DB>>(self, value: V) -> Expr<DB> {
+        self.compare("<=", value)
+    }
+
+    /// `column LIKE pattern`
+    pub fn like<DB: Database, V: BindProxy<DB>>(self, pattern: V) -> Expr<DB> {
+        self.compare("LIKE", pattern)
+    }
+
+    /// `column IS NULL`
+    pub fn is_null<DB: Database>(self) -> Expr<DB> {
+        Expr::predicate(format!("{} IS NULL", self.to_sql()), Vec::new())
+    }
+
+    /// `column IS NOT NULL`
+    pub fn is_not_null<DB: Database>(self) -> Expr<DB> {
+        Expr::predicate(format!("{} IS NOT NULL", self.to_sql()), Vec::new())
+    }
+
+    /// `column BETWEEN low AND high`
+    pub fn between<DB: Database, V: BindProxy<DB>>(self, low: V, high: V) -> Expr<DB> {
+        let low = low.into_bind_value();
+        let high = high.into_bind_value();
+        Expr::predicate(
+            format!("{} BETWEEN {} AND {}", self.to_sql(), placeholder(&low), placeholder(&high)),
+            vec![low, high],
+        )
+    }
+
+    /// `column IN (v1, v2, ...)`
+    ///
+    /// An empty list renders as `1=0`, which matches no rows.
+    pub fn in_<DB, I>(self, values: I) -> Expr<DB>
+    where
+        DB: Database,
+        I: IntoIterator,
+        I::Item: BindProxy<DB>,
+    {
+        self.in_list("IN", "1=0", values)
+    }
+
+    /// `column NOT IN (v1, v2, ...)`
+    ///
+    /// An empty list renders as `1=1`, which matches every row.
+    pub fn not_in<DB, I>(self, values: I) -> Expr<DB>
+    where
+        DB: Database,
+        I: IntoIterator,
+        I::Item: BindProxy<DB>,
+    {
+        self.in_list("NOT IN", "1=1", values)
+    }
+
+    /// `column IN (SELECT ...)`
+    ///
+    /// The subquery must select a single column.
+    pub fn in_subquery<DB: Database, Q: IntoSubquery<DB>>(self, query: Q) -> Expr<DB> {
+        Expr::subquery_predicate(&format!("{} IN ({{}})", self.to_sql()), query)
+    }
+
+    /// `column NOT IN (SELECT ...)`
+    ///
+    /// Matches no rows if the subquery returns a NULL.
+    pub fn not_in_subquery<DB: Database, Q: IntoSubquery<DB>>(self, query: Q) -> Expr<DB> {
+        Expr::subquery_predicate(&format!("{} NOT IN ({{}})", self.to_sql()), query)
+    }
+
+    /// `column = other`, e.g. to correlate an [`Expr::exists`] subquery with
+    /// the outer query.
+    pub fn eq_column<DB: Database>(self, other: Column) -> Expr<DB> {
+        Expr::predicate(format!("{} = {}", self.to_sql(), other.to_sql()), Vec::new())
+    }
+
+    fn in_list<DB, I>(self, op: &str, empty: &str, values: I) -> Expr<DB>
+    where
+        DB: Database,
+        I: IntoIterator,
+        I::Item: BindProxy<DB>,
+    {
+        let params: Vec<BindValue<DB>> = values.into_iter().map(|v| v.into_bind_value()).collect();
+        if params.is_empty() {
+            return Expr::predicate(empty.to_string(), params);
+        }
+        let placeholders: Vec<&str> = params.iter().map(placeholder).collect();
+        Expr::predicate(
+            format!("{} {} ({})", self.to_sql(), op, placeholders.join(", ")),
+            params,
+        )
+    }
+}
+
+/// Placeholder for a bind value, with the PostgreSQL cast needed for values
+/// that `BindProxy` converts to strings (NUMERIC, dates, UUIDs).
+fn placeholder<DB: Database>(value: &BindValue<DB>) -> &'static str {
+    if !matches!(Dialect::current(), Dialect::PostgreSQL) {
+        return "{}";
+    }
+    match value {
+        BindValue::Decimal(_) => "{}::numeric",
+        BindValue::NaiveDate(_) => "{}::date",
+        BindValue::NaiveTime(_) => "{}::time",
+        BindValue::NaiveDateTime(_) => "{}::timestamp",
+        BindValue::DateTimeUtc(_) => "{}::timestamptz",
+        BindValue::Uuid(_) => "{}::uuid",
+        _ => "{}",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExprKind {
+    Predicate,
+    And,
+    Or,
+}
+
+/// A boolean SQL expression with its bind values.
+///
+/// The SQL uses `{}` placeholders, exactly like the strings accepted by
+/// `where_query`, and is turned into `$n` / `?` markers when the final
+/// statement is prepared. Bind values are kept in placeholder order.
+#[derive(Debug)]
+pub struct Expr<DB: Database> {
+    sql: String,
+    params: Vec<BindValue<DB>>,
+    kind: ExprKind,
+    /// The problem of an invalid nested subquery, reported by the builder
+    /// the expression is passed to
+    problem: Option<String>,
+}
+
+impl<DB: Database> Clone for Expr<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            sql: self.sql.clone(),
+            params: self.params.clone(),
+            kind: self.kind,
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Expr<DB> {
+    fn predicate(sql: String, params: Vec<BindValue<DB>>) -> Self {
+        Self { sql, params, kind: ExprKind::Predicate, problem: None }
+    }
+
+    fn subquery_predicate<Q: IntoSubquery<DB>>(template: &str, query: Q) -> Self {
+        let query = query.into_subquery();
+        let problem = query.problem().map(str::to_string);
+        let (sql, params) = query.into_parts();
+        Self { problem, ..Self::predicate(template.replacen("{}", &sql, 1), params) }
+    }
+
+    /// Wraps a raw SQL fragment with `{}` placeholders and its bind values.
+    ///
+    /// ```ignore
+    /// Expr::raw("LOWER(email) = {}", vec!["alice@example.com".into_bind_value()])
+    /// ```
+    pub fn raw(sql: &str, params: Vec<BindValue<DB>>) -> Self {
+        Self::predicate(format!("({})", sql), params)
+    }
+
+    /// `EXISTS (SELECT ...)`
+    ///
+    /// ```ignore
+    /// // Customers with at least one paid order
+    /// Customer::select().filter(Expr::exists(
+    ///     Order::select().filter(
+    ///         Order::CUSTOMER_ID.qualified().eq_column(Customer::ID.qualified())
+    ///             .and(Order::STATUS.eq("paid")),
+    ///     ),
+    /// ))
+    /// ```
+    pub fn exists<Q: IntoSubquery<DB>>(query: Q) -> Self {
+        Self::subquery_predicate("EXISTS ({})", query)
+    }
+
+    /// `NOT EXISTS (SELECT ...)`
+    pub fn not_exists<Q: IntoSubquery<DB>>(query: Q) -> Self {
+        Self::subquery_predicate("NOT EXISTS ({})", query)
+    }
+
+    /// `self AND other`
+    pub fn and(self, other: Expr<DB>) -> Self {
+        self.combine(ExprKind::And, other)
+    }
+
+    /// `self OR other`
+    pub fn or(self, other: Expr<DB>) -> Self {
+        self.combine(ExprKind::Or, other)
+    }
+
+    /// `NOT (self)`
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self { problem: self.problem, ..Self::predicate(format!("NOT ({})", self.sql), self.params) }
+    }
+
+    fn combine(mut self, kind: ExprKind, other: Expr<DB>) -> Self {
+        let keyword = if kind == ExprKind::And { "AND" } else { "OR" };
+        let left = self.operand_for(kind);
+        let right = other.operand_for(kind);
+        self.params.extend(other.params);
+        Self {
+            sql: format!("{} {} {}", left, keyword, right),
+            params: self.params,
+            kind,
+            problem: self.problem.or(other.problem),
+        }
+    }
+
+    // AND binds tighter than OR, so only OR groups nested under AND need parentheses.
+    fn operand_for(&self, parent: ExprKind) -> String {
+        if parent == ExprKind::And && self.kind == ExprKind::Or {
+            format!("({})", self.sql)
+        } else {
+            self.sql.clone()
+        }
+    }
+
+    /// The SQL fragment with `{}` placeholders.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The bind values, in placeholder order.
+    pub fn params(&self) -> &[BindValue<DB>] {
+        &self.params
+    }
+
+    /// Why a nested subquery is invalid, if one is.
+    pub(crate) fn problem(&self) -> Option<&str> {
+        self.problem.as_deref()
+    }
+
+    /// Splits the expression into its SQL fragment and bind values.
+    pub fn into_parts(self) -> (String, Vec<BindValue<DB>>) {
+        (self.sql, self.params)
+    }
+}
+
+impl<DB: Database> std::ops::Not for Expr<DB> {
+    type Output = Expr<DB>;
+
+    fn not(self) -> Self::Output {
+        Expr::not(self)
+    }
+}
+
+/// Anything that can be used as a WHERE clause: a raw `&str` with `{}`
+/// placeholders (values bound by the caller) or an [`Expr`] (values bound
+/// automatically).
+pub trait IntoWhere<DB: Database> {
+    /// Returns the WHERE template and the values to bind, in order.
+    fn into_where(self) -> (String, Vec<BindValue<DB>>);
+}
+
+impl<DB: Database> IntoWhere<DB> for &str {
+    fn into_where(self) -> (String, Vec<BindValue<DB>>) {
+        (self.to_string(), Vec::new())
+    }
+}
+
+impl<DB: Database> IntoWhere<DB> for &String {
+    fn into_where(self) -> (String, Vec<BindValue<DB>>) {
+        (self.clone(), Vec::new())
+    }
+}
+
+impl<DB: Database> IntoWhere<DB> for Expr<DB> {
+    fn into_where(self) -> (String, Vec<BindValue<DB>>) {
+        self.into_parts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::Subquery;
+
+    const EMAIL: Column = Column::new("users", "email");
+    const AGE: Column = Column::new("users", "age");
+    const ROLE: Column = Column::new("users", "role");
+
+    #[cfg(feature = "postgres")]
+    type Db = sqlx::Postgres;
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    type Db = sqlx::MySql;
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    type Db = sqlx::Sqlite;
+
+    #[test]
+    fn test_and_or_precedence() {
+        let expr: Expr<Db> = EMAIL.eq("a@b.c").and(AGE.gt(18)).or(ROLE.in_(vec!["admin", "owner"]));
+        #[cfg(feature = "postgres")]
+        assert_eq!(expr.sql(), r#""email" = {} AND "age" > {} OR "role" IN ({}, {})"#);
+        assert_eq!(expr.params().len(), 4);
+
+        let grouped: Expr<Db> = EMAIL.eq("a@b.c").and(AGE.gt(18).or(AGE.is_null()));
+        #[cfg(feature = "postgres")]
+        assert_eq!(grouped.sql(), r#""email" = {} AND ("age" > {} OR "age" IS NULL)"#);
+        assert_eq!(grouped.params().len(), 2);
+    }
+
+    #[test]
+    fn test_not_between_like() {
+        let expr: Expr<Db> = !AGE.between(18, 65).and(EMAIL.like("%@corp.com"));
+        #[cfg(feature = "postgres")]
+        assert_eq!(expr.sql(), r#"NOT ("age" BETWEEN {} AND {} AND "email" LIKE {})"#);
+        assert_eq!(expr.params().len(), 3);
+    }
+
+    #[test]
+    fn test_empty_in_lists() {
+        let none: Expr<Db> = ROLE.in_(Vec::<String>::new());
+        assert_eq!(none.sql(), "1=0");
+        let all: Expr<Db> = ROLE.not_in(Vec::<String>::new());
+        assert_eq!(all.sql(), "1=1");
+    }
+
+    #[test]
+    fn test_qualified_column() {
+        let expr: Expr<Db> = EMAIL.qualified().is_not_null();
+        #[cfg(feature = "postgres")]
+        assert_eq!(expr.sql(), r#""users"."email" IS NOT NULL"#);
+        #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+        assert_eq!(expr.sql(), "`users`.`email` IS NOT NULL");
+    }
+
+    #[test]
+    fn test_subqueries_keep_their_params_in_order() {
+        let paid = Subquery::raw("SELECT user_id FROM orders WHERE status = {}", vec!["paid".into_bind_value()]);
+        let expr: Expr<Db> = AGE.gt(18).and(EMAIL.not_in_subquery(paid.clone())).or(Expr::not_exists(paid));
+        #[cfg(feature = "postgres")]
+        assert_eq!(
+            expr.sql(),
+            r#""age" > {} AND "email" NOT IN (SELECT user_id FROM orders WHERE status = {}) OR NOT EXISTS (SELECT user_id FROM orders WHERE status = {})"#
+        );
+        assert_eq!(expr.params().len(), 3);
+    }
+
+    #[cfg(all(feature = "postgres", feature = "decimal"))]
+    #[test]
+    fn test_postgres_casts_converted_values() {
+        let price = Column::new("products", "price");
+        let expr: Expr<Db> = price.ge(rust_decimal::Decimal::new(1999, 2));
+        assert_eq!(expr.sql(), r#""price" >= {}::numeric"#);
+    }
+}
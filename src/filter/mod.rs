@@ -0,0 +1,45 @@
+//! Type-safe filter expressions for WHERE clauses.
+//!
+//! The `EnhancedCrud` derive generates one [`Column`] constant per field
+//! (`User::EMAIL`, `User::AGE`, ...). Comparing a column with a value yields an
+//! [`Expr`] that carries both the SQL fragment and its typed bind values, so
+//! filters compose without hand-written placeholders:
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::EnhancedCrud;
+//!
+//! let adults = User::where_query(
+//!     User::EMAIL.like("%@example.com")
+//!         .and(User::AGE.gt(18))
+//!         .or(User::ROLE.in_(vec!["admin", "owner"])),
+//! )
+//! .fetch_all(&pool)
+//! .await?;
+//! ```
+//!
+//! Plain strings keep working everywhere an [`Expr`] is accepted, through the
+//! [`IntoWhere`] trait.
+//!
+//! Select and aggregation queries nest as [`Subquery`]s, with their bind
+//! values carried along:
+//!
+//! ```ignore
+//! let big_spenders = Order::agg_query()
+//!     .group_by("customer_id")
+//!     .having("SUM(amount) > {}", &[&1000i64]);
+//!
+//! let customers = Customer::select()
+//!     .filter(Customer::ID.in_subquery(big_spenders))
+//!     .fetch_all(&pool)
+//!     .await?;
+//! ```
+
+mod expr;
+mod order;
+mod subquery;
+
+pub use expr::{Column, Expr, IntoWhere};
+pub use order::{Nulls, Order};
+pub use subquery::{IntoSubquery, Subquery};
+pub(crate) use subquery::With;
+pub(crate) use order::{order_by_sql, order_by_sql_with};
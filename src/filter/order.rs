@@ -0,0 +1,74 @@
+//! Sort directions and NULL placement shared by the query builders.
+
+use super::Column;
+use crate::Dialect;
+
+/// Sort direction for ORDER BY clauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    /// The SQL keyword for this direction.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Order::Asc => "ASC",
+            Order::Desc => "DESC",
+        }
+    }
+}
+
+impl std::fmt::Display for Order {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_sql())
+    }
+}
+
+/// Placement of NULLs in an ORDER BY key.
+///
+/// Rendered as `NULLS FIRST` / `NULLS LAST` on PostgreSQL and SQLite; MySQL has
+/// no such clause, so an `IS NULL` sort key is emitted ahead of the column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+impl Nulls {
+    /// The SQL clause for this placement.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Nulls::First => "NULLS FIRST",
+            Nulls::Last => "NULLS LAST",
+        }
+    }
+}
+
+/// Renders ORDER BY keys (without the keywords), emulating NULL placement on
+/// MySQL.
+pub(crate) fn order_by_sql(keys: &[(Column, Order, Option<Nulls>)]) -> String {
+    order_by_sql_with(keys, |column| column.to_sql())
+}
+
+/// Like [`order_by_sql`], rendering each column with `column_sql`.
+pub(crate) fn order_by_sql_with(
+    keys: &[(Column, Order, Option<Nulls>)],
+    column_sql: impl Fn(&Column) -> String,
+) -> String {
+    let db = Dialect::current();
+    keys.iter()
+        .map(|(column, order, nulls)| {
+            let column_sql = column_sql(column);
+            match (nulls, db) {
+                (None, _) => format!("{} {}", column_sql, order),
+                // MySQL sorts NULLs first for ASC; order on `IS NULL` instead
+                (Some(Nulls::First), Dialect::MySQL) => format!("{} IS NULL DESC, {} {}", column_sql, column_sql, order),
+                (Some(Nulls::Last), Dialect::MySQL) => format!("{} IS NULL ASC, {} {}", column_sql, column_sql, order),
+                (Some(nulls), _) => format!("{} {} {}", column_sql, order, nulls.as_sql()),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,186 @@
+//! SELECT statements nested in filters, FROM and WITH clauses.
+
+use sqlx::Database;
+
+use crate::proxy::BindValue;
+use crate::Dialect;
+
+/// A SELECT statement with `{}` placeholders and its bind values, ready to
+/// be nested in another statement.
+///
+/// Placeholders are only numbered when the outermost statement is prepared,
+/// so a subquery's values take their place among the outer query's without
+/// any offset bookkeeping. Select and aggregation builders convert into a
+/// subquery through [`IntoSubquery`]:
+///
+/// ```ignore
+/// let eu_customers = Customer::select()
+///     .filter(Customer::REGION.eq("EU"))
+///     .select_column(Customer::ID);
+///
+/// let orders = Order::select()
+///     .filter(Order::STATUS.eq("paid").and(Order::CUSTOMER_ID.in_subquery(eu_customers)))
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct Subquery<DB: Database> {
+    sql: String,
+    params: Vec<BindValue<DB>>,
+    /// Why the builder this came from could not be built, reported by the
+    /// outer builder
+    problem: Option<String>,
+}
+
+impl<DB: Database> Clone for Subquery<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            sql: self.sql.clone(),
+            params: self.params.clone(),
+            problem: self.problem.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Subquery<DB> {
+    /// Wraps a raw SELECT statement with `{}` placeholders and its bind values.
+    pub fn raw(sql: &str, params: Vec<BindValue<DB>>) -> Self {
+        Self { sql: sql.to_string(), params, problem: None }
+    }
+
+    /// Marks the subquery as invalid, for builders whose own `build` would
+    /// fail with `problem`.
+    pub(crate) fn with_problem(mut self, problem: Option<String>) -> Self {
+        self.problem = problem;
+        self
+    }
+
+    /// Why the subquery is invalid, if it is.
+    pub(crate) fn problem(&self) -> Option<&str> {
+        self.problem.as_deref()
+    }
+
+    /// The statement with `{}` placeholders.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The bind values, in placeholder order.
+    pub fn params(&self) -> &[BindValue<DB>] {
+        &self.params
+    }
+
+    /// Splits the subquery into its statement and bind values.
+    pub fn into_parts(self) -> (String, Vec<BindValue<DB>>) {
+        (self.sql, self.params)
+    }
+}
+
+/// Anything that can be nested as a subquery: a [`Subquery`], a
+/// `SelectQueryBuilder` or an `AggQueryBuilder`.
+pub trait IntoSubquery<DB: Database> {
+    /// Returns the statement with `{}` placeholders and its bind values.
+    fn into_subquery(self) -> Subquery<DB>;
+}
+
+impl<DB: Database> IntoSubquery<DB> for Subquery<DB> {
+    fn into_subquery(self) -> Subquery<DB> {
+        self
+    }
+}
+
+/// The common table expressions of a WITH clause, with their bind values.
+pub(crate) struct With<DB: Database> {
+    recursive: bool,
+    ctes: Vec<String>,
+    params: Vec<BindValue<DB>>,
+    /// The first invalid CTE's problem
+    problem: Option<String>,
+}
+
+impl<DB: Database> Default for With<DB> {
+    fn default() -> Self {
+        Self { recursive: false, ctes: Vec::new(), params: Vec::new(), problem: None }
+    }
+}
+
+impl<DB: Database> With<DB> {
+    /// Adds `name AS (query)`.
+    pub(crate) fn push(&mut self, name: &str, query: Subquery<DB>) {
+        if self.problem.is_none() {
+            self.problem = query.problem.clone();
+        }
+        let (sql, params) = query.into_parts();
+        self.ctes.push(format!("{} AS ({})", Dialect::current().quote_identifier(name), sql));
+        self.params.extend(params);
+    }
+
+    /// Adds `name AS (anchor UNION step)`, making the clause `WITH RECURSIVE`.
+    ///
+    /// UNION rather than UNION ALL, so a row reached twice, e.g. through a
+    /// cycle, is not expanded again and the recursion ends.
+    pub(crate) fn push_recursive(&mut self, name: &str, anchor: Subquery<DB>, step: Subquery<DB>) {
+        let problem = anchor.problem.clone().or_else(|| step.problem.clone());
+        let (anchor, mut params) = anchor.into_parts();
+        let (step, step_params) = step.into_parts();
+        params.extend(step_params);
+        self.push(name, Subquery::raw(&format!("{} UNION {}", anchor, step), params).with_problem(problem));
+        self.recursive = true;
+    }
+
+    /// Renders `WITH [RECURSIVE] a AS (...), ... `, with the `extra` CTE
+    /// last (its parameters bound after these), or nothing if there are none.
+    pub(crate) fn sql(&self, extra: Option<&str>) -> String {
+        let ctes: Vec<&str> = self.ctes.iter().map(String::as_str).chain(extra).collect();
+        if ctes.is_empty() {
+            return String::new();
+        }
+        format!("WITH {}{} ", if self.recursive { "RECURSIVE " } else { "" }, ctes.join(", "))
+    }
+
+    /// The bind values, in placeholder order.
+    pub(crate) fn params(&self) -> &[BindValue<DB>] {
+        &self.params
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ctes.is_empty()
+    }
+
+    /// Why one of the CTEs is invalid, if one is.
+    pub(crate) fn problem(&self) -> Option<&str> {
+        self.problem.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "postgres")]
+    type Db = sqlx::Postgres;
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    type Db = sqlx::MySql;
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    type Db = sqlx::Sqlite;
+
+    #[test]
+    fn test_with_clause() {
+        let mut with = With::<Db>::default();
+        assert_eq!(with.sql(None), "");
+        assert_eq!(with.sql(Some("q AS (SELECT 1)")), "WITH q AS (SELECT 1) ");
+
+        with.push("a", Subquery::raw("SELECT {}", vec![BindValue::I64(1)]));
+        with.push_recursive(
+            "b",
+            Subquery::raw("SELECT {}", vec![BindValue::I64(2)]),
+            Subquery::raw("SELECT n + {} FROM b", vec![BindValue::I64(3)]),
+        );
+        let (a, b) = (Dialect::current().quote_identifier("a"), Dialect::current().quote_identifier("b"));
+        assert_eq!(
+            with.sql(None),
+            format!("WITH RECURSIVE {} AS (SELECT {{}}), {} AS (SELECT {{}} UNION SELECT n + {{}} FROM b) ", a, b)
+        );
+        assert_eq!(with.params().len(), 3);
+    }
+}
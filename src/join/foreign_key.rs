@@ -0,0 +1,36 @@
+//! JOIN conditions derived from `#[crud(references(..))]` foreign keys.
+
+use super::SchemeAccessor;
+use crate::Dialect;
+
+/// A foreign key linking `Self` and `T`, usable as an equality JOIN condition.
+///
+/// Implemented by `#[derive(EnhancedCrud)]` in both directions for every
+/// `#[crud(references(T))]` field: `Order: ForeignKeyJoin<Customer>` and
+/// `Customer: ForeignKeyJoin<Order>`.
+#[diagnostic::on_unimplemented(
+    message = "no foreign key between `{Self}` and `{T}`",
+    label = "cannot infer the JOIN condition",
+    note = "declare it with #[crud(references({T}))] on the foreign-key field, or pass an explicit condition to join_inner/join_left/join_right/join_full"
+)]
+pub trait ForeignKeyJoin<T> {
+    /// The joined columns: one of `Self`'s table, one of `T`'s table.
+    fn join_columns() -> (&'static str, &'static str);
+
+    /// The ON clause, e.g. `"orders"."customer_id" = "customers"."id"`.
+    fn join_condition() -> String
+    where
+        Self: SchemeAccessor + Sized,
+        T: SchemeAccessor,
+    {
+        let db = Dialect::current();
+        let (left, right) = Self::join_columns();
+        format!(
+            "{}.{} = {}.{}",
+            db.quote_identifier(Self::get_scheme().table_name()),
+            db.quote_identifier(left),
+            db.quote_identifier(T::get_scheme().table_name()),
+            db.quote_identifier(right)
+        )
+    }
+}
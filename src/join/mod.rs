@@ -28,12 +28,30 @@
 //! )
 //! .fetch_all(&pool)
 //! .await?;
+//!
+//! // Or infer the ON clause from a #[crud(references(Customer))] field on Order
+//! let results = Order::join_inner_fk::<Customer>().fetch_all(&pool).await?;
+//! ```
+//!
+//! Chaining further joins returns `JoinTuple3` up to `JoinTuple5`:
+//!
+//! ```ignore
+//! let rows: Vec<JoinTuple3<Order, Customer, Address>> = Order::join_inner::<Customer>(
+//!     "orders.customer_id = customers.id"
+//! )
+//! .join_left::<Address>("addresses.customer_id = customers.id")
+//! .fetch_all(&pool)
+//! .await?;
 //! ```
 
+mod foreign_key;
+mod multi_query_builder;
 mod query_builder;
 mod sql_generator;
 mod tuple_decoder;
 
-pub use query_builder::JoinQueryBuilder;
+pub use foreign_key::ForeignKeyJoin;
+pub use multi_query_builder::{JoinPaginate, JoinWith, MultiJoinQueryBuilder};
+pub use query_builder::{JoinAlias, JoinQueryBuilder};
 pub use sql_generator::{JoinSqlGenerator, JoinType, JoinClause, SchemeAccessor};
-pub use tuple_decoder::{JoinTuple2, JoinTuple3, JoinTuple4, JoinTuple5};
+pub use tuple_decoder::{JoinRow, JoinTuple2, JoinTuple3, JoinTuple4, JoinTuple5};
@@ -0,0 +1,569 @@
+//! Query builder for JOINs over two to five entities.
+
+use super::{JoinRow, JoinSqlGenerator, JoinTuple2, JoinTuple3, JoinTuple4, JoinTuple5, JoinType};
+use super::sql_generator::SchemeAccessor;
+use crate::{get_or_insert_sql, prepare_where, Dialect, Scheme};
+use crate::filter::{order_by_sql, order_by_sql_with, Column, Expr, Nulls, Order};
+use crate::pagination::{Page, TOTAL_COLUMN};
+use crate::proxy::{BindProxy, BindTarget, BindValue};
+use sqlx::{Database, Error, Pool, Row};
+use std::marker::PhantomData;
+
+#[cfg(feature = "postgres")]
+use sqlx::{postgres::PgRow, Postgres};
+
+#[cfg(feature = "mysql")]
+use sqlx::{mysql::MySqlRow, MySql};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{sqlite::SqliteRow, Sqlite};
+
+/// Result tuple obtained by joining one more entity `X`.
+#[diagnostic::on_unimplemented(
+    message = "cannot join `{X}`: a JOIN query returns at most 5 entities",
+    label = "`{Self}` already holds the maximum number of entities"
+)]
+pub trait JoinWith<X> {
+    type Output;
+}
+
+impl<A, B, X> JoinWith<X> for JoinTuple2<A, B> {
+    type Output = JoinTuple3<A, B, X>;
+}
+
+impl<A, B, C, X> JoinWith<X> for JoinTuple3<A, B, C> {
+    type Output = JoinTuple4<A, B, C, X>;
+}
+
+impl<A, B, C, D, X> JoinWith<X> for JoinTuple4<A, B, C, D> {
+    type Output = JoinTuple5<A, B, C, D, X>;
+}
+
+/// JOIN query over several entities, decoding each row into `T`
+/// (`JoinTuple2` to `JoinTuple5`).
+///
+/// Created by chaining a join onto a two-entity [`JoinQueryBuilder`](super::JoinQueryBuilder),
+/// which delegates to this builder:
+///
+/// ```ignore
+/// use sqlx_struct_enhanced::Order as Sort; // the entity here is also named `Order`
+///
+/// let rows: Vec<JoinTuple4<Order, Customer, Address, Region>> =
+///     Order::join_inner::<Customer>(r#""orders"."customer_id" = "customers"."id""#)
+///         .join_left::<Address>(r#""addresses"."customer_id" = "customers"."id""#)
+///         .join_inner::<Region>(r#""regions"."id" = "addresses"."region_id""#)
+///         .filter(Region::CODE.qualified().eq("EU"))
+///         .order_by(Order::CREATED_AT, Sort::Desc)
+///         .limit(50)
+///         .fetch_all(&pool)
+///         .await?;
+/// ```
+pub struct MultiJoinQueryBuilder<'a, T, DB: Database> {
+    base: (&'static Scheme, Option<String>),
+    /// Scheme, alias, JOIN type and condition of each joined table
+    joins: Vec<(&'static Scheme, Option<String>, JoinType, String)>,
+    where_clause: Option<String>,
+    where_params: Vec<BindValue<DB>>,
+    order: Vec<(Column, Order, Option<Nulls>)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    _phantom: PhantomData<(T, &'a DB)>,
+}
+
+impl<'a, T, DB: Database> MultiJoinQueryBuilder<'a, T, DB> {
+    pub(super) fn from_parts(
+        base: (&'static Scheme, Option<String>),
+        joins: Vec<(&'static Scheme, Option<String>, JoinType, String)>,
+    ) -> Self {
+        Self {
+            base,
+            joins,
+            where_clause: None,
+            where_params: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Add an INNER JOIN with another entity.
+    pub fn join_inner<X: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(None, JoinType::Inner, condition)
+    }
+
+    /// Add a LEFT JOIN with another entity.
+    pub fn join_left<X: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(None, JoinType::Left, condition)
+    }
+
+    /// Add a RIGHT JOIN with another entity.
+    pub fn join_right<X: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(None, JoinType::Right, condition)
+    }
+
+    /// Add a FULL JOIN with another entity.
+    pub fn join_full<X: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(None, JoinType::Full, condition)
+    }
+
+    /// Add an INNER JOIN with another entity under an alias; see
+    /// [`JoinQueryBuilder::join_inner_as`](super::JoinQueryBuilder::join_inner_as).
+    pub fn join_inner_as<X: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(Some(alias), JoinType::Inner, condition)
+    }
+
+    /// Add a LEFT JOIN with another entity under an alias.
+    pub fn join_left_as<X: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(Some(alias), JoinType::Left, condition)
+    }
+
+    /// Add a RIGHT JOIN with another entity under an alias.
+    pub fn join_right_as<X: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(Some(alias), JoinType::Right, condition)
+    }
+
+    /// Add a FULL JOIN with another entity under an alias.
+    pub fn join_full_as<X: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.join::<X>(Some(alias), JoinType::Full, condition)
+    }
+
+    fn join<X: SchemeAccessor>(mut self, alias: Option<&str>, join_type: JoinType, condition: &str) -> MultiJoinQueryBuilder<'a, T::Output, DB>
+    where
+        T: JoinWith<X>,
+    {
+        self.joins.push((X::get_scheme(), alias.map(str::to_string), join_type, condition.to_string()));
+        MultiJoinQueryBuilder {
+            base: self.base,
+            joins: self.joins,
+            where_clause: self.where_clause,
+            where_params: self.where_params,
+            order: self.order,
+            limit: self.limit,
+            offset: self.offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Add a WHERE clause with the given statement and parameters.
+    ///
+    /// The statement should use "{}" as parameter placeholders. The parameters
+    /// are bound as strings; use [`bind_proxy`](Self::bind_proxy) for typed values.
+    pub fn where_(mut self, clause: &str, params: &[&str]) -> Self {
+        self.where_clause = Some(clause.to_string());
+        self.where_params = params.iter().map(|s| BindValue::String(s.to_string())).collect();
+        self
+    }
+
+    /// Add a WHERE clause from a filter expression, binding its values natively.
+    ///
+    /// Use qualified columns to avoid ambiguity between the joined tables.
+    pub fn filter(mut self, expr: Expr<DB>) -> Self {
+        let (clause, params) = expr.into_parts();
+        self.where_clause = Some(clause);
+        self.where_params = params;
+        self
+    }
+
+    /// Bind the next "{}" placeholder of the WHERE clause with its native
+    /// type (integers, timestamps, decimals, ...), after any `where_` strings.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .where_("orders.amount >= {} AND orders.created_at >= {}", &[])
+    /// .bind_proxy(100)
+    /// .bind_proxy(since)
+    /// ```
+    pub fn bind_proxy<V: BindProxy<DB>>(mut self, value: V) -> Self {
+        self.where_params.push(value.into_bind_value());
+        self
+    }
+
+    /// Sort by a column of any joined entity. The column is always rendered
+    /// table-qualified; use [`Column::aliased`] for aliased tables.
+    pub fn order_by(mut self, column: Column, order: Order) -> Self {
+        self.order.push((column.qualified(), order, None));
+        self
+    }
+
+    /// Sort by a column with explicit NULL placement.
+    pub fn order_by_nulls(mut self, column: Column, order: Order, nulls: Nulls) -> Self {
+        self.order.push((column.qualified(), order, Some(nulls)));
+        self
+    }
+
+    /// Return at most `n` rows.
+    pub fn limit(mut self, n: u64) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Skip the first `n` rows.
+    pub fn offset(mut self, n: u64) -> Self {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Offset pagination returning a `Page` with totals. `page` is 1-based;
+    /// `limit` and `offset` are replaced by the page bounds.
+    pub fn paginate(mut self, page: u64, per_page: u64) -> JoinPaginate<'a, T, DB> {
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+        self.limit = Some(per_page);
+        // Saturates at the largest OFFSET the databases accept
+        self.offset = Some((page - 1).saturating_mul(per_page).min(i64::MAX as u64));
+        JoinPaginate { query: self, page, per_page }
+    }
+
+    /// The SQL this query runs, e.g. for logging.
+    pub fn sql(&self) -> &'static str {
+        self.build(false)
+    }
+
+    /// Build the SQL query and return a cached `&'static str`.
+    ///
+    /// The cache key covers every table, JOIN type and condition as well as
+    /// the WHERE, ORDER BY and LIMIT/OFFSET clauses.
+    fn build(&self, with_total: bool) -> &'static str {
+        let mut tail = String::new();
+        if !self.order.is_empty() {
+            tail.push_str(" ORDER BY ");
+            if self.is_union() {
+                // A UNION can only be ordered by its result columns
+                tail.push_str(&order_by_sql_with(&self.order, |column| {
+                    JoinSqlGenerator::quote_qualified_column(column.table(), column.name())
+                }));
+            } else {
+                tail.push_str(&order_by_sql(&self.order));
+            }
+        }
+        tail.push_str(Dialect::current().limit_offset(self.limit.is_some(), self.offset.is_some()));
+
+        let cache_key = format!(
+            "join-{}-where-{}{}-total-{}",
+            self.shape_key(),
+            self.where_clause.as_deref().unwrap_or(""),
+            tail,
+            with_total
+        );
+        get_or_insert_sql(cache_key, || {
+            let total = if with_total {
+                format!(", COUNT(*) OVER() AS {}", Dialect::current().quote_identifier(TOTAL_COLUMN))
+            } else {
+                String::new()
+            };
+            let statement = self.generator().gen_select_query(&total, self.where_clause.as_deref());
+            prepare_where(&format!("{}{}", statement, tail), 1)
+        })
+    }
+
+    /// The SQL to execute, once the JOINs are known to be supported.
+    fn statement(&self, with_total: bool) -> Result<&'static str, Error> {
+        self.generator().check_supported()?;
+        Ok(self.build(with_total))
+    }
+
+    /// Count of the rows matched by the joins and WHERE clause.
+    fn count_sql(&self) -> &'static str {
+        let where_clause = self.where_clause.as_deref();
+        let cache_key = format!("join-count-{}-where-{}", self.shape_key(), where_clause.unwrap_or(""));
+        get_or_insert_sql(cache_key, || prepare_where(&self.generator().gen_count_query(where_clause), 1))
+    }
+
+    /// Whether a FULL JOIN is emulated with a UNION ALL, which repeats the
+    /// WHERE clause (and its binds) in both SELECTs.
+    fn is_union(&self) -> bool {
+        self.joins.iter().any(|(_, _, join_type, _)| *join_type == JoinType::Full && !join_type.is_native())
+    }
+
+    fn shape_key(&self) -> String {
+        let joins = self.joins.iter()
+            .map(|(scheme, alias, join_type, condition)| {
+                format!("{}-{}-as-{:?}-{}", join_type, scheme.table_name(), alias, condition)
+            })
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}-as-{:?}-{}", self.base.0.table_name(), self.base.1, joins)
+    }
+
+    fn generator(&self) -> JoinSqlGenerator {
+        let joins: Vec<(&Scheme, Option<&str>, JoinType, &str)> = self.joins.iter()
+            .map(|(scheme, alias, join_type, condition)| (*scheme, alias.as_deref(), *join_type, condition.as_str()))
+            .collect();
+        JoinSqlGenerator::with_aliases((self.base.0, self.base.1.as_deref()), &joins)
+    }
+
+    /// The alias, or else the table name, of each entity in tuple order.
+    fn qualifiers(&self) -> Vec<&str> {
+        let (base, alias) = &self.base;
+        std::iter::once(alias.as_deref().unwrap_or(base.table_name()))
+            .chain(self.joins.iter().map(|(scheme, alias, _, _)| alias.as_deref().unwrap_or(scheme.table_name())))
+            .collect()
+    }
+}
+
+/// Offset pagination over a JOIN query, created by `paginate`.
+///
+/// Like [`Paginate`](crate::pagination::Paginate), the total comes back with
+/// the page through `COUNT(*) OVER()` where window functions are available.
+pub struct JoinPaginate<'a, T, DB: Database> {
+    query: MultiJoinQueryBuilder<'a, T, DB>,
+    page: u64,
+    per_page: u64,
+}
+
+impl<T, DB: Database> JoinPaginate<'_, T, DB> {
+    /// The SQL used to fetch the page (LIMIT and OFFSET are the last two binds).
+    pub fn sql(&self) -> &'static str {
+        self.query.build(self.window_total())
+    }
+
+    // MySQL 5.7 has no window functions, so the total needs its own COUNT query.
+    const WINDOW_TOTAL: bool = cfg!(not(feature = "mysql_5_7"));
+
+    /// Whether the total comes back with the rows. A window over an emulated
+    /// FULL JOIN would only count one side of the UNION.
+    fn window_total(&self) -> bool {
+        Self::WINDOW_TOTAL && !self.query.is_union()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> MultiJoinQueryBuilder<'_, T, Postgres> {
+    fn bind_where<'q, Q: BindTarget<'q, Postgres>>(&self, mut query: Q) -> Q {
+        let repeat = if self.is_union() { 2 } else { 1 };
+        for _ in 0..repeat {
+            for param in self.where_params.iter().cloned() {
+                query = param.bind_to(query);
+            }
+        }
+        query
+    }
+
+    fn bind_params<'q, Q: BindTarget<'q, Postgres>>(&self, query: Q) -> Q {
+        let mut query = self.bind_where(query);
+        if let Some(n) = self.limit {
+            query = query.bind_value(n as i64);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind_value(n as i64);
+        }
+        query
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> MultiJoinQueryBuilder<'_, T, Postgres>
+where
+    T: JoinRow<PgRow>,
+{
+    /// Execute the query and fetch all results.
+    pub async fn fetch_all(self, pool: &Pool<Postgres>) -> Result<Vec<T>, Error> {
+        let rows = self.bind_params(sqlx::query(self.statement(false)?)).fetch_all(pool).await?;
+        let qualifiers = self.qualifiers();
+        rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect()
+    }
+
+    /// Execute the query and fetch exactly one result.
+    pub async fn fetch_one(self, pool: &Pool<Postgres>) -> Result<T, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_one(pool).await?;
+        T::from_join_row(&row, &self.qualifiers())
+    }
+
+    /// Execute the query and fetch at most one result.
+    pub async fn fetch_optional(self, pool: &Pool<Postgres>) -> Result<Option<T>, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_optional(pool).await?;
+        row.map(|row| T::from_join_row(&row, &self.qualifiers())).transpose()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> JoinPaginate<'_, T, Postgres>
+where
+    T: JoinRow<PgRow>,
+{
+    /// Execute the query and return the requested page.
+    pub async fn fetch(self, pool: &Pool<Postgres>) -> Result<Page<T>, Error> {
+        let rows = self.query.bind_params(sqlx::query(self.query.statement(self.window_total())?)).fetch_all(pool).await?;
+        let total = match rows.first() {
+            Some(row) if self.window_total() => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if self.page == 1 => 0,
+            // Past the last page no row carries the window total
+            _ => {
+                let count = sqlx::query_as::<_, (i64,)>(self.query.count_sql());
+                self.query.bind_where(count).fetch_one(pool).await?.0
+            }
+        };
+        let qualifiers = self.query.qualifiers();
+        let items = rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T> MultiJoinQueryBuilder<'_, T, MySql> {
+    fn bind_where<'q, Q: BindTarget<'q, MySql>>(&self, mut query: Q) -> Q {
+        let repeat = if self.is_union() { 2 } else { 1 };
+        for _ in 0..repeat {
+            for param in self.where_params.iter().cloned() {
+                query = param.bind_to(query);
+            }
+        }
+        query
+    }
+
+    fn bind_params<'q, Q: BindTarget<'q, MySql>>(&self, query: Q) -> Q {
+        let mut query = self.bind_where(query);
+        if let Some(n) = self.limit {
+            query = query.bind_value(n);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind_value(n);
+        }
+        query
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T> MultiJoinQueryBuilder<'_, T, MySql>
+where
+    T: JoinRow<MySqlRow>,
+{
+    /// Execute the query and fetch all results.
+    pub async fn fetch_all(self, pool: &Pool<MySql>) -> Result<Vec<T>, Error> {
+        let rows = self.bind_params(sqlx::query(self.statement(false)?)).fetch_all(pool).await?;
+        let qualifiers = self.qualifiers();
+        rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect()
+    }
+
+    /// Execute the query and fetch exactly one result.
+    pub async fn fetch_one(self, pool: &Pool<MySql>) -> Result<T, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_one(pool).await?;
+        T::from_join_row(&row, &self.qualifiers())
+    }
+
+    /// Execute the query and fetch at most one result.
+    pub async fn fetch_optional(self, pool: &Pool<MySql>) -> Result<Option<T>, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_optional(pool).await?;
+        row.map(|row| T::from_join_row(&row, &self.qualifiers())).transpose()
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T> JoinPaginate<'_, T, MySql>
+where
+    T: JoinRow<MySqlRow>,
+{
+    /// Execute the query and return the requested page.
+    pub async fn fetch(self, pool: &Pool<MySql>) -> Result<Page<T>, Error> {
+        let rows = self.query.bind_params(sqlx::query(self.query.statement(self.window_total())?)).fetch_all(pool).await?;
+        let total = match rows.first() {
+            Some(row) if self.window_total() => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if self.page == 1 => 0,
+            _ => {
+                let count = sqlx::query_as::<_, (i64,)>(self.query.count_sql());
+                self.query.bind_where(count).fetch_one(pool).await?.0
+            }
+        };
+        let qualifiers = self.query.qualifiers();
+        let items = rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<T> MultiJoinQueryBuilder<'_, T, Sqlite> {
+    fn bind_where<'q, Q: BindTarget<'q, Sqlite>>(&self, mut query: Q) -> Q {
+        let repeat = if self.is_union() { 2 } else { 1 };
+        for _ in 0..repeat {
+            for param in self.where_params.iter().cloned() {
+                query = param.bind_to(query);
+            }
+        }
+        query
+    }
+
+    fn bind_params<'q, Q: BindTarget<'q, Sqlite>>(&self, query: Q) -> Q {
+        let mut query = self.bind_where(query);
+        if let Some(n) = self.limit {
+            query = query.bind_value(n as i64);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind_value(n as i64);
+        }
+        query
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<T> MultiJoinQueryBuilder<'_, T, Sqlite>
+where
+    T: JoinRow<SqliteRow>,
+{
+    /// Execute the query and fetch all results.
+    pub async fn fetch_all(self, pool: &Pool<Sqlite>) -> Result<Vec<T>, Error> {
+        let rows = self.bind_params(sqlx::query(self.statement(false)?)).fetch_all(pool).await?;
+        let qualifiers = self.qualifiers();
+        rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect()
+    }
+
+    /// Execute the query and fetch exactly one result.
+    pub async fn fetch_one(self, pool: &Pool<Sqlite>) -> Result<T, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_one(pool).await?;
+        T::from_join_row(&row, &self.qualifiers())
+    }
+
+    /// Execute the query and fetch at most one result.
+    pub async fn fetch_optional(self, pool: &Pool<Sqlite>) -> Result<Option<T>, Error> {
+        let row = self.bind_params(sqlx::query(self.statement(false)?)).fetch_optional(pool).await?;
+        row.map(|row| T::from_join_row(&row, &self.qualifiers())).transpose()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<T> JoinPaginate<'_, T, Sqlite>
+where
+    T: JoinRow<SqliteRow>,
+{
+    /// Execute the query and return the requested page.
+    pub async fn fetch(self, pool: &Pool<Sqlite>) -> Result<Page<T>, Error> {
+        let rows = self.query.bind_params(sqlx::query(self.query.statement(self.window_total())?)).fetch_all(pool).await?;
+        let total = match rows.first() {
+            Some(row) if self.window_total() => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if self.page == 1 => 0,
+            // Past the last page no row carries the window total
+            _ => {
+                let count = sqlx::query_as::<_, (i64,)>(self.query.count_sql());
+                self.query.bind_where(count).fetch_one(pool).await?.0
+            }
+        };
+        let qualifiers = self.query.qualifiers();
+        let items = rows.iter().map(|row| T::from_join_row(row, &qualifiers)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
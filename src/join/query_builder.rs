@@ -3,10 +3,14 @@
 //! Provides a type-safe builder pattern for constructing and executing
 //! JOIN queries that return entity tuples.
 
-use super::{JoinType, JoinSqlGenerator, JoinTuple2};
+use super::{JoinType, JoinTuple2, JoinTuple3, JoinPaginate, MultiJoinQueryBuilder};
 use super::sql_generator::SchemeAccessor;
-use crate::{prepare_where, get_or_insert_sql};
+use crate::filter::{Column, Expr, Nulls, Order};
+use crate::proxy::BindProxy;
+use crate::relation::Entity;
 use sqlx::{Database, Pool, Error};
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::marker::PhantomData;
 
 #[cfg(feature = "postgres")]
@@ -38,31 +42,22 @@ where
     B: SchemeAccessor,
     DB: Database,
 {
-    join_type: JoinType,
-    join_condition: String,
-    where_clause: Option<String>,
-    where_params: Vec<String>,
-    _phantom_a: PhantomData<A>,
-    _phantom_b: PhantomData<B>,
-    _phantom_db: PhantomData<&'a DB>,
+    inner: MultiJoinQueryBuilder<'a, JoinTuple2<A, B>, DB>,
 }
 
-#[cfg(feature = "postgres")]
-impl<'a, A, B> JoinQueryBuilder<'a, A, B, Postgres>
+impl<'a, A, B, DB> JoinQueryBuilder<'a, A, B, DB>
 where
-    A: SchemeAccessor + Unpin + Send,
-    B: SchemeAccessor + Unpin + Send,
+    A: SchemeAccessor,
+    B: SchemeAccessor,
+    DB: Database,
 {
     /// Create a new JOIN query builder.
     pub fn new(join_type: JoinType, condition: &str) -> Self {
         Self {
-            join_type,
-            join_condition: condition.to_string(),
-            where_clause: None,
-            where_params: Vec::new(),
-            _phantom_a: PhantomData,
-            _phantom_b: PhantomData,
-            _phantom_db: PhantomData,
+            inner: MultiJoinQueryBuilder::from_parts(
+                (A::get_scheme(), None),
+                vec![(B::get_scheme(), None, join_type, condition.to_string())],
+            ),
         }
     }
 
@@ -75,34 +70,248 @@ where
     /// ```ignore
     /// .where_("orders.status = {} AND customers.region = {}", &["completed", "north"])
     /// ```
-    pub fn where_(mut self, clause: &str, params: &[&str]) -> Self {
-        self.where_clause = Some(clause.to_string());
-        self.where_params = params.iter().map(|s| s.to_string()).collect();
-        self
+    pub fn where_(self, clause: &str, params: &[&str]) -> Self {
+        Self { inner: self.inner.where_(clause, params) }
+    }
+
+    /// Add a WHERE clause from a filter expression, binding its values natively.
+    ///
+    /// Use qualified columns to avoid ambiguity between the joined tables.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// .filter(Order::STATUS.qualified().eq("completed"))
+    /// ```
+    pub fn filter(self, expr: Expr<DB>) -> Self {
+        Self { inner: self.inner.filter(expr) }
+    }
+
+    /// Bind the next "{}" placeholder of the WHERE clause with its native type.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// Order::join_inner::<Customer>(r#""orders"."customer_id" = "customers"."id""#)
+    ///     .where_("orders.amount >= {} AND orders.created_at >= {}", &[])
+    ///     .bind_proxy(100)
+    ///     .bind_proxy(since)
+    /// ```
+    pub fn bind_proxy<V: BindProxy<DB>>(self, value: V) -> Self {
+        Self { inner: self.inner.bind_proxy(value) }
+    }
+
+    /// Sort by a column of either entity (rendered table-qualified).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use sqlx_struct_enhanced::Order as Sort; // the entity here is also named `Order`
+    ///
+    /// .order_by(Customer::NAME, Sort::Asc)
+    /// .order_by(Order::CREATED_AT, Sort::Desc)
+    /// ```
+    pub fn order_by(self, column: Column, order: Order) -> Self {
+        Self { inner: self.inner.order_by(column, order) }
     }
 
-    /// Build the SQL query and return a cached `&'static str`.
-    fn build(&self) -> &'static str {
-        let generator = JoinSqlGenerator::new::<A, B>(self.join_type, &self.join_condition);
+    /// Sort by a column with explicit NULL placement.
+    pub fn order_by_nulls(self, column: Column, order: Order, nulls: Nulls) -> Self {
+        Self { inner: self.inner.order_by_nulls(column, order, nulls) }
+    }
 
-        let where_clause = self.where_clause.as_ref().map(|clause| {
-            format!("WHERE {}", prepare_where(clause, 1))
+    /// Return at most `n` rows.
+    pub fn limit(self, n: u64) -> Self {
+        Self { inner: self.inner.limit(n) }
+    }
+
+    /// Skip the first `n` rows.
+    pub fn offset(self, n: u64) -> Self {
+        Self { inner: self.inner.offset(n) }
+    }
+
+    /// Offset pagination returning a `Page` with totals. `page` is 1-based.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use sqlx_struct_enhanced::Order as Sort;
+    ///
+    /// let page = Order::join_inner::<Customer>(r#""orders"."customer_id" = "customers"."id""#)
+    ///     .order_by(Order::CREATED_AT, Sort::Desc)
+    ///     .paginate(2, 20)
+    ///     .fetch(&pool)
+    ///     .await?;
+    /// ```
+    pub fn paginate(self, page: u64, per_page: u64) -> JoinPaginate<'a, JoinTuple2<A, B>, DB> {
+        self.inner.paginate(page, per_page)
+    }
+
+    /// The SQL this query runs, e.g. for logging.
+    pub fn sql(&self) -> &'static str {
+        self.inner.sql()
+    }
+
+    /// Add an INNER JOIN with a third entity; the results decode into
+    /// [`JoinTuple3`](super::JoinTuple3).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = Order::join_inner::<Customer>(r#""orders"."customer_id" = "customers"."id""#)
+    ///     .join_left::<Address>(r#""addresses"."customer_id" = "customers"."id""#)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn join_inner<C: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_inner::<C>(condition)
+    }
+
+    /// Add a LEFT JOIN with a third entity.
+    pub fn join_left<C: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_left::<C>(condition)
+    }
+
+    /// Add a RIGHT JOIN with a third entity.
+    pub fn join_right<C: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_right::<C>(condition)
+    }
+
+    /// Add a FULL JOIN with a third entity.
+    pub fn join_full<C: SchemeAccessor>(self, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_full::<C>(condition)
+    }
+
+    /// Add an INNER JOIN with a third entity under an alias, e.g. to join
+    /// the same table again in another role. Its columns are then qualified
+    /// by the alias in the condition, filters and ordering.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = Shipment::join_inner::<Address>(r#""addresses"."id" = "shipments"."origin_id""#)
+    ///     .join_inner_as::<Address>("dest", r#""dest"."id" = "shipments"."destination_id""#)
+    ///     .filter(Address::COUNTRY.aliased("dest").eq("FR"))
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn join_inner_as<C: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_inner_as::<C>(alias, condition)
+    }
+
+    /// Add a LEFT JOIN with a third entity under an alias.
+    pub fn join_left_as<C: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_left_as::<C>(alias, condition)
+    }
+
+    /// Add a RIGHT JOIN with a third entity under an alias.
+    pub fn join_right_as<C: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_right_as::<C>(alias, condition)
+    }
+
+    /// Add a FULL JOIN with a third entity under an alias.
+    pub fn join_full_as<C: SchemeAccessor>(self, alias: &str, condition: &str) -> MultiJoinQueryBuilder<'a, JoinTuple3<A, B, C>, DB> {
+        self.inner.join_full_as::<C>(alias, condition)
+    }
+}
+
+/// Groups the rows of a one-to-many join by the parent's primary key.
+///
+/// Parents keep the order in which they first appear, and so do their
+/// children. Rows without a child (LEFT JOIN misses) only contribute the
+/// parent; rows without a parent (RIGHT/FULL JOIN misses) are dropped.
+fn group_by_parent<A, B>(rows: Vec<JoinTuple2<A, B>>) -> Vec<(A, Vec<B>)>
+where
+    A: Entity,
+    A::Key: Eq + Hash + Clone,
+{
+    let mut groups: Vec<(A, Vec<B>)> = Vec::new();
+    let mut positions = HashMap::new();
+    for JoinTuple2(parent, child) in rows {
+        let Some(parent) = parent else { continue };
+        let key = parent.primary_key().clone();
+        let position = *positions.entry(key).or_insert_with(|| {
+            groups.push((parent, Vec::new()));
+            groups.len() - 1
         });
+        if let Some(child) = child {
+            groups[position].1.push(child);
+        }
+    }
+    groups
+}
 
-        let sql = generator.gen_full_query(where_clause.as_deref());
+/// Start of an aliased JOIN query, created by `EnhancedCrud::alias`.
+///
+/// Every entity of the query is given an alias, which qualifies its columns
+/// in the SQL and in the decoded rows. This is what makes self-joins work.
+///
+/// # Example
+///
+/// ```ignore
+/// let rows: Vec<JoinTuple2<Employee, Employee>> = Employee::alias("e")
+///     .join_left::<Employee>("m", r#""e"."manager_id" = "m"."id""#)
+///     .order_by(Employee::NAME.aliased("e"), Order::Asc)
+///     .fetch_all(&pool)
+///     .await?;
+///
+/// for JoinTuple2(employee, manager) in rows { /* ... */ }
+/// ```
+pub struct JoinAlias<'a, A, DB>
+where
+    A: SchemeAccessor,
+    DB: Database,
+{
+    alias: String,
+    _phantom: PhantomData<(A, &'a DB)>,
+}
 
-        // Include join type in cache key to avoid reusing wrong JOIN type SQL
-        let cache_key = format!(
-            "join-{}-{}-{}-where-{}",
-            self.join_type,
-            A::get_scheme().table_name(),
-            B::get_scheme().table_name(),
-            self.where_clause.as_ref().unwrap_or(&String::new())
-        );
+impl<'a, A, DB> JoinAlias<'a, A, DB>
+where
+    A: SchemeAccessor,
+    DB: Database,
+{
+    /// Alias the FROM entity of a JOIN query.
+    pub fn new(alias: &str) -> Self {
+        Self { alias: alias.to_string(), _phantom: PhantomData }
+    }
+
+    /// INNER JOIN another entity under `alias`.
+    pub fn join_inner<B: SchemeAccessor>(self, alias: &str, condition: &str) -> JoinQueryBuilder<'a, A, B, DB> {
+        self.join(alias, JoinType::Inner, condition)
+    }
 
-        get_or_insert_sql(cache_key, || sql)
+    /// LEFT JOIN another entity under `alias`.
+    pub fn join_left<B: SchemeAccessor>(self, alias: &str, condition: &str) -> JoinQueryBuilder<'a, A, B, DB> {
+        self.join(alias, JoinType::Left, condition)
     }
 
+    /// RIGHT JOIN another entity under `alias`.
+    pub fn join_right<B: SchemeAccessor>(self, alias: &str, condition: &str) -> JoinQueryBuilder<'a, A, B, DB> {
+        self.join(alias, JoinType::Right, condition)
+    }
+
+    /// FULL JOIN another entity under `alias`.
+    pub fn join_full<B: SchemeAccessor>(self, alias: &str, condition: &str) -> JoinQueryBuilder<'a, A, B, DB> {
+        self.join(alias, JoinType::Full, condition)
+    }
+
+    fn join<B: SchemeAccessor>(self, alias: &str, join_type: JoinType, condition: &str) -> JoinQueryBuilder<'a, A, B, DB> {
+        JoinQueryBuilder {
+            inner: MultiJoinQueryBuilder::from_parts(
+                (A::get_scheme(), Some(self.alias)),
+                vec![(B::get_scheme(), Some(alias.to_string()), join_type, condition.to_string())],
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a, A, B> JoinQueryBuilder<'a, A, B, Postgres>
+where
+    A: SchemeAccessor + Unpin + Send,
+    B: SchemeAccessor + Unpin + Send,
+{
     /// Execute the query and fetch all results.
     ///
     /// # Returns
@@ -127,14 +336,7 @@ where
         self,
         pool: &Pool<Postgres>
     ) -> Result<Vec<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_all(pool).await
+        self.inner.fetch_all(pool).await
     }
 
     /// Execute the query and fetch exactly one result.
@@ -159,14 +361,7 @@ where
         self,
         pool: &Pool<Postgres>
     ) -> Result<JoinTuple2<A, B>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_one(pool).await
+        self.inner.fetch_one(pool).await
     }
 
     /// Execute the query and fetch at most one result.
@@ -187,14 +382,35 @@ where
         self,
         pool: &Pool<Postgres>
     ) -> Result<Option<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
+        self.inner.fetch_optional(pool).await
+    }
 
-        query.fetch_optional(pool).await
+    /// Execute the query and group the rows by parent, for one-to-many joins.
+    ///
+    /// Returns one `(parent, children)` pair per distinct primary key of `A`,
+    /// in order of first appearance; parents without children (LEFT JOIN
+    /// misses) get an empty `Vec`. `limit` and `paginate` count rows, not parents.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use sqlx_struct_enhanced::Order as Sort; // the entity here is also named `Order`
+    ///
+    /// let orders: Vec<(Order, Vec<OrderLine>)> =
+    ///     Order::join_left::<OrderLine>(r#""order_lines"."order_id" = "orders"."id""#)
+    ///         .order_by(Order::CREATED_AT, Sort::Desc)
+    ///         .fetch_grouped(&pool)
+    ///         .await?;
+    /// ```
+    pub async fn fetch_grouped(
+        self,
+        pool: &Pool<Postgres>
+    ) -> Result<Vec<(A, Vec<B>)>, Error>
+    where
+        A: Entity,
+        A::Key: Eq + Hash + Clone,
+    {
+        self.inner.fetch_all(pool).await.map(group_by_parent)
     }
 }
 
@@ -208,61 +424,12 @@ where
     A: SchemeAccessor + Unpin + Send,
     B: SchemeAccessor + Unpin + Send,
 {
-    /// Create a new JOIN query builder for MySQL.
-    pub fn new(join_type: JoinType, condition: &str) -> Self {
-        Self {
-            join_type,
-            join_condition: condition.to_string(),
-            where_clause: None,
-            where_params: Vec::new(),
-            _phantom_a: PhantomData,
-            _phantom_b: PhantomData,
-            _phantom_db: PhantomData,
-        }
-    }
-
-    /// Add a WHERE clause with the given statement and parameters.
-    pub fn where_(mut self, clause: &str, params: &[&str]) -> Self {
-        self.where_clause = Some(clause.to_string());
-        self.where_params = params.iter().map(|s| s.to_string()).collect();
-        self
-    }
-
-    /// Build the SQL query and return a cached `&'static str`.
-    fn build(&self) -> &'static str {
-        let generator = JoinSqlGenerator::new::<A, B>(self.join_type, &self.join_condition);
-
-        let where_clause = self.where_clause.as_ref().map(|clause| {
-            format!("WHERE {}", prepare_where(clause, 1))
-        });
-
-        let sql = generator.gen_full_query(where_clause.as_deref());
-
-        // Include join type in cache key to avoid reusing wrong JOIN type SQL
-        let cache_key = format!(
-            "join-{}-{}-{}-where-{}",
-            self.join_type,
-            A::get_scheme().table_name(),
-            B::get_scheme().table_name(),
-            self.where_clause.as_ref().unwrap_or(&String::new())
-        );
-
-        get_or_insert_sql(cache_key, || sql)
-    }
-
     /// Execute the query and fetch all results.
     pub async fn fetch_all(
         self,
         pool: &Pool<MySql>
     ) -> Result<Vec<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_all(pool).await
+        self.inner.fetch_all(pool).await
     }
 
     /// Execute the query and fetch exactly one result.
@@ -270,14 +437,7 @@ where
         self,
         pool: &Pool<MySql>
     ) -> Result<JoinTuple2<A, B>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_one(pool).await
+        self.inner.fetch_one(pool).await
     }
 
     /// Execute the query and fetch at most one result.
@@ -285,14 +445,19 @@ where
         self,
         pool: &Pool<MySql>
     ) -> Result<Option<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
+        self.inner.fetch_optional(pool).await
+    }
 
-        query.fetch_optional(pool).await
+    /// Execute the query and group the rows by parent, for one-to-many joins.
+    pub async fn fetch_grouped(
+        self,
+        pool: &Pool<MySql>
+    ) -> Result<Vec<(A, Vec<B>)>, Error>
+    where
+        A: Entity,
+        A::Key: Eq + Hash + Clone,
+    {
+        self.inner.fetch_all(pool).await.map(group_by_parent)
     }
 }
 
@@ -306,61 +471,12 @@ where
     A: SchemeAccessor + Unpin + Send,
     B: SchemeAccessor + Unpin + Send,
 {
-    /// Create a new JOIN query builder for SQLite.
-    pub fn new(join_type: JoinType, condition: &str) -> Self {
-        Self {
-            join_type,
-            join_condition: condition.to_string(),
-            where_clause: None,
-            where_params: Vec::new(),
-            _phantom_a: PhantomData,
-            _phantom_b: PhantomData,
-            _phantom_db: PhantomData,
-        }
-    }
-
-    /// Add a WHERE clause with the given statement and parameters.
-    pub fn where_(mut self, clause: &str, params: &[&str]) -> Self {
-        self.where_clause = Some(clause.to_string());
-        self.where_params = params.iter().map(|s| s.to_string()).collect();
-        self
-    }
-
-    /// Build the SQL query and return a cached `&'static str`.
-    fn build(&self) -> &'static str {
-        let generator = JoinSqlGenerator::new::<A, B>(self.join_type, &self.join_condition);
-
-        let where_clause = self.where_clause.as_ref().map(|clause| {
-            format!("WHERE {}", prepare_where(clause, 1))
-        });
-
-        let sql = generator.gen_full_query(where_clause.as_deref());
-
-        // Include join type in cache key to avoid reusing wrong JOIN type SQL
-        let cache_key = format!(
-            "join-{}-{}-{}-where-{}",
-            self.join_type,
-            A::get_scheme().table_name(),
-            B::get_scheme().table_name(),
-            self.where_clause.as_ref().unwrap_or(&String::new())
-        );
-
-        get_or_insert_sql(cache_key, || sql)
-    }
-
     /// Execute the query and fetch all results.
     pub async fn fetch_all(
         self,
         pool: &Pool<Sqlite>
     ) -> Result<Vec<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_all(pool).await
+        self.inner.fetch_all(pool).await
     }
 
     /// Execute the query and fetch exactly one result.
@@ -368,14 +484,7 @@ where
         self,
         pool: &Pool<Sqlite>
     ) -> Result<JoinTuple2<A, B>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
-
-        query.fetch_one(pool).await
+        self.inner.fetch_one(pool).await
     }
 
     /// Execute the query and fetch at most one result.
@@ -383,21 +492,73 @@ where
         self,
         pool: &Pool<Sqlite>
     ) -> Result<Option<JoinTuple2<A, B>>, Error> {
-        let sql = self.build();
-        let mut query = sqlx::query_as::<_, JoinTuple2<A, B>>(sql);
-
-        for param in &self.where_params {
-            query = query.bind(param);
-        }
+        self.inner.fetch_optional(pool).await
+    }
 
-        query.fetch_optional(pool).await
+    /// Execute the query and group the rows by parent, for one-to-many joins.
+    pub async fn fetch_grouped(
+        self,
+        pool: &Pool<Sqlite>
+    ) -> Result<Vec<(A, Vec<B>)>, Error>
+    where
+        A: Entity,
+        A::Key: Eq + Hash + Clone,
+    {
+        self.inner.fetch_all(pool).await.map(group_by_parent)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Scheme;
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, PartialEq)]
+    struct Parent(u32);
+
+    impl Entity for Parent {
+        type Key = u32;
+
+        fn scheme() -> &'static Scheme {
+            static SCHEME: Lazy<Scheme> = Lazy::new(|| Scheme {
+                table_name: "parents".to_string(),
+                insert_fields: vec!["id".to_string()],
+                update_fields: vec![],
+                id_field: "id".to_string(),
+                column_definitions: vec![],
+            });
+            &SCHEME
+        }
+
+        fn primary_key(&self) -> &u32 {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_group_by_parent_keeps_first_appearance_order() {
+        let rows = vec![
+            JoinTuple2(Some(Parent(2)), Some("a")),
+            JoinTuple2(Some(Parent(1)), Some("b")),
+            JoinTuple2(Some(Parent(2)), Some("c")),
+        ];
+        assert_eq!(
+            group_by_parent(rows),
+            vec![(Parent(2), vec!["a", "c"]), (Parent(1), vec!["b"])]
+        );
+    }
 
-    // Note: Tests will be added once the derive macro generates SchemeAccessor implementations
-    // for test structs. For now, the basic structure is in place.
+    #[test]
+    fn test_group_by_parent_drops_missing_children_and_parents() {
+        let rows = vec![
+            JoinTuple2(Some(Parent(1)), None),
+            JoinTuple2(None, Some("orphan")),
+            JoinTuple2(Some(Parent(3)), Some("d")),
+        ];
+        assert_eq!(
+            group_by_parent(rows),
+            vec![(Parent(1), vec![]), (Parent(3), vec!["d"])]
+        );
+    }
 }
@@ -5,7 +5,8 @@
 //! It uses table-qualified column aliases (e.g., "table.column") to ensure
 //! uniqueness.
 
-use crate::{ColumnDefinition, Scheme};
+use crate::{ColumnDefinition, Dialect, Scheme};
+use sqlx::Error;
 
 /// Type of SQL join.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -27,10 +28,28 @@ impl std::fmt::Display for JoinType {
     }
 }
 
+impl JoinType {
+    /// Whether the current database supports this JOIN type. SQLite lacks
+    /// RIGHT and FULL JOIN and MySQL lacks FULL JOIN; the generator emulates
+    /// them there.
+    pub fn is_native(self) -> bool {
+        self.is_native_on(Dialect::current())
+    }
+
+    fn is_native_on(self, db: Dialect) -> bool {
+        !matches!(
+            (self, db),
+            (JoinType::Right, Dialect::SQLite) | (JoinType::Full, Dialect::MySQL | Dialect::SQLite)
+        )
+    }
+}
+
 /// Represents a JOIN operation in the query.
 #[derive(Debug, Clone, PartialEq)]
 pub struct JoinClause {
     pub table_name: String,
+    /// Alias of the joined table, needed when a table is joined more than once
+    pub alias: Option<String>,
     pub condition: String,
     pub join_type: JoinType,
 }
@@ -48,13 +67,24 @@ pub struct JoinClause {
 /// FROM orders
 /// INNER JOIN customers ON orders.customer_id = customers.id
 /// ```
+///
+/// Aliased tables are qualified by their alias instead, e.g.
+/// `"m"."id" AS "m.id"` for `LEFT JOIN "employees" AS "m"`.
+///
+/// JOIN types the database lacks are emulated, keeping the select list (and
+/// so the decoded tuples) identical: a RIGHT JOIN becomes a LEFT JOIN with
+/// swapped operands, and a FULL JOIN becomes a LEFT JOIN `UNION ALL` the
+/// unmatched rows of the right side (an anti-join).
 pub struct JoinSqlGenerator {
-    table_a_name: String,
-    table_a_fields: Vec<ColumnDefinition>,
-    table_b_name: String,
-    table_b_fields: Vec<ColumnDefinition>,
-    join_type: JoinType,
-    join_condition: String,
+    /// Selected tables, their columns and primary key, starting with the FROM
+    /// table. Each is qualified by its alias, or by its name when it has none.
+    tables: Vec<(String, Vec<ColumnDefinition>, String)>,
+    /// The FROM table and its alias
+    from: (String, Option<String>),
+    /// One clause per joined table, in order
+    joins: Vec<JoinClause>,
+    /// Dialect the SQL is rendered for; its missing JOIN types are emulated
+    db: Dialect,
 }
 
 impl JoinSqlGenerator {
@@ -69,80 +99,232 @@ impl JoinSqlGenerator {
         A: SchemeAccessor,
         B: SchemeAccessor,
     {
-        let scheme_a = A::get_scheme();
-        let scheme_b = B::get_scheme();
+        Self::with_joins(A::get_scheme(), &[(B::get_scheme(), join_type, condition)])
+    }
+
+    /// Create a SQL generator joining any number of tables to `base`, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Scheme of the FROM table
+    /// * `joins` - Scheme, JOIN type and condition of each joined table
+    pub fn with_joins(base: &Scheme, joins: &[(&Scheme, JoinType, &str)]) -> Self {
+        let joins: Vec<_> = joins.iter()
+            .map(|(scheme, join_type, condition)| (*scheme, None, *join_type, *condition))
+            .collect();
+        Self::with_aliases((base, None), &joins)
+    }
+
+    /// Create a SQL generator whose tables may carry aliases, as needed for
+    /// self-joins or joining the same table in several roles.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Scheme and optional alias of the FROM table
+    /// * `joins` - Scheme, optional alias, JOIN type and condition of each joined table
+    pub fn with_aliases(
+        base: (&Scheme, Option<&str>),
+        joins: &[(&Scheme, Option<&str>, JoinType, &str)],
+    ) -> Self {
+        let (base, base_alias) = base;
+        let qualifier = |scheme: &Scheme, alias: Option<&str>| alias.unwrap_or(scheme.table_name()).to_string();
+        let mut tables = vec![(
+            qualifier(base, base_alias),
+            base.column_definitions().to_vec(),
+            base.id_field().to_string(),
+        )];
+        let mut clauses = Vec::with_capacity(joins.len());
+        for (scheme, alias, join_type, condition) in joins {
+            tables.push((qualifier(scheme, *alias), scheme.column_definitions().to_vec(), scheme.id_field().to_string()));
+            clauses.push(JoinClause {
+                table_name: scheme.table_name().to_string(),
+                alias: alias.map(str::to_string),
+                condition: condition.to_string(),
+                join_type: *join_type,
+            });
+        }
 
         Self {
-            table_a_name: scheme_a.table_name().to_string(),
-            table_a_fields: scheme_a.column_definitions().to_vec(),
-            table_b_name: scheme_b.table_name().to_string(),
-            table_b_fields: scheme_b.column_definitions().to_vec(),
-            join_type,
-            join_condition: condition.to_string(),
+            tables,
+            from: (base.table_name().to_string(), base_alias.map(str::to_string)),
+            joins: clauses,
+            db: Dialect::current(),
         }
     }
 
-    /// Quote an identifier for the current database type.
+    /// Quote an identifier for the generator's dialect.
     fn quote_identifier(&self, identifier: &str) -> String {
-        #[cfg(feature = "postgres")]
-        return format!("\"{}\"", identifier);
-
-        #[cfg(feature = "mysql")]
-        return format!("`{}`", identifier);
-
-        #[cfg(feature = "sqlite")]
-        return identifier.to_string();
+        self.db.quote_identifier(identifier)
     }
 
-    /// Quote a qualified column name (table.column) for the current database type.
-    fn quote_qualified_column(&self, table: &str, column: &str) -> String {
-        #[cfg(feature = "postgres")]
-        return format!("\"{}.{}\"", table, column);
-
-        #[cfg(feature = "mysql")]
-        return format!("`{}.{}`", table, column);
+    /// Quote a qualified column name (table.column) as a single identifier
+    /// for the current dialect.
+    ///
+    /// This is the name of the result column, e.g. for ORDER BY on a UNION.
+    pub fn quote_qualified_column(table: &str, column: &str) -> String {
+        quote_qualified_alias(Dialect::current(), table, column)
+    }
 
-        #[cfg(feature = "sqlite")]
-        return format!("{}.{}", table, column);
+    /// Quote a table reference, appending its alias if any.
+    fn table_ref(&self, table: &str, alias: Option<&str>) -> String {
+        match alias {
+            Some(alias) => format!("{} AS {}", self.quote_identifier(table), self.quote_identifier(alias)),
+            None => self.quote_identifier(table),
+        }
     }
 
     /// Generate SELECT clause with table-qualified column aliases.
     ///
-    /// Each column is aliased as "table_name.column_name" to prevent
-    /// conflicts when both tables have columns with the same name.
+    /// Each column is aliased as "table_name.column_name" (or
+    /// "alias.column_name") to prevent conflicts when several tables have
+    /// columns with the same name.
     pub fn gen_select_clause(&self) -> String {
         let mut columns = Vec::new();
 
-        // Table A columns: orders.id AS "orders.id"
-        for col in &self.table_a_fields {
-            let quoted_table = self.quote_identifier(&self.table_a_name);
-            let quoted_col = self.quote_identifier(&col.name);
-            let qualified = format!("{}.{}", quoted_table, quoted_col);
-            let alias = self.quote_qualified_column(&self.table_a_name, &col.name);
-            columns.push(format!("{} AS {}", qualified, alias));
-        }
-
-        // Table B columns: customers.id AS "customers.id"
-        for col in &self.table_b_fields {
-            let quoted_table = self.quote_identifier(&self.table_b_name);
-            let quoted_col = self.quote_identifier(&col.name);
-            let qualified = format!("{}.{}", quoted_table, quoted_col);
-            let alias = self.quote_qualified_column(&self.table_b_name, &col.name);
-            columns.push(format!("{} AS {}", qualified, alias));
+        // orders.id AS "orders.id", customers.id AS "customers.id", ...
+        for (table, fields, _) in &self.tables {
+            let quoted_table = self.quote_identifier(table);
+            for col in fields {
+                let quoted_col = self.quote_identifier(&col.name);
+                let qualified = format!("{}.{}", quoted_table, quoted_col);
+                let alias = quote_qualified_alias(self.db, table, &col.name);
+                columns.push(format!("{} AS {}", qualified, alias));
+            }
         }
 
         columns.join(", ")
     }
 
     /// Generate the FROM and JOIN clauses.
+    ///
+    /// RIGHT JOINs are emulated where needed; an emulated FULL JOIN needs a
+    /// UNION and is only produced by [`gen_select_query`](Self::gen_select_query).
     pub fn gen_from_join(&self) -> String {
-        let quoted_table_a = self.quote_identifier(&self.table_a_name);
-        let quoted_table_b = self.quote_identifier(&self.table_b_name);
+        let (from, _) = self.append_joins(self.table_ref(&self.from.0, self.from.1.as_deref()), false, &self.joins);
+        format!("FROM {}", from)
+    }
 
-        format!(
-            "FROM {} {} {} ON {}",
-            quoted_table_a, self.join_type, quoted_table_b, self.join_condition
-        )
+    /// Append JOIN clauses to `from`, a table reference or (when `nested`)
+    /// a join. Returns the join and whether it is nested.
+    fn append_joins(&self, mut from: String, mut nested: bool, joins: &[JoinClause]) -> (String, bool) {
+        for join in joins {
+            let table = self.table_ref(&join.table_name, join.alias.as_deref());
+            from = if join.join_type == JoinType::Right && !join.join_type.is_native_on(self.db) {
+                // a RIGHT JOIN b ON c == b LEFT JOIN a ON c
+                format!("{} LEFT JOIN {} ON {}", table, Self::parenthesize(from, nested), join.condition)
+            } else {
+                format!("{} {} {} ON {}", from, join.join_type, table, join.condition)
+            };
+            nested = true;
+        }
+        (from, nested)
+    }
+
+    fn parenthesize(from: String, nested: bool) -> String {
+        if nested {
+            format!("({})", from)
+        } else {
+            from
+        }
+    }
+
+    /// Position of the FULL JOIN emulated with a UNION, if any.
+    fn emulated_full_join(&self) -> Option<usize> {
+        self.joins.iter().position(|join| join.join_type == JoinType::Full && !join.join_type.is_native_on(self.db))
+    }
+
+    /// Whether the query is a UNION of two SELECTs (an emulated FULL JOIN).
+    ///
+    /// ORDER BY must then name the result columns, see
+    /// [`quote_qualified_column`](Self::quote_qualified_column).
+    pub fn is_union(&self) -> bool {
+        self.emulated_full_join().is_some()
+    }
+
+    /// Check that the JOINs can be expressed on the current database.
+    ///
+    /// The FULL JOIN emulation filters the unmatched rows after all joins,
+    /// so it supports a single FULL JOIN, not followed by a RIGHT JOIN.
+    pub fn check_supported(&self) -> Result<(), Error> {
+        if let Some(position) = self.emulated_full_join() {
+            let later = &self.joins[position + 1..];
+            if later.iter().any(|join| matches!(join.join_type, JoinType::Right | JoinType::Full)) {
+                return Err(Error::Configuration(
+                    "a FULL JOIN followed by a RIGHT or FULL JOIN cannot be emulated on this database".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate a SELECT of `select` (e.g. `SELECT a, b`) over the joined
+    /// tables, filtered by `where_clause` (without the WHERE keyword).
+    ///
+    /// An emulated FULL JOIN produces two SELECTs joined by UNION ALL, both
+    /// filtered by `where_clause`: its parameters are bound twice.
+    fn gen_statement(&self, select: &str, where_clause: Option<&str>) -> String {
+        let base = self.table_ref(&self.from.0, self.from.1.as_deref());
+        let Some(position) = self.emulated_full_join() else {
+            let (from, _) = self.append_joins(base, false, &self.joins);
+            return match where_clause {
+                Some(w) => format!("{} FROM {} WHERE {}", select, from, w),
+                None => format!("{} FROM {}", select, from),
+            };
+        };
+
+        let full = &self.joins[position];
+        let table = self.table_ref(&full.table_name, full.alias.as_deref());
+        let rest = &self.joins[position + 1..];
+        let (prefix, nested) = self.append_joins(base, false, &self.joins[..position]);
+
+        // Every row of the left side, matched or not
+        let (left, _) = self.append_joins(
+            format!("{} LEFT JOIN {} ON {}", prefix, table, full.condition),
+            true,
+            rest,
+        );
+        // Rows of the right side matching nothing on the left
+        let (right, _) = self.append_joins(
+            format!("{} LEFT JOIN {} ON {}", table, Self::parenthesize(prefix, nested), full.condition),
+            true,
+            rest,
+        );
+        let unmatched = self.tables[..=position].iter()
+            .map(|(table, _, id)| format!("{}.{} IS NULL", self.quote_identifier(table), self.quote_identifier(id)))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        match where_clause {
+            Some(w) => format!(
+                "{} FROM {} WHERE {} UNION ALL {} FROM {} WHERE {} AND ({})",
+                select, left, w, select, right, unmatched, w
+            ),
+            None => format!(
+                "{} FROM {} UNION ALL {} FROM {} WHERE {}",
+                select, left, select, right, unmatched
+            ),
+        }
+    }
+
+    /// Generate the SELECT of all entity columns plus `extra_columns` (e.g.
+    /// `, COUNT(*) OVER() AS "__total"`), filtered by `where_clause`
+    /// (without the WHERE keyword).
+    pub fn gen_select_query(&self, extra_columns: &str, where_clause: Option<&str>) -> String {
+        self.gen_statement(&format!("SELECT {}{}", self.gen_select_clause(), extra_columns), where_clause)
+    }
+
+    /// Generate a `SELECT COUNT(*)` of the rows matched by the joins and
+    /// `where_clause` (without the WHERE keyword).
+    pub fn gen_count_query(&self, where_clause: Option<&str>) -> String {
+        if self.is_union() {
+            format!(
+                "SELECT COUNT(*) FROM ({}) AS {}",
+                self.gen_statement("SELECT 1", where_clause),
+                self.quote_identifier("__rows")
+            )
+        } else {
+            self.gen_statement("SELECT COUNT(*)", where_clause)
+        }
     }
 
     /// Generate the full JOIN query with optional WHERE clause.
@@ -151,13 +333,10 @@ impl JoinSqlGenerator {
     ///
     /// * `where_clause` - Optional WHERE clause (must include "WHERE" keyword if present)
     pub fn gen_full_query(&self, where_clause: Option<&str>) -> String {
-        let select = self.gen_select_clause();
-        let from_join = self.gen_from_join();
-        let where_str = where_clause.unwrap_or("");
-
-        format!("SELECT {} {} {}", select, from_join, where_str)
-            .trim_end()
-            .to_string()
+        let condition = where_clause
+            .map(|w| w.trim().trim_start_matches("WHERE").trim_start())
+            .filter(|w| !w.is_empty());
+        self.gen_select_query("", condition)
     }
 }
 
@@ -167,29 +346,40 @@ impl JoinSqlGenerator {
 pub trait SchemeAccessor {
     fn get_scheme() -> &'static Scheme;
 
-    /// Decode this entity from a PostgreSQL row with qualified column names.
+    /// Decode this entity from a PostgreSQL row with qualified column names
+    /// (`"qualifier.column"`), where `qualifier` is the table name or alias
+    /// the entity was selected under.
     ///
     /// Returns `Ok(Some(entity))` if successfully decoded,
     /// `Ok(None)` if all columns are NULL (for LEFT/RIGHT/FULL joins),
     /// `Err(Error)` if decoding fails.
     #[cfg(feature = "postgres")]
-    fn decode_from_qualified_row_pg(row: &sqlx::postgres::PgRow) -> Result<Option<Self>, sqlx::Error>
+    fn decode_from_qualified_row_pg(row: &sqlx::postgres::PgRow, qualifier: &str) -> Result<Option<Self>, sqlx::Error>
     where
         Self: Sized;
 
     /// Decode this entity from a MySQL row with qualified column names.
     #[cfg(feature = "mysql")]
-    fn decode_from_qualified_row_mysql(row: &sqlx::mysql::MySqlRow) -> Result<Option<Self>, sqlx::Error>
+    fn decode_from_qualified_row_mysql(row: &sqlx::mysql::MySqlRow, qualifier: &str) -> Result<Option<Self>, sqlx::Error>
     where
         Self: Sized;
 
     /// Decode this entity from a SQLite row with qualified column names.
     #[cfg(feature = "sqlite")]
-    fn decode_from_qualified_row_sqlite(row: &sqlx::sqlite::SqliteRow) -> Result<Option<Self>, sqlx::Error>
+    fn decode_from_qualified_row_sqlite(row: &sqlx::sqlite::SqliteRow, qualifier: &str) -> Result<Option<Self>, sqlx::Error>
     where
         Self: Sized;
 }
 
+/// Quotes `table.column` as a single identifier. SQLite leaves identifiers
+/// bare, but unquoted the dot would be parsed as a qualified column.
+fn quote_qualified_alias(db: Dialect, table: &str, column: &str) -> String {
+    match db {
+        Dialect::SQLite => format!("\"{}.{}\"", table, column),
+        _ => db.quote_identifier(&format!("{}.{}", table, column)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,6 +392,104 @@ mod tests {
         assert_eq!(format!("{}", JoinType::Full), "FULL JOIN");
     }
 
-    // Note: More comprehensive tests require actual Scheme implementations
-    // which will be added when the derive macro is updated
+    fn scheme(table: &str) -> Scheme {
+        let column = |name: &str| ColumnDefinition {
+            name: name.to_string(),
+            cast_as: None,
+            is_decimal: false,
+            is_uuid: false,
+            is_lazy: false,
+        };
+        Scheme {
+            table_name: table.to_string(),
+            insert_fields: vec![],
+            update_fields: vec![],
+            id_field: "id".to_string(),
+            column_definitions: vec![column("id"), column("ref_id")],
+        }
+    }
+
+    fn generator_for(db: Dialect, joins: &[(&Scheme, JoinType, &str)]) -> JoinSqlGenerator {
+        let mut generator = JoinSqlGenerator::with_joins(&scheme("a"), joins);
+        generator.db = db;
+        generator
+    }
+
+    #[test]
+    fn test_right_join_is_swapped_on_sqlite() {
+        let b = scheme("b");
+        let generator = generator_for(Dialect::SQLite, &[(&b, JoinType::Right, "a.ref_id = b.id")]);
+        assert_eq!(generator.gen_from_join(), "FROM b LEFT JOIN a ON a.ref_id = b.id");
+        // The select list, and so the decoded tuple, keeps the (a, b) order
+        assert!(generator.gen_select_clause().starts_with(r#"a.id AS "a.id""#));
+
+        let mysql = generator_for(Dialect::MySQL, &[(&b, JoinType::Right, "a.ref_id = b.id")]);
+        assert_eq!(mysql.gen_from_join(), "FROM `a` RIGHT JOIN `b` ON a.ref_id = b.id");
+    }
+
+    #[test]
+    fn test_right_join_wraps_earlier_joins_on_sqlite() {
+        let (b, c) = (scheme("b"), scheme("c"));
+        let generator = generator_for(Dialect::SQLite, &[
+            (&b, JoinType::Inner, "a.ref_id = b.id"),
+            (&c, JoinType::Right, "b.ref_id = c.id"),
+        ]);
+        assert_eq!(
+            generator.gen_from_join(),
+            "FROM c LEFT JOIN (a INNER JOIN b ON a.ref_id = b.id) ON b.ref_id = c.id"
+        );
+    }
+
+    #[test]
+    fn test_full_join_is_a_union_with_an_anti_join() {
+        let b = scheme("b");
+        for (db, q) in [(Dialect::MySQL, "`"), (Dialect::SQLite, "")] {
+            let generator = generator_for(db, &[(&b, JoinType::Full, "a.ref_id = b.id")]);
+            assert!(generator.is_union());
+            let select = format!("SELECT {}", generator.gen_select_clause());
+            assert_eq!(
+                generator.gen_select_query("", Some("b.ref_id = {}")),
+                format!(
+                    "{select} FROM {q}a{q} LEFT JOIN {q}b{q} ON a.ref_id = b.id WHERE b.ref_id = {{}} UNION ALL \
+                     {select} FROM {q}b{q} LEFT JOIN {q}a{q} ON a.ref_id = b.id WHERE {q}a{q}.{q}id{q} IS NULL AND (b.ref_id = {{}})"
+                )
+            );
+            assert_eq!(
+                generator.gen_count_query(None),
+                format!(
+                    "SELECT COUNT(*) FROM (SELECT 1 FROM {q}a{q} LEFT JOIN {q}b{q} ON a.ref_id = b.id UNION ALL \
+                     SELECT 1 FROM {q}b{q} LEFT JOIN {q}a{q} ON a.ref_id = b.id WHERE {q}a{q}.{q}id{q} IS NULL) AS {q}__rows{q}"
+                )
+            );
+        }
+        let postgres = generator_for(Dialect::PostgreSQL, &[(&b, JoinType::Full, "a.ref_id = b.id")]);
+        assert!(!postgres.is_union());
+        assert!(postgres.gen_select_query("", None).ends_with(r#"FROM "a" FULL JOIN "b" ON a.ref_id = b.id"#));
+    }
+
+    #[test]
+    fn test_full_join_anti_join_covers_every_left_table() {
+        let (b, c, d) = (scheme("b"), scheme("c"), scheme("d"));
+        let generator = generator_for(Dialect::MySQL, &[
+            (&b, JoinType::Left, "a.ref_id = b.id"),
+            (&c, JoinType::Full, "b.ref_id = c.id"),
+            (&d, JoinType::Inner, "c.ref_id = d.id"),
+        ]);
+        let sql = generator.gen_count_query(None);
+        assert!(sql.contains(concat!(
+            "SELECT 1 FROM `c` LEFT JOIN (`a` LEFT JOIN `b` ON a.ref_id = b.id) ON b.ref_id = c.id ",
+            "INNER JOIN `d` ON c.ref_id = d.id WHERE `a`.`id` IS NULL AND `b`.`id` IS NULL",
+        )));
+        assert!(generator.check_supported().is_ok());
+    }
+
+    #[test]
+    fn test_full_join_followed_by_right_join_is_rejected() {
+        let (b, c) = (scheme("b"), scheme("c"));
+        let generator = generator_for(Dialect::SQLite, &[
+            (&b, JoinType::Full, "a.ref_id = b.id"),
+            (&c, JoinType::Right, "b.ref_id = c.id"),
+        ]);
+        assert!(matches!(generator.check_supported(), Err(Error::Configuration(_))));
+    }
 }
@@ -1,7 +1,7 @@
 //! Custom FromRow implementations for JOIN query result tuples.
 //!
 //! This module provides newtype wrappers that implement FromRow for entity tuples,
-//! handling table-qualified column names like "orders.id".
+//! handling qualified column names like "orders.id" (or "m.id" for aliased tables).
 
 use sqlx::Error;
 
@@ -80,26 +80,31 @@ pub struct JoinTuple5<A, B, C, D, E>(
     pub Option<E>,
 );
 
-// Implement FromRow for PostgreSQL rows for 2-table joins
-//
-// This implementation uses the SchemeAccessor trait to decode entities
-// from qualified column names (e.g., "orders.id", "customers.name").
+/// Decodes a JOIN result tuple from a row, reading each entity's columns
+/// under the qualifier it was selected with: its table name, or its alias in
+/// aliased and self-joins.
+///
+/// The `FromRow` implementations of the tuples decode with the table names.
+pub trait JoinRow<R>: Sized {
+    /// `qualifiers` holds one table name or alias per entity, in tuple order.
+    fn from_join_row(row: &R, qualifiers: &[&str]) -> Result<Self, Error>;
+}
+
+// Decode PostgreSQL rows of 2-table joins; entities whose columns are all NULL
+// (outer joins) or missing decode to None.
 #[cfg(feature = "postgres")]
-impl<'r, A, B> sqlx::FromRow<'r, PgRow> for JoinTuple2<A, B>
+impl<A, B> JoinRow<PgRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
 {
-    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
-        // Decode entity A from its qualified columns
-        // Returns Ok(None) if the entity has all NULL columns (LEFT/RIGHT/FULL join)
-        let entity_a = match A::decode_from_qualified_row_pg(row) {
+    fn from_join_row(row: &PgRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_pg(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        // Decode entity B from its qualified columns
-        let entity_b = match B::decode_from_qualified_row_pg(row) {
+        let entity_b = match B::decode_from_qualified_row_pg(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -108,26 +113,37 @@ where
     }
 }
 
-// Implement FromRow for PostgreSQL rows for 3-table joins
 #[cfg(feature = "postgres")]
-impl<'r, A, B, C> sqlx::FromRow<'r, PgRow> for JoinTuple3<A, B, C>
+impl<'r, A, B> sqlx::FromRow<'r, PgRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
-    C: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r PgRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_pg(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name()])
+    }
+}
+
+// Decode PostgreSQL rows of 3-table joins
+#[cfg(feature = "postgres")]
+impl<A, B, C> JoinRow<PgRow> for JoinTuple3<A, B, C>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &PgRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_pg(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_pg(row) {
+        let entity_b = match B::decode_from_qualified_row_pg(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_pg(row) {
+        let entity_c = match C::decode_from_qualified_row_pg(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -136,32 +152,44 @@ where
     }
 }
 
-// Implement FromRow for PostgreSQL rows for 4-table joins
 #[cfg(feature = "postgres")]
-impl<'r, A, B, C, D> sqlx::FromRow<'r, PgRow> for JoinTuple4<A, B, C, D>
+impl<'r, A, B, C> sqlx::FromRow<'r, PgRow> for JoinTuple3<A, B, C>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
-    D: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r PgRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_pg(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name()])
+    }
+}
+
+// Decode PostgreSQL rows of 4-table joins
+#[cfg(feature = "postgres")]
+impl<A, B, C, D> JoinRow<PgRow> for JoinTuple4<A, B, C, D>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &PgRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_pg(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_pg(row) {
+        let entity_b = match B::decode_from_qualified_row_pg(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_pg(row) {
+        let entity_c = match C::decode_from_qualified_row_pg(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_pg(row) {
+        let entity_d = match D::decode_from_qualified_row_pg(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -170,38 +198,51 @@ where
     }
 }
 
-// Implement FromRow for PostgreSQL rows for 5-table joins
 #[cfg(feature = "postgres")]
-impl<'r, A, B, C, D, E> sqlx::FromRow<'r, PgRow> for JoinTuple5<A, B, C, D, E>
+impl<'r, A, B, C, D> sqlx::FromRow<'r, PgRow> for JoinTuple4<A, B, C, D>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
     D: SchemeAccessor + Send + Unpin,
-    E: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r PgRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_pg(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name()])
+    }
+}
+
+// Decode PostgreSQL rows of 5-table joins
+#[cfg(feature = "postgres")]
+impl<A, B, C, D, E> JoinRow<PgRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &PgRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_pg(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_pg(row) {
+        let entity_b = match B::decode_from_qualified_row_pg(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_pg(row) {
+        let entity_c = match C::decode_from_qualified_row_pg(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_pg(row) {
+        let entity_d = match D::decode_from_qualified_row_pg(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_e = match E::decode_from_qualified_row_pg(row) {
+        let entity_e = match E::decode_from_qualified_row_pg(row, qualifiers[4]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -210,24 +251,39 @@ where
     }
 }
 
+#[cfg(feature = "postgres")]
+impl<'r, A, B, C, D, E> sqlx::FromRow<'r, PgRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name(), E::get_scheme().table_name()])
+    }
+}
+
 // ============================================================================
 // MySQL implementations
 // ============================================================================
 
-// Implement FromRow for MySQL rows for 2-table joins
+// Decode MySQL rows of 2-table joins; entities whose columns are all NULL
+// (outer joins) or missing decode to None.
 #[cfg(feature = "mysql")]
-impl<'r, A, B> sqlx::FromRow<'r, MySqlRow> for JoinTuple2<A, B>
+impl<A, B> JoinRow<MySqlRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
 {
-    fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_mysql(row) {
+    fn from_join_row(row: &MySqlRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_mysql(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_mysql(row) {
+        let entity_b = match B::decode_from_qualified_row_mysql(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -236,26 +292,37 @@ where
     }
 }
 
-// Implement FromRow for MySQL rows for 3-table joins
 #[cfg(feature = "mysql")]
-impl<'r, A, B, C> sqlx::FromRow<'r, MySqlRow> for JoinTuple3<A, B, C>
+impl<'r, A, B> sqlx::FromRow<'r, MySqlRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
-    C: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_mysql(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name()])
+    }
+}
+
+// Decode MySQL rows of 3-table joins
+#[cfg(feature = "mysql")]
+impl<A, B, C> JoinRow<MySqlRow> for JoinTuple3<A, B, C>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &MySqlRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_mysql(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_mysql(row) {
+        let entity_b = match B::decode_from_qualified_row_mysql(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_mysql(row) {
+        let entity_c = match C::decode_from_qualified_row_mysql(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -264,32 +331,44 @@ where
     }
 }
 
-// Implement FromRow for MySQL rows for 4-table joins
 #[cfg(feature = "mysql")]
-impl<'r, A, B, C, D> sqlx::FromRow<'r, MySqlRow> for JoinTuple4<A, B, C, D>
+impl<'r, A, B, C> sqlx::FromRow<'r, MySqlRow> for JoinTuple3<A, B, C>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
-    D: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_mysql(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name()])
+    }
+}
+
+// Decode MySQL rows of 4-table joins
+#[cfg(feature = "mysql")]
+impl<A, B, C, D> JoinRow<MySqlRow> for JoinTuple4<A, B, C, D>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &MySqlRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_mysql(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_mysql(row) {
+        let entity_b = match B::decode_from_qualified_row_mysql(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_mysql(row) {
+        let entity_c = match C::decode_from_qualified_row_mysql(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_mysql(row) {
+        let entity_d = match D::decode_from_qualified_row_mysql(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -298,38 +377,51 @@ where
     }
 }
 
-// Implement FromRow for MySQL rows for 5-table joins
 #[cfg(feature = "mysql")]
-impl<'r, A, B, C, D, E> sqlx::FromRow<'r, MySqlRow> for JoinTuple5<A, B, C, D, E>
+impl<'r, A, B, C, D> sqlx::FromRow<'r, MySqlRow> for JoinTuple4<A, B, C, D>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
     D: SchemeAccessor + Send + Unpin,
-    E: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_mysql(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name()])
+    }
+}
+
+// Decode MySQL rows of 5-table joins
+#[cfg(feature = "mysql")]
+impl<A, B, C, D, E> JoinRow<MySqlRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &MySqlRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_mysql(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_mysql(row) {
+        let entity_b = match B::decode_from_qualified_row_mysql(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_mysql(row) {
+        let entity_c = match C::decode_from_qualified_row_mysql(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_mysql(row) {
+        let entity_d = match D::decode_from_qualified_row_mysql(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_e = match E::decode_from_qualified_row_mysql(row) {
+        let entity_e = match E::decode_from_qualified_row_mysql(row, qualifiers[4]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -338,24 +430,39 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'r, A, B, C, D, E> sqlx::FromRow<'r, MySqlRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name(), E::get_scheme().table_name()])
+    }
+}
+
 // ============================================================================
 // SQLite implementations
 // ============================================================================
 
-// Implement FromRow for SQLite rows for 2-table joins
+// Decode SQLite rows of 2-table joins; entities whose columns are all NULL
+// (outer joins) or missing decode to None.
 #[cfg(feature = "sqlite")]
-impl<'r, A, B> sqlx::FromRow<'r, SqliteRow> for JoinTuple2<A, B>
+impl<A, B> JoinRow<SqliteRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
 {
-    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_sqlite(row) {
+    fn from_join_row(row: &SqliteRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_sqlite(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_sqlite(row) {
+        let entity_b = match B::decode_from_qualified_row_sqlite(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -364,26 +471,37 @@ where
     }
 }
 
-// Implement FromRow for SQLite rows for 3-table joins
 #[cfg(feature = "sqlite")]
-impl<'r, A, B, C> sqlx::FromRow<'r, SqliteRow> for JoinTuple3<A, B, C>
+impl<'r, A, B> sqlx::FromRow<'r, SqliteRow> for JoinTuple2<A, B>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
-    C: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_sqlite(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name()])
+    }
+}
+
+// Decode SQLite rows of 3-table joins
+#[cfg(feature = "sqlite")]
+impl<A, B, C> JoinRow<SqliteRow> for JoinTuple3<A, B, C>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &SqliteRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_sqlite(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_sqlite(row) {
+        let entity_b = match B::decode_from_qualified_row_sqlite(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_sqlite(row) {
+        let entity_c = match C::decode_from_qualified_row_sqlite(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -392,32 +510,44 @@ where
     }
 }
 
-// Implement FromRow for SQLite rows for 4-table joins
 #[cfg(feature = "sqlite")]
-impl<'r, A, B, C, D> sqlx::FromRow<'r, SqliteRow> for JoinTuple4<A, B, C, D>
+impl<'r, A, B, C> sqlx::FromRow<'r, SqliteRow> for JoinTuple3<A, B, C>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
-    D: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_sqlite(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name()])
+    }
+}
+
+// Decode SQLite rows of 4-table joins
+#[cfg(feature = "sqlite")]
+impl<A, B, C, D> JoinRow<SqliteRow> for JoinTuple4<A, B, C, D>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &SqliteRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_sqlite(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_sqlite(row) {
+        let entity_b = match B::decode_from_qualified_row_sqlite(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_sqlite(row) {
+        let entity_c = match C::decode_from_qualified_row_sqlite(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_sqlite(row) {
+        let entity_d = match D::decode_from_qualified_row_sqlite(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -426,38 +556,51 @@ where
     }
 }
 
-// Implement FromRow for SQLite rows for 5-table joins
 #[cfg(feature = "sqlite")]
-impl<'r, A, B, C, D, E> sqlx::FromRow<'r, SqliteRow> for JoinTuple5<A, B, C, D, E>
+impl<'r, A, B, C, D> sqlx::FromRow<'r, SqliteRow> for JoinTuple4<A, B, C, D>
 where
     A: SchemeAccessor + Send + Unpin,
     B: SchemeAccessor + Send + Unpin,
     C: SchemeAccessor + Send + Unpin,
     D: SchemeAccessor + Send + Unpin,
-    E: SchemeAccessor + Send + Unpin,
 {
     fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
-        let entity_a = match A::decode_from_qualified_row_sqlite(row) {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name()])
+    }
+}
+
+// Decode SQLite rows of 5-table joins
+#[cfg(feature = "sqlite")]
+impl<A, B, C, D, E> JoinRow<SqliteRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_join_row(row: &SqliteRow, qualifiers: &[&str]) -> Result<Self, Error> {
+        let entity_a = match A::decode_from_qualified_row_sqlite(row, qualifiers[0]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_b = match B::decode_from_qualified_row_sqlite(row) {
+        let entity_b = match B::decode_from_qualified_row_sqlite(row, qualifiers[1]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_c = match C::decode_from_qualified_row_sqlite(row) {
+        let entity_c = match C::decode_from_qualified_row_sqlite(row, qualifiers[2]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_d = match D::decode_from_qualified_row_sqlite(row) {
+        let entity_d = match D::decode_from_qualified_row_sqlite(row, qualifiers[3]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
 
-        let entity_e = match E::decode_from_qualified_row_sqlite(row) {
+        let entity_e = match E::decode_from_qualified_row_sqlite(row, qualifiers[4]) {
             Ok(opt) => opt,
             Err(_) => None,
         };
@@ -466,6 +609,20 @@ where
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl<'r, A, B, C, D, E> sqlx::FromRow<'r, SqliteRow> for JoinTuple5<A, B, C, D, E>
+where
+    A: SchemeAccessor + Send + Unpin,
+    B: SchemeAccessor + Send + Unpin,
+    C: SchemeAccessor + Send + Unpin,
+    D: SchemeAccessor + Send + Unpin,
+    E: SchemeAccessor + Send + Unpin,
+{
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        Self::from_join_row(row, &[A::get_scheme().table_name(), B::get_scheme().table_name(), C::get_scheme().table_name(), D::get_scheme().table_name(), E::get_scheme().table_name()])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
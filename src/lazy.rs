@@ -0,0 +1,71 @@
+//! Dirty tracking for `#[crud(lazy)]` fields.
+//!
+//! A lazy field holding a value may simply have been loaded with
+//! `load_<field>`, so `update_bind` cannot tell from the field whether to
+//! write it back. The generated `set_<field>` marks the field in the row's
+//! [`LazyDirty`], and `update_bind` writes the column (`None` as NULL) only
+//! while it is marked.
+//!
+//! Marks live on the row itself, so copies of a row never share them. They
+//! are cleared by `load_<field>` and by the generated `save` once its UPDATE
+//! succeeded; a failed or never-executed `update_bind` leaves them set.
+
+/// Marks of the `#[crud(lazy)]` fields of one row set through `set_<field>`.
+///
+/// Structs with lazy fields declare one such field, which is not a column:
+///
+/// ```ignore
+/// #[sqlx(skip)]
+/// dirty: LazyDirty,
+/// ```
+///
+/// Bit `n` belongs to the struct's `n`-th lazy field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LazyDirty(u64);
+
+impl LazyDirty {
+    /// Maximum number of lazy fields per struct.
+    pub const CAPACITY: u32 = u64::BITS;
+
+    /// Marks lazy field `n` as explicitly set.
+    pub fn mark(&mut self, n: u32) {
+        self.0 |= 1 << n;
+    }
+
+    /// Whether lazy field `n` is marked.
+    pub fn is_marked(&self, n: u32) -> bool {
+        self.0 & (1 << n) != 0
+    }
+
+    /// Removes the mark of lazy field `n`.
+    pub fn clear(&mut self, n: u32) {
+        self.0 &= !(1 << n);
+    }
+
+    /// Removes all marks.
+    pub fn clear_all(&mut self) {
+        self.0 = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks_are_per_field() {
+        let mut dirty = LazyDirty::default();
+        dirty.mark(0);
+        dirty.mark(63);
+        assert!(dirty.is_marked(0));
+        assert!(!dirty.is_marked(1));
+        assert!(dirty.is_marked(63));
+
+        dirty.clear(0);
+        assert!(!dirty.is_marked(0));
+        assert!(dirty.is_marked(63));
+
+        dirty.clear_all();
+        assert_eq!(dirty, LazyDirty::default());
+    }
+}
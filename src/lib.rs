@@ -2,25 +2,35 @@ pub mod traits;
 pub mod proxy;
 pub mod decimal_helpers;
 pub mod aggregate;
+pub mod dialect;
 pub mod join;
+pub mod filter;
+pub mod pagination;
+pub mod lazy;
+pub mod relation;
+pub mod select;
 
 // Migration module is currently PostgreSQL-only
 #[cfg(feature = "postgres")]
 pub mod migration;
 
-pub use sqlx_struct_macros::EnhancedCrud;
+pub use sqlx_struct_macros::{AggRow, EnhancedCrud, Projection};
 pub use traits::{EnhancedCrud, EnhancedCrudExt};
-pub use aggregate::{AggQueryBuilder, Join, JoinType};
-pub use join::{JoinQueryBuilder, JoinType as JoinQueryType, JoinClause, SchemeAccessor};
+pub use aggregate::{AggCast, AggQueryBuilder, AggRow, FrameBound, Join, JoinType, TimeBucket, WindowFunction};
+pub use dialect::Dialect;
+pub use join::{JoinAlias, JoinPaginate, JoinQueryBuilder, MultiJoinQueryBuilder, JoinType as JoinQueryType, JoinClause, SchemeAccessor};
+pub use filter::{Column, Expr, IntoSubquery, IntoWhere, Nulls, Order, Subquery};
+pub use pagination::{Chunked, Cursor, Keyset, KeysetPage, Page, Paginate};
+pub use select::{Projection, SelectQueryBuilder};
 
 #[cfg(feature = "postgres")]
-pub use proxy::{EnhancedQueryAsPostgres, EnhancedQuery, BindProxy, BindValue};
+pub use proxy::{EnhancedQueryAsPostgres, EnhancedQuery, BindParam, BindProxy, BindValue};
 
 #[cfg(all(feature = "mysql", not(feature = "postgres")))]
-pub use proxy::{EnhancedQueryAsMySql, EnhancedQuery, BindProxy, BindValue};
+pub use proxy::{EnhancedQueryAsMySql, EnhancedQuery, BindParam, BindProxy, BindValue};
 
 #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
-pub use proxy::{EnhancedQueryAsSqlite, EnhancedQuery, BindProxy, BindValue};
+pub use proxy::{EnhancedQueryAsSqlite, EnhancedQuery, BindParam, BindProxy, BindValue};
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
@@ -259,34 +269,23 @@ where
 }
 
 
-#[cfg(feature = "postgres")]
-fn get_db() -> DbType {
-    DbType::PostgreSQL
-}
-
-#[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
-fn get_db() -> DbType {
-    DbType::MySQL
-}
-
-#[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
-fn get_db() -> DbType {
-    DbType::SQLite
-}
-
-#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
-fn get_db() -> DbType {
-    compile_error!("You must enable one of the database features: postgres, mysql, or sqlite")
-}
-
-/// Translates a parameter placeholder to the database-specific format.
-///
-/// - PostgreSQL: Returns the parameter as-is (e.g., "$1", "$2")
-/// - MySQL/SQLite: Returns "?" for all parameters
-fn param_trans(p: String) -> String {
-    match get_db() {
-        DbType::PostgreSQL => p,
-        DbType::MySQL | DbType::SQLite => "?".to_string(),
+/// Renders one entry of a SELECT column list, quoted and with its optional cast.
+fn select_column_sql(name: &str, cast_as: Option<&str>) -> String {
+    let db = Dialect::current();
+    let quoted_name = db.quote_identifier(name);
+    match cast_as {
+        Some(cast_type) => {
+            // PostgreSQL: "column"::TYPE as "column"
+            // Example: "commission_rate"::TEXT as "commission_rate"
+            // MySQL/SQLite: cast types differ from PostgreSQL's, use quoted column name only
+            match db {
+                Dialect::PostgreSQL => {
+                    format!("{} as {}", db.cast(&quoted_name, cast_type), quoted_name)
+                }
+                Dialect::MySQL | Dialect::SQLite => quoted_name,
+            }
+        }
+        None => quoted_name,
     }
 }
 
@@ -305,17 +304,7 @@ fn param_trans(p: String) -> String {
 /// // MySQL/SQLite: "name = ? AND age = ?"
 /// ```
 pub fn prepare_where(w: &str, field_count: i32) -> String {
-    let param_count = w.matches("{}").count() as i32;
-    let mut where_sql = w.to_string();
-
-    for i in 0..param_count {
-        let param = param_trans(format!("${}", i + field_count));
-        if let Some(pos) = where_sql.find("{}") {
-            where_sql.replace_range(pos..pos + 2, &param);
-        }
-    }
-
-    where_sql
+    Dialect::current().placeholders(w, field_count as usize)
 }
 /// Column definition with optional type casting for SQL queries.
 ///
@@ -352,6 +341,10 @@ pub struct ColumnDefinition {
     /// Whether this is a UUID field (Rust String type bound to UUID column)
     /// When true, bulk operations add ::uuid cast for type inference.
     pub is_uuid: bool,
+    /// Whether this is a lazy field (`#[crud(lazy)]`)
+    /// When true, the column is left out of the default SELECT list and of
+    /// UPDATE statements, and is loaded on demand.
+    pub is_lazy: bool,
 }
 
 
@@ -450,24 +443,29 @@ impl Scheme {
             }
 
             // Generate explicit column list with optional casting and identifier quoting
-            let db = get_db();
+            // Lazy columns are only loaded on demand
             self.column_definitions.iter()
-                .map(|col| {
-                    let quoted_name = db.quote_identifier(&col.name);
-                    match &col.cast_as {
-                        Some(cast_type) => {
-                            // PostgreSQL: "column"::TYPE as "column"
-                            // Example: "commission_rate"::TEXT as "commission_rate"
-                            // MySQL/SQLite: don't support cast syntax, use quoted column name only
-                            match db {
-                                DbType::PostgreSQL => {
-                                    format!("{}::{} as {}", quoted_name, cast_type, quoted_name)
-                                }
-                                DbType::MySQL | DbType::SQLite => quoted_name,
-                            }
-                        }
-                        None => quoted_name,
-                    }
+                .filter(|col| !col.is_lazy)
+                .map(|col| select_column_sql(&col.name, col.cast_as.as_deref()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    /// Generates the SELECT column list for a subset of the columns, in the
+    /// given order, applying each column's `cast_as` like
+    /// `gen_select_columns_static`.
+    ///
+    /// Used by projections (`EnhancedCrud::select_as`).
+    pub fn gen_select_columns_subset_static(&self, columns: &[&str]) -> &'static str {
+        let key = format!("{}-select-columns-{}", self.table_name, columns.join(","));
+        get_or_insert_sql(key, || {
+            columns.iter()
+                .map(|name| {
+                    let cast_as = self.column_definitions.iter()
+                        .find(|col| col.name == *name)
+                        .and_then(|col| col.cast_as.as_deref());
+                    select_column_sql(name, cast_as)
                 })
                 .collect::<Vec<_>>()
                 .join(", ")
@@ -480,7 +478,7 @@ impl Scheme {
     pub fn gen_count_sql_static(&self, where_stmt: &str) -> &'static str {
         let key = format!("{}-count-{}", self.table_name, where_stmt);
         get_or_insert_sql(key, || {
-            let quoted_table = get_db().quote_identifier(&self.table_name);
+            let quoted_table = Dialect::current().quote_identifier(&self.table_name);
             let where_sql = prepare_where(where_stmt, 1);
             format!("SELECT COUNT(*) FROM {} WHERE {}", quoted_table, where_sql)
         })
@@ -497,8 +495,8 @@ impl Scheme {
     pub fn gen_insert_sql_static(&self) -> &'static str {
         let key = format!("{}-insert", self.table_name);
         get_or_insert_sql(key, || {
+            let db = Dialect::current();
             let params: Vec<String> = self.insert_fields.iter().enumerate().map(|(idx, field_name)|{
-                let p = format!("${}", idx + 1);
                 // Find the column definition for this field
                 let col_def = self.column_definitions.iter()
                     .find(|col| col.name == *field_name);
@@ -510,21 +508,20 @@ impl Scheme {
                             "[SQLxEnhanced] Adding ::numeric cast for field '{}' at position {} in table '{}'",
                             field_name, idx + 1, self.table_name
                         );
-                        return format!("{}::numeric", param_trans(p));
+                        return db.typed_placeholder(idx + 1, "numeric");
                     }
                 }
-                param_trans(p)
+                db.placeholder(idx + 1)
             }).collect();
             let params_str = params.join(",");
 
             // Generate explicit column list to avoid dependency on database table column order
-            let db = get_db();
             let columns: Vec<String> = self.insert_fields.iter()
                 .map(|field_name| db.quote_identifier(field_name))
                 .collect();
             let columns_str = columns.join(",");
 
-            let quoted_table = get_db().quote_identifier(&self.table_name);
+            let quoted_table = db.quote_identifier(&self.table_name);
             let sql = format!(r#"INSERT INTO {} ({}) VALUES ({})"#, quoted_table, columns_str, params_str);
             eprintln!("[SQLxEnhanced] Generated INSERT SQL for table '{}': {}", self.table_name, sql);
             sql
@@ -538,15 +535,15 @@ impl Scheme {
     pub fn gen_bulk_insert_sql_static(&self, row_count: usize) -> &'static str {
         let key = format!("{}-bulk-insert-{}-rows", self.table_name, row_count);
         get_or_insert_sql(key, || {
+            let db = Dialect::current();
             let field_count = self.insert_fields.len();
             let mut all_params = Vec::new();
             let mut param_index = 1;
 
             for _ in 0..row_count {
                 let row_params: Vec<String> = (0..field_count).map(|field_idx| {
-                    let p = format!("${}", param_index);
+                    let n = param_index;
                     param_index += 1;
-                    let param = param_trans(p);
 
                     // Find the column definition for this field
                     let field_name = &self.insert_fields[field_idx];
@@ -556,16 +553,16 @@ impl Scheme {
                     // Add ::numeric cast for DECIMAL fields (is_decimal=true indicates DECIMAL stored as String)
                     if let Some(col) = col_def {
                         if col.is_decimal {
-                            return format!("{}::numeric", param);
+                            return db.typed_placeholder(n, "numeric");
                         }
                     }
 
-                    param
+                    db.placeholder(n)
                 }).collect();
                 all_params.push(format!("({})", row_params.join(",")));
             }
 
-            let quoted_table = get_db().quote_identifier(&self.table_name);
+            let quoted_table = db.quote_identifier(&self.table_name);
             format!(r#"INSERT INTO {} VALUES {}"#, quoted_table, all_params.join(","))
         })
     }
@@ -577,7 +574,7 @@ impl Scheme {
     pub fn gen_bulk_update_sql_static(&self, row_count: usize) -> &'static str {
         let key = format!("{}-bulk-update-{}-rows", self.table_name, row_count);
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
             let quoted_id_field = db.quote_identifier(&self.id_field);
             let mut param_index = 1;
@@ -596,17 +593,16 @@ impl Scheme {
                     .map_or(false, |col| col.is_decimal);
 
                 let when_clauses: Vec<String> = (0..row_count).map(|_| {
-                    let id_param = param_trans(format!("${}", param_index));
-                    param_index += 1;
-                    let val_param = param_trans(format!("${}", param_index));
+                    let id_param = db.placeholder(param_index);
                     param_index += 1;
 
                     // Add ::numeric cast for DECIMAL fields
                     let val_param_with_cast = if is_decimal {
-                        format!("{}::numeric", val_param)
+                        db.typed_placeholder(param_index, "numeric")
                     } else {
-                        val_param
+                        db.placeholder(param_index)
                     };
+                    param_index += 1;
 
                     format!("WHEN {} THEN {}", id_param, val_param_with_cast)
                 }).collect();
@@ -617,7 +613,7 @@ impl Scheme {
 
             // Generate IN clause for IDs
             let id_params: Vec<String> = (0..row_count).map(|_| {
-                let p = param_trans(format!("${}", param_index));
+                let p = db.placeholder(param_index);
                 param_index += 1;
                 p
             }).collect();
@@ -636,48 +632,48 @@ impl Scheme {
     /// Returns a cached `&'static str` for efficient reuse.
     pub fn gen_update_by_id_sql_static(&self) -> &'static str {
         let key = format!("{}-update-by-id", self.table_name);
-        get_or_insert_sql(key, || {
-            let db = get_db();
-            let quoted_table = db.quote_identifier(&self.table_name);
-            let quoted_id_field = db.quote_identifier(&self.id_field);
-            let set_seq: Vec<String> = self.update_fields.iter().enumerate().map(|(idx, fd)|{
-                let quoted_field = db.quote_identifier(fd);
-                let p = format!("${}", idx + 1);
-                let p = param_trans(p);
-                // Find the column definition for this field
-                let col_def = self.column_definitions.iter()
-                    .find(|col| col.name == *fd);
-                // Add ::numeric cast for DECIMAL fields (is_decimal=true indicates DECIMAL stored as String)
-                let param_with_cast = if let Some(col) = col_def {
-                    if col.is_decimal {
-                        format!("{}::numeric", p)
-                    } else {
-                        p
-                    }
-                } else {
-                    p
-                };
-                format!("{}={}", quoted_field, param_with_cast)
-            }).collect();
+        get_or_insert_sql(key, || self.update_by_id_sql(&[]))
+    }
 
-            // Check if ID field is a DECIMAL field
-            let id_col_def = self.column_definitions.iter()
-                .find(|col| col.name == self.id_field);
-            let id_param_base = format!("${}", self.update_fields.len() + 1);
-            let id_param = param_trans(id_param_base);
-            // Add ::numeric cast for DECIMAL ID fields
-            let id_param_with_cast = if let Some(col) = id_col_def {
-                if col.is_decimal {
-                    format!("{}::numeric", id_param)
-                } else {
-                    id_param
-                }
+    /// Generates an UPDATE by ID like `gen_update_by_id_sql_static`, also
+    /// setting the given lazy columns after the regular update fields.
+    ///
+    /// Used by `update_bind` when lazy fields hold a value.
+    pub fn gen_update_by_id_with_sql_static(&self, lazy_fields: &[&str]) -> &'static str {
+        let key = format!("{}-update-by-id-with-{}", self.table_name, lazy_fields.join(","));
+        get_or_insert_sql(key, || self.update_by_id_sql(lazy_fields))
+    }
+
+    fn update_by_id_sql(&self, lazy_fields: &[&str]) -> String {
+        let db = Dialect::current();
+        let quoted_table = db.quote_identifier(&self.table_name);
+        let quoted_id_field = db.quote_identifier(&self.id_field);
+        let fields: Vec<&str> = self.update_fields.iter().map(String::as_str).chain(lazy_fields.iter().copied()).collect();
+        let set_seq: Vec<String> = fields.iter().enumerate().map(|(idx, fd)|{
+            let quoted_field = db.quote_identifier(fd);
+            // Find the column definition for this field
+            let col_def = self.column_definitions.iter()
+                .find(|col| col.name == *fd);
+            // Add ::numeric cast for DECIMAL fields (is_decimal=true indicates DECIMAL stored as String)
+            let param_with_cast = if col_def.is_some_and(|col| col.is_decimal) {
+                db.typed_placeholder(idx + 1, "numeric")
             } else {
-                id_param
+                db.placeholder(idx + 1)
             };
+            format!("{}={}", quoted_field, param_with_cast)
+        }).collect();
+
+        // Check if ID field is a DECIMAL field
+        let id_col_def = self.column_definitions.iter()
+            .find(|col| col.name == self.id_field);
+        // Add ::numeric cast for DECIMAL ID fields
+        let id_param_with_cast = if id_col_def.is_some_and(|col| col.is_decimal) {
+            db.typed_placeholder(fields.len() + 1, "numeric")
+        } else {
+            db.placeholder(fields.len() + 1)
+        };
 
-            format!(r#"UPDATE {} SET {} WHERE {}={}"#, quoted_table, set_seq.join(","), quoted_id_field, id_param_with_cast)
-        })
+        format!(r#"UPDATE {} SET {} WHERE {}={}"#, quoted_table, set_seq.join(","), quoted_id_field, id_param_with_cast)
     }
 
     /// Generates an UPDATE query with a custom WHERE clause.
@@ -686,15 +682,15 @@ impl Scheme {
     pub fn gen_update_where_sql_static(&self, where_stmt: &str) -> &'static str {
         let key = format!("{}-update-where-{}", self.table_name, where_stmt);
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
             let set_seq: Vec<String> = self.update_fields.iter().enumerate().map(|(idx, fd)|{
                 let quoted_field = db.quote_identifier(fd);
-                let p = format!("${}", idx + 1);
-                let p = param_trans(p);
-                format!("{}={}", quoted_field, p)
+                format!("{}={}", quoted_field, db.placeholder(idx + 1))
             }).collect();
-            let where_sql = prepare_where(where_stmt, self.insert_fields.len() as i32);
+            // WHERE placeholders continue after the SET ones; lazy columns are
+            // not in update_fields, so insert_fields cannot be used to count them
+            let where_sql = prepare_where(where_stmt, set_seq.len() as i32 + 1);
             format!(r#"UPDATE {} SET {} WHERE {}"#, quoted_table, set_seq.join(","), where_sql)
         })
     }
@@ -705,23 +701,18 @@ impl Scheme {
     pub fn gen_delete_sql_static(&self) -> &'static str {
         let key = format!("{}-delete-by-id", self.table_name);
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
             let quoted_id_field = db.quote_identifier(&self.id_field);
 
             // Check if ID field is a DECIMAL field
             let id_col_def = self.column_definitions.iter()
                 .find(|col| col.name == self.id_field);
-            let id_param = param_trans("$1".to_string());
             // Add ::numeric cast for DECIMAL ID fields
-            let id_param_with_cast = if let Some(col) = id_col_def {
-                if col.is_decimal {
-                    format!("{}::numeric", id_param)
-                } else {
-                    id_param
-                }
+            let id_param_with_cast = if id_col_def.is_some_and(|col| col.is_decimal) {
+                db.typed_placeholder(1, "numeric")
             } else {
-                id_param
+                db.placeholder(1)
             };
 
             format!(r#"DELETE FROM {} WHERE {}={}"#, quoted_table, quoted_id_field, id_param_with_cast)
@@ -734,7 +725,7 @@ impl Scheme {
     pub fn gen_delete_where_sql_static(&self, where_stmt: &str) -> &'static str {
         let key = format!("{}-delete-where-{}", self.table_name, where_stmt);
         get_or_insert_sql(key, || {
-            let quoted_table = get_db().quote_identifier(&self.table_name);
+            let quoted_table = Dialect::current().quote_identifier(&self.table_name);
             let where_sql = prepare_where(where_stmt, 1);
             format!(r#"DELETE FROM {} WHERE {}"#, quoted_table, where_sql)
         })
@@ -746,7 +737,7 @@ impl Scheme {
     pub fn gen_bulk_delete_sql_static(&self, count: usize) -> &'static str {
         let key = format!("{}-bulk-delete-{}", self.table_name, count);
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
             let quoted_id_field = db.quote_identifier(&self.id_field);
 
@@ -759,15 +750,14 @@ impl Scheme {
                 .map_or(false, |col| col.is_uuid);
 
             let params: Vec<String> = (1..=count).map(|i| {
-                let p = param_trans(format!("${}", i));
                 // Add ::numeric cast for DECIMAL ID fields
                 if is_id_decimal {
-                    format!("{}::numeric", p)
+                    db.typed_placeholder(i, "numeric")
                 // Add ::uuid cast for UUID ID fields
                 } else if is_id_uuid {
-                    format!("{}::uuid", p)
+                    db.typed_placeholder(i, "uuid")
                 } else {
-                    p
+                    db.placeholder(i)
                 }
             }).collect();
             let params_str = params.join(",");
@@ -794,43 +784,55 @@ impl Scheme {
     /// SELECT * FROM users WHERE id IN (?,?,?)
     /// ```
     pub fn gen_bulk_select_sql_static(&self, count: usize) -> &'static str {
+        self.gen_select_in_sql_static(&self.id_field, count)
+    }
+
+    /// Generates a SELECT of the rows whose `column` matches one of `count`
+    /// values (`WHERE column IN (...)`), with the same casts as
+    /// `gen_bulk_select_sql_static`.
+    ///
+    /// Used by relation preloading to fetch children by foreign key.
+    pub fn gen_select_in_sql_static(&self, column: &str, count: usize) -> &'static str {
         // IMPORTANT: Call gen_select_columns_static() BEFORE acquiring the lock
         // to avoid deadlock since it also accesses SQL_CACHE
         let columns = self.gen_select_columns_static();
-        let key = format!("{}-bulk-select-{}", self.table_name, count);
+        let key = if column == self.id_field {
+            format!("{}-bulk-select-{}", self.table_name, count)
+        } else {
+            format!("{}-select-in-{}-{}", self.table_name, column, count)
+        };
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
-            let quoted_id_field = db.quote_identifier(&self.id_field);
+            let quoted_column = db.quote_identifier(column);
 
-            // Check if ID field is a DECIMAL or UUID field
-            let id_col_def = self.column_definitions.iter()
-                .find(|col| col.name == self.id_field);
-            let is_id_decimal = id_col_def
-                .map_or(false, |col| col.is_decimal);
-            let is_id_uuid = id_col_def
-                .map_or(false, |col| col.is_uuid);
+            // Check if the column is a DECIMAL or UUID field
+            let col_def = self.column_definitions.iter()
+                .find(|col| col.name == column);
+            let is_decimal = col_def
+                .is_some_and(|col| col.is_decimal);
+            let is_uuid = col_def
+                .is_some_and(|col| col.is_uuid);
 
             if count == 0 {
                 // Empty list: return a query that always returns empty result
                 format!(r#"SELECT {} FROM {} WHERE 1=0"#, columns, quoted_table)
             } else {
                 let params: Vec<String> = (1..=count).map(|i| {
-                    let p = param_trans(format!("${}", i));
-                    // Add ::numeric cast for DECIMAL ID fields
-                    if is_id_decimal {
-                        format!("{}::numeric", p)
-                    // Add ::uuid cast for UUID ID fields
-                    } else if is_id_uuid {
-                        format!("{}::uuid", p)
+                    // Add ::numeric cast for DECIMAL columns
+                    if is_decimal {
+                        db.typed_placeholder(i, "numeric")
+                    // Add ::uuid cast for UUID columns
+                    } else if is_uuid {
+                        db.typed_placeholder(i, "uuid")
                     } else {
-                        p
+                        db.placeholder(i)
                     }
                 }).collect();
                 let in_clause = params.join(",");
                 format!(
                     r#"SELECT {} FROM {} WHERE {} IN ({})"#,
-                    columns, quoted_table, quoted_id_field, in_clause
+                    columns, quoted_table, quoted_column, in_clause
                 )
             }
         })
@@ -845,29 +847,50 @@ impl Scheme {
         let columns = self.gen_select_columns_static();
         let key = format!("{}-select-by-id", self.table_name);
         get_or_insert_sql(key, || {
-            let db = get_db();
+            let db = Dialect::current();
             let quoted_table = db.quote_identifier(&self.table_name);
             let quoted_id_field = db.quote_identifier(&self.id_field);
 
             // Check if ID field is a DECIMAL field
             let id_col_def = self.column_definitions.iter()
                 .find(|col| col.name == self.id_field);
-            let id_param = param_trans("$1".to_string());
             // Add ::numeric cast for DECIMAL ID fields
-            let id_param_with_cast = if let Some(col) = id_col_def {
-                if col.is_decimal {
-                    format!("{}::numeric", id_param)
-                } else {
-                    id_param
-                }
+            let id_param_with_cast = if id_col_def.is_some_and(|col| col.is_decimal) {
+                db.typed_placeholder(1, "numeric")
             } else {
-                id_param
+                db.placeholder(1)
             };
 
             format!(r#"SELECT {} FROM {} WHERE {}={}"#, columns, quoted_table, quoted_id_field, id_param_with_cast)
         })
     }
 
+    /// Generates a SELECT of a single column by ID, used to load lazy fields.
+    ///
+    /// Returns a cached `&'static str` for efficient reuse.
+    pub fn gen_select_column_by_id_sql_static(&self, column: &str) -> &'static str {
+        let key = format!("{}-select-column-by-id-{}", self.table_name, column);
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            let quoted_table = db.quote_identifier(&self.table_name);
+            let quoted_id_field = db.quote_identifier(&self.id_field);
+            let cast_as = self.column_definitions.iter()
+                .find(|col| col.name == column)
+                .and_then(|col| col.cast_as.as_deref());
+            let is_id_decimal = self.column_definitions.iter()
+                .any(|col| col.name == self.id_field && col.is_decimal);
+            let id_param_with_cast = if is_id_decimal {
+                db.typed_placeholder(1, "numeric")
+            } else {
+                db.placeholder(1)
+            };
+            format!(
+                r#"SELECT {} FROM {} WHERE {}={}"#,
+                select_column_sql(column, cast_as), quoted_table, quoted_id_field, id_param_with_cast
+            )
+        })
+    }
+
     /// Generates a SELECT query with a custom WHERE clause.
     ///
     /// Returns a cached `&'static str` for efficient reuse.
@@ -877,12 +900,45 @@ impl Scheme {
         let columns = self.gen_select_columns_static();
         let key = format!("{}-select-where-{}", self.table_name, where_stmt);
         get_or_insert_sql(key, || {
-            let quoted_table = get_db().quote_identifier(&self.table_name);
+            let quoted_table = Dialect::current().quote_identifier(&self.table_name);
             let where_sql = prepare_where(where_stmt, 1);
             format!(r#"SELECT {} FROM {} WHERE {}"#, columns, quoted_table, where_sql)
         })
     }
 
+    /// Generates a paginated SELECT query with a custom WHERE clause.
+    ///
+    /// Rows are ordered by the ID field and the last two placeholders are
+    /// LIMIT and OFFSET. With `with_total`, each row also carries the total
+    /// match count via `COUNT(*) OVER()` so the page and its total come back
+    /// in a single round trip.
+    ///
+    /// Returns a cached `&'static str` for efficient reuse.
+    pub fn gen_paginate_sql_static(&self, where_stmt: &str, with_total: bool) -> &'static str {
+        // IMPORTANT: Call gen_select_columns_static() BEFORE acquiring the lock
+        // to avoid deadlock since it also accesses SQL_CACHE
+        let columns = self.gen_select_columns_static();
+        let order_by = Dialect::current().quote_identifier(&self.id_field);
+        self.gen_paginate_ordered_sql_static(where_stmt, columns, &order_by, with_total)
+    }
+
+    /// Generates a paginated SELECT like `gen_paginate_sql_static` with an
+    /// already rendered column list and ORDER BY list.
+    pub fn gen_paginate_ordered_sql_static(&self, where_stmt: &str, columns: &str, order_by: &str, with_total: bool) -> &'static str {
+        let key = format!("{}-paginate-{}-{}-{}-{}", self.table_name, with_total, columns, where_stmt, order_by);
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            let quoted_table = db.quote_identifier(&self.table_name);
+            let total = if with_total {
+                format!(", COUNT(*) OVER() AS {}", db.quote_identifier(pagination::TOTAL_COLUMN))
+            } else {
+                String::new()
+            };
+            let where_sql = prepare_where(&format!("{} ORDER BY {} LIMIT {{}} OFFSET {{}}", where_stmt, order_by), 1);
+            format!(r#"SELECT {}{} FROM {} WHERE {}"#, columns, total, quoted_table, where_sql)
+        })
+    }
+
     /// Prepares custom SQL by replacing the `[Self]` placeholder with the table name.
     ///
     /// This method is used for custom queries where you want to dynamically insert the table name.
@@ -902,7 +958,7 @@ impl Scheme {
     /// // Output: "SELECT id, name, amount::TEXT as amount FROM my_table WHERE id = $1"
     /// ```
     pub fn pre_sql_static(&self, sql: &str) -> String {
-        let quoted_table = get_db().quote_identifier(&self.table_name);
+        let quoted_table = Dialect::current().quote_identifier(&self.table_name);
         let sql = sql.replace("[Self]", quoted_table.as_str());
 
         // Replace SELECT * with explicit column list to apply cast_as
@@ -915,36 +971,6 @@ impl Scheme {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-#[allow(dead_code)]
-enum DbType {
-    PostgreSQL,
-    MySQL,
-    SQLite
-}
-
-impl DbType {
-    /// 为SQL标识符添加数据库特定的引号包装
-    ///
-    /// PostgreSQL: "identifier"
-    /// MySQL: `identifier`
-    /// SQLite: identifier (无引号)
-    ///
-    /// # 示例
-    /// ```
-    /// DbType::PostgreSQL.quote_identifier("user")  // "\"user\""
-    /// DbType::MySQL.quote_identifier("user")       // "`user`"
-    /// DbType::SQLite.quote_identifier("user")      // "user"
-    /// ```
-    pub fn quote_identifier(&self, identifier: &str) -> String {
-        match self {
-            DbType::PostgreSQL => format!("\"{}\"", identifier),
-            DbType::MySQL => format!("`{}`", identifier),
-            DbType::SQLite => identifier.to_string(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1046,8 +1072,8 @@ mod tests {
             update_fields: vec!["commission_rate".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "commission_rate".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "commission_rate".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -1066,10 +1092,10 @@ mod tests {
             update_fields: vec!["name".to_string(), "price".to_string(), "discount".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "name".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
-                ColumnDefinition { name: "discount".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "name".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "discount".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -1094,9 +1120,9 @@ mod tests {
             update_fields: vec!["price".to_string(), "discount".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
-                ColumnDefinition { name: "discount".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "discount".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -1121,9 +1147,9 @@ mod tests {
             update_fields: vec!["name".to_string(), "price".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "name".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "name".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -1148,8 +1174,8 @@ mod tests {
             update_fields: vec!["price".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
-                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "price".to_string(), cast_as: Some("TEXT".to_string()), is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -1290,6 +1316,32 @@ mod tests {
         assert_eq!(sql, "UPDATE products SET name=?,price=? WHERE category = ?");
     }
 
+    #[test]
+    fn test_update_where_skips_lazy_column() {
+        let scheme = Scheme {
+            table_name: "lazy_notes".to_string(),
+            insert_fields: vec!["id".to_string(), "title".to_string(), "body".to_string()],
+            update_fields: vec!["title".to_string()],
+            id_field: "id".to_string(),
+            column_definitions: vec![
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "title".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "body".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: true },
+            ],
+        };
+
+        let sql = scheme.gen_update_where_sql_static("category = {} AND owner = {}");
+
+        #[cfg(feature = "postgres")]
+        assert_eq!(sql, "UPDATE \"lazy_notes\" SET \"title\"=$1 WHERE category = $2 AND owner = $3");
+
+        #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+        assert_eq!(sql, "UPDATE `lazy_notes` SET `title`=? WHERE category = ? AND owner = ?");
+
+        #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+        assert_eq!(sql, "UPDATE lazy_notes SET title=? WHERE category = ? AND owner = ?");
+    }
+
     #[test]
     fn test_delete_where() {
         let scheme = Scheme {
@@ -2477,8 +2529,8 @@ mod tests {
             update_fields: vec!["customer_name".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true },
-                ColumnDefinition { name: "customer_name".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true, is_lazy: false },
+                ColumnDefinition { name: "customer_name".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -2502,8 +2554,8 @@ mod tests {
             update_fields: vec!["customer_name".to_string()],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true },
-                ColumnDefinition { name: "customer_name".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true, is_lazy: false },
+                ColumnDefinition { name: "customer_name".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -2528,7 +2580,7 @@ mod tests {
             update_fields: vec![],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: true, is_lazy: false },
             ],
         };
 
@@ -2539,7 +2591,7 @@ mod tests {
             update_fields: vec![],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: true, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: true, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -2550,7 +2602,7 @@ mod tests {
             update_fields: vec![],
             id_field: "id".to_string(),
             column_definitions: vec![
-                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false },
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
             ],
         };
 
@@ -2568,5 +2620,79 @@ mod tests {
             assert_eq!(sql_regular, "DELETE FROM \"regular_table\" WHERE \"id\" IN ($1,$2)");
         }
     }
-}
 
+    #[test]
+    fn test_paginate_sql_generation() {
+        let scheme = Scheme {
+            table_name: "paged_items".to_string(),
+            insert_fields: vec!["id".to_string(), "status".to_string()],
+            update_fields: vec!["status".to_string()],
+            id_field: "id".to_string(),
+            column_definitions: vec![
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "status".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+            ],
+        };
+
+        let with_total = scheme.gen_paginate_sql_static("status = {}", true);
+        let without_total = scheme.gen_paginate_sql_static("status = {}", false);
+
+        #[cfg(feature = "postgres")]
+        {
+            assert_eq!(with_total, r#"SELECT "id", "status", COUNT(*) OVER() AS "__total" FROM "paged_items" WHERE status = $1 ORDER BY "id" LIMIT $2 OFFSET $3"#);
+            assert_eq!(without_total, r#"SELECT "id", "status" FROM "paged_items" WHERE status = $1 ORDER BY "id" LIMIT $2 OFFSET $3"#);
+        }
+
+        #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+        {
+            assert_eq!(with_total, "SELECT `id`, `status`, COUNT(*) OVER() AS `__total` FROM `paged_items` WHERE status = ? ORDER BY `id` LIMIT ? OFFSET ?");
+            assert_eq!(without_total, "SELECT `id`, `status` FROM `paged_items` WHERE status = ? ORDER BY `id` LIMIT ? OFFSET ?");
+        }
+
+        #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+        {
+            assert_eq!(with_total, "SELECT id, status, COUNT(*) OVER() AS __total FROM paged_items WHERE status = ? ORDER BY id LIMIT ? OFFSET ?");
+            assert_eq!(without_total, "SELECT id, status FROM paged_items WHERE status = ? ORDER BY id LIMIT ? OFFSET ?");
+        }
+    }
+
+    #[test]
+    fn test_lazy_column_sql_generation() {
+        let scheme = Scheme {
+            table_name: "lazy_docs".to_string(),
+            insert_fields: vec!["id".to_string(), "title".to_string(), "body".to_string()],
+            update_fields: vec!["title".to_string()],
+            id_field: "id".to_string(),
+            column_definitions: vec![
+                ColumnDefinition { name: "id".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "title".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: false },
+                ColumnDefinition { name: "body".to_string(), cast_as: None, is_decimal: false, is_uuid: false, is_lazy: true },
+            ],
+        };
+
+        let columns = scheme.gen_select_columns_static();
+        let update = scheme.gen_update_by_id_with_sql_static(&["body"]);
+        let load = scheme.gen_select_column_by_id_sql_static("body");
+
+        #[cfg(feature = "postgres")]
+        {
+            assert_eq!(columns, r#""id", "title""#);
+            assert_eq!(update, r#"UPDATE "lazy_docs" SET "title"=$1,"body"=$2 WHERE "id"=$3"#);
+            assert_eq!(load, r#"SELECT "body" FROM "lazy_docs" WHERE "id"=$1"#);
+        }
+
+        #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+        {
+            assert_eq!(columns, "`id`, `title`");
+            assert_eq!(update, "UPDATE `lazy_docs` SET `title`=?,`body`=? WHERE `id`=?");
+            assert_eq!(load, "SELECT `body` FROM `lazy_docs` WHERE `id`=?");
+        }
+
+        #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+        {
+            assert_eq!(columns, "id, title");
+            assert_eq!(update, "UPDATE lazy_docs SET title=?,body=? WHERE id=?");
+            assert_eq!(load, "SELECT body FROM lazy_docs WHERE id=?");
+        }
+    }
+}
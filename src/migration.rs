@@ -5,6 +5,8 @@
 
 use sqlx::{Pool, FromRow, Row as _};
 
+use crate::Dialect;
+
 #[cfg(feature = "postgres")]
 use sqlx::Postgres;
 
@@ -1265,32 +1267,47 @@ pub struct IndexCost {
 
 /// Generates UP and DOWN SQL statements for migrations
 pub struct SqlGenerator {
-    /// Database type (postgres, mysql, sqlite)
-    pub database_type: String,
+    /// SQL dialect of the target database
+    pub dialect: Dialect,
 }
 
 impl SqlGenerator {
+    /// Create a new SQL generator for the given dialect
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect }
+    }
+
     /// Create a new SQL generator for PostgreSQL
     pub fn new_postgres() -> Self {
-        Self {
-            database_type: "postgres".to_string(),
-        }
+        Self::new(Dialect::PostgreSQL)
     }
 
     /// Create a new SQL generator for MySQL
     pub fn new_mysql() -> Self {
-        Self {
-            database_type: "mysql".to_string(),
-        }
+        Self::new(Dialect::MySQL)
     }
 
     /// Create a new SQL generator for SQLite
     pub fn new_sqlite() -> Self {
-        Self {
-            database_type: "sqlite".to_string(),
+        Self::new(Dialect::SQLite)
+    }
+
+    /// Database type name (`"postgres"`, `"mysql"` or `"sqlite"`), as held by
+    /// the former `database_type` field
+    #[deprecated(note = "use the `dialect` field instead")]
+    pub fn database_type(&self) -> &'static str {
+        match self.dialect {
+            Dialect::PostgreSQL => "postgres",
+            Dialect::MySQL => "mysql",
+            Dialect::SQLite => "sqlite",
         }
     }
 
+    /// Quotes a table, column or index name for the target dialect
+    fn quote(&self, name: &str) -> String {
+        self.dialect.quote_identifier(name)
+    }
+
     /// Generate UP and DOWN SQL for a complete migration
     pub fn generate_migration_sql(
         &self,
@@ -1424,8 +1441,8 @@ impl SqlGenerator {
                                 DataMigrationType::Compute { expression } => {
                                     let sql = format!(
                                         "UPDATE {} SET {} = {}",
-                                        change.table_name,
-                                        column.name,
+                                        self.quote(&change.table_name),
+                                        self.quote(&column.name),
                                         expression
                                     );
                                     up_sql.push(sql);
@@ -1433,15 +1450,15 @@ impl SqlGenerator {
                                     let default_sql = if let Some(default_val) = &column.default {
                                         format!(
                                             "UPDATE {} SET {} = {}",
-                                            change.table_name,
-                                            column.name,
+                                            self.quote(&change.table_name),
+                                            self.quote(&column.name),
                                             default_val
                                         )
                                     } else if column.nullable {
                                         format!(
                                             "UPDATE {} SET {} = NULL",
-                                            change.table_name,
-                                            column.name
+                                            self.quote(&change.table_name),
+                                            self.quote(&column.name)
                                         )
                                     } else {
                                         continue; // Skip if no default and not nullable
@@ -1506,60 +1523,57 @@ impl SqlGenerator {
 
         format!(
             "CREATE TABLE {} (\n    {}\n);",
-            table.name,
+            self.quote(&table.name),
             columns_str
         )
     }
 
     /// Generate DROP TABLE SQL
     pub fn generate_drop_table_sql(&self, table_name: &str) -> String {
-        format!("DROP TABLE IF EXISTS {};", table_name)
+        format!("DROP TABLE IF EXISTS {};", self.quote(table_name))
     }
 
     /// Generate RENAME TABLE SQL
     pub fn generate_rename_table_sql(&self, old_name: &str, new_name: &str) -> String {
-        match self.database_type.as_str() {
-            "postgres" | "sqlite" => {
-                format!("ALTER TABLE {} RENAME TO {};", old_name, new_name)
+        match self.dialect {
+            Dialect::PostgreSQL | Dialect::SQLite => {
+                format!("ALTER TABLE {} RENAME TO {};", self.quote(old_name), self.quote(new_name))
             }
-            "mysql" => {
-                format!("RENAME TABLE {} TO {};", old_name, new_name)
+            Dialect::MySQL => {
+                format!("RENAME TABLE {} TO {};", self.quote(old_name), self.quote(new_name))
             }
-            _ => format!("ALTER TABLE {} RENAME TO {};", old_name, new_name),
         }
     }
 
     /// Generate ADD COLUMN SQL
     pub fn generate_add_column_sql(&self, table_name: &str, column: &ColumnDef) -> String {
         let column_def = self.format_column_definition(column);
-        format!("ALTER TABLE {} ADD COLUMN {};", table_name, column_def)
+        format!("ALTER TABLE {} ADD COLUMN {};", self.quote(table_name), column_def)
     }
 
     /// Generate ADD COLUMN SQL (simple version for DOWN migration)
     fn generate_add_column_simple_sql(&self, table_name: &str, column_name: &str, sql_type: &str) -> String {
-        match self.database_type.as_str() {
-            "postgres" => {
-                format!("ALTER TABLE {} ADD COLUMN {} {};", table_name, column_name, sql_type)
-            }
-            "mysql" => {
-                format!("ALTER TABLE {} ADD COLUMN {} {};", table_name, column_name, sql_type)
-            }
-            "sqlite" => {
-                // SQLite doesn't support ALTER TABLE ADD COLUMN with constraints in older versions
-                // For simplicity, we use basic syntax
-                format!("ALTER TABLE {} ADD COLUMN {} {};", table_name, column_name, sql_type)
-            }
-            _ => format!("ALTER TABLE {} ADD COLUMN {} {};", table_name, column_name, sql_type),
-        }
+        // SQLite doesn't support ALTER TABLE ADD COLUMN with constraints in older versions,
+        // so the basic syntax is used everywhere
+        format!(
+            "ALTER TABLE {} ADD COLUMN {} {};",
+            self.quote(table_name),
+            self.quote(column_name),
+            sql_type
+        )
     }
 
     /// Generate DROP COLUMN SQL
     fn generate_drop_column_sql(&self, table_name: &str, column_name: &str) -> String {
-        match self.database_type.as_str() {
-            "postgres" | "mysql" => {
-                format!("ALTER TABLE {} DROP COLUMN {};", table_name, column_name)
+        match self.dialect {
+            Dialect::PostgreSQL | Dialect::MySQL => {
+                format!(
+                    "ALTER TABLE {} DROP COLUMN {};",
+                    self.quote(table_name),
+                    self.quote(column_name)
+                )
             }
-            "sqlite" => {
+            Dialect::SQLite => {
                 // SQLite has limited ALTER TABLE support
                 // In real implementation, would need to recreate table
                 format!(
@@ -1567,82 +1581,83 @@ impl SqlGenerator {
                     table_name, column_name
                 )
             }
-            _ => format!("ALTER TABLE {} DROP COLUMN {};", table_name, column_name),
         }
     }
 
     /// Generate RENAME COLUMN SQL
     fn generate_rename_column_sql(&self, table_name: &str, old_name: &str, new_name: &str) -> String {
-        match self.database_type.as_str() {
-            "postgres" => {
+        match self.dialect {
+            Dialect::PostgreSQL => {
                 format!(
                     "ALTER TABLE {} RENAME COLUMN {} TO {};",
-                    table_name, old_name, new_name
+                    self.quote(table_name),
+                    self.quote(old_name),
+                    self.quote(new_name)
                 )
             }
-            "mysql" => {
+            Dialect::MySQL => {
                 format!(
                     "ALTER TABLE {} CHANGE COLUMN {} {} {}",
-                    table_name, old_name, new_name, "VARCHAR(255)" // Would need actual type
+                    self.quote(table_name),
+                    self.quote(old_name),
+                    self.quote(new_name),
+                    "VARCHAR(255)" // Would need actual type
                 )
             }
-            "sqlite" => {
+            Dialect::SQLite => {
                 format!(
                     "-- SQLite requires table recreation to rename column: {}.{} -> {}",
                     table_name, old_name, new_name
                 )
             }
-            _ => format!(
-                "ALTER TABLE {} RENAME COLUMN {} TO {};",
-                table_name, old_name, new_name
-            ),
         }
     }
 
     /// Generate CREATE INDEX SQL
     pub fn generate_create_index_sql(&self, table_name: &str, index: &IndexDef) -> String {
         let unique = if index.unique { "UNIQUE " } else { "" };
-        let columns_str = index.columns.join(", ");
-
-        match self.database_type.as_str() {
-            "postgres" => {
+        // Index columns may be expressions such as `lower(email)`, which
+        // `quote_column` leaves as they are
+        let columns_str = index
+            .columns
+            .iter()
+            .map(|column| self.dialect.quote_column(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let index_name = self.quote(&index.name);
+        let table_name = self.quote(table_name);
+
+        match self.dialect {
+            Dialect::PostgreSQL => {
                 format!(
                     "CREATE {}INDEX IF NOT EXISTS {} ON {} USING {} ({});",
-                    unique, index.name, table_name, index.index_type, columns_str
+                    unique, index_name, table_name, index.index_type, columns_str
                 )
             }
-            "mysql" => {
+            Dialect::MySQL => {
                 format!(
                     "CREATE {}INDEX {} ON {} ({});",
-                    unique, index.name, table_name, columns_str
+                    unique, index_name, table_name, columns_str
                 )
             }
-            "sqlite" => {
+            Dialect::SQLite => {
                 format!(
                     "CREATE {}INDEX IF NOT EXISTS {} ON {} ({});",
-                    unique, index.name, table_name, columns_str
+                    unique, index_name, table_name, columns_str
                 )
             }
-            _ => format!(
-                "CREATE {}INDEX {} ON {} ({});",
-                unique, index.name, table_name, columns_str
-            ),
         }
     }
 
     /// Generate DROP INDEX SQL
     fn generate_drop_index_sql(&self, index_name: &str) -> String {
-        match self.database_type.as_str() {
-            "postgres" => {
-                format!("DROP INDEX IF EXISTS {};", index_name)
-            }
-            "mysql" => {
-                format!("DROP INDEX {};", index_name)
+        match self.dialect {
+            Dialect::PostgreSQL | Dialect::SQLite => {
+                format!("DROP INDEX IF EXISTS {};", self.quote(index_name))
             }
-            "sqlite" => {
-                format!("DROP INDEX IF EXISTS {};", index_name)
+            Dialect::MySQL => {
+                format!("DROP INDEX {};", self.quote(index_name))
             }
-            _ => format!("DROP INDEX IF NOT EXISTS {};", index_name),
         }
     }
 
@@ -1657,7 +1672,10 @@ impl SqlGenerator {
 
         format!(
             "{} {}{}{}",
-            column.name, column.sql_type, null_constraint, default_constraint
+            self.quote(&column.name),
+            column.sql_type,
+            null_constraint,
+            default_constraint
         )
     }
 
@@ -1670,7 +1688,9 @@ impl SqlGenerator {
     ) -> String {
         format!(
             "UPDATE {} SET {} = {};",
-            table_name, column_name, expression
+            self.quote(table_name),
+            self.quote(column_name),
+            expression
         )
     }
 }
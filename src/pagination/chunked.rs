@@ -0,0 +1,256 @@
+//! Chunked iteration over a whole table in primary-key order.
+
+use futures::stream::{self, Stream, TryStreamExt};
+use sqlx::{Database, Error, FromRow, Pool};
+use std::future::Future;
+
+use super::Keyset;
+use crate::filter::{Column, Expr};
+use crate::proxy::BindProxy;
+use crate::Scheme;
+
+#[cfg(feature = "postgres")]
+use sqlx::{postgres::PgRow, Postgres};
+
+#[cfg(feature = "mysql")]
+use sqlx::{mysql::MySqlRow, MySql};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{sqlite::SqliteRow, Sqlite};
+
+/// Chunked scan created by `EnhancedCrud::chunked`.
+///
+/// Each chunk is a primary-key range: the next query continues strictly after
+/// the last key of the previous chunk, so rows are never skipped or repeated
+/// by concurrent inserts the way OFFSET paging would.
+pub struct Chunked<T, DB: Database> {
+    scheme: &'static Scheme,
+    id: Column,
+    chunk_size: u64,
+    filter: Option<Expr<DB>>,
+    resume: Option<Expr<DB>>,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T, DB: Database> Chunked<T, DB> {
+    /// Creates a scan over the primary-key column `id` returning `chunk_size`
+    /// rows (at least 1) per query.
+    pub fn new(scheme: &'static Scheme, id: Column, chunk_size: u64) -> Self {
+        Self {
+            scheme,
+            id,
+            chunk_size: chunk_size.max(1),
+            filter: None,
+            resume: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Restricts the rows being scanned. Repeated calls are combined with AND.
+    pub fn filter(mut self, expr: Expr<DB>) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => existing.and(expr),
+            None => expr,
+        });
+        self
+    }
+
+    /// Starts after a previously processed primary key, e.g. one saved by an
+    /// interrupted job.
+    pub fn resume_after<K: BindProxy<DB>>(mut self, key: K) -> Self {
+        self.resume = Some(self.id.gt(key));
+        self
+    }
+
+    /// The keyset query fetching the first chunk.
+    pub fn into_keyset(self) -> Keyset<T, DB> {
+        let keyset = Keyset::new(self.scheme, self.id, None).limit(self.chunk_size);
+        self.filter.into_iter().chain(self.resume).fold(keyset, Keyset::filter)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> Chunked<T, Postgres>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    /// Calls `f` with each chunk in primary-key order and returns the number
+    /// of rows processed. Stops at the first error from the query or from `f`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::chunked(1000)
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .for_each_chunk(&pool, |batch| async move {
+    ///         reindex(&batch).await
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn for_each_chunk<F, Fut, E>(self, pool: &Pool<Postgres>, mut f: F) -> Result<u64, E>
+    where
+        F: FnMut(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: From<Error>,
+    {
+        let mut keyset = self.into_keyset();
+        let mut processed = 0;
+        loop {
+            let page = keyset.clone().fetch(pool).await?;
+            if page.items.is_empty() {
+                break;
+            }
+            processed += page.items.len() as u64;
+            f(page.items).await?;
+            match page.next_cursor {
+                Some(cursor) => keyset.set_cursor(cursor),
+                None => break,
+            }
+        }
+        Ok(processed)
+    }
+
+    /// Streams rows one at a time, fetching a chunk whenever the previous one
+    /// has been consumed.
+    pub fn stream<'p>(self, pool: &'p Pool<Postgres>) -> impl Stream<Item = Result<T, Error>> + 'p
+    where
+        T: 'p,
+    {
+        stream::try_unfold(Some(self.into_keyset()), move |state| async move {
+            let Some(mut keyset) = state else {
+                return Ok::<_, Error>(None);
+            };
+            let page = keyset.clone().fetch(pool).await?;
+            let next = page.next_cursor.map(|cursor| {
+                keyset.set_cursor(cursor);
+                keyset
+            });
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+impl<T> Chunked<T, MySql>
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    /// Calls `f` with each chunk in primary-key order and returns the number
+    /// of rows processed. Stops at the first error from the query or from `f`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::chunked(1000)
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .for_each_chunk(&pool, |batch| async move {
+    ///         reindex(&batch).await
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn for_each_chunk<F, Fut, E>(self, pool: &Pool<MySql>, mut f: F) -> Result<u64, E>
+    where
+        F: FnMut(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: From<Error>,
+    {
+        let mut keyset = self.into_keyset();
+        let mut processed = 0;
+        loop {
+            let page = keyset.clone().fetch(pool).await?;
+            if page.items.is_empty() {
+                break;
+            }
+            processed += page.items.len() as u64;
+            f(page.items).await?;
+            match page.next_cursor {
+                Some(cursor) => keyset.set_cursor(cursor),
+                None => break,
+            }
+        }
+        Ok(processed)
+    }
+
+    /// Streams rows one at a time, fetching a chunk whenever the previous one
+    /// has been consumed.
+    pub fn stream<'p>(self, pool: &'p Pool<MySql>) -> impl Stream<Item = Result<T, Error>> + 'p
+    where
+        T: 'p,
+    {
+        stream::try_unfold(Some(self.into_keyset()), move |state| async move {
+            let Some(mut keyset) = state else {
+                return Ok::<_, Error>(None);
+            };
+            let page = keyset.clone().fetch(pool).await?;
+            let next = page.next_cursor.map(|cursor| {
+                keyset.set_cursor(cursor);
+                keyset
+            });
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+impl<T> Chunked<T, Sqlite>
+where
+    T: for<'r> FromRow<'r, SqliteRow> + Send + Unpin,
+{
+    /// Calls `f` with each chunk in primary-key order and returns the number
+    /// of rows processed. Stops at the first error from the query or from `f`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::chunked(1000)
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .for_each_chunk(&pool, |batch| async move {
+    ///         reindex(&batch).await
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn for_each_chunk<F, Fut, E>(self, pool: &Pool<Sqlite>, mut f: F) -> Result<u64, E>
+    where
+        F: FnMut(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: From<Error>,
+    {
+        let mut keyset = self.into_keyset();
+        let mut processed = 0;
+        loop {
+            let page = keyset.clone().fetch(pool).await?;
+            if page.items.is_empty() {
+                break;
+            }
+            processed += page.items.len() as u64;
+            f(page.items).await?;
+            match page.next_cursor {
+                Some(cursor) => keyset.set_cursor(cursor),
+                None => break,
+            }
+        }
+        Ok(processed)
+    }
+
+    /// Streams rows one at a time, fetching a chunk whenever the previous one
+    /// has been consumed.
+    pub fn stream<'p>(self, pool: &'p Pool<Sqlite>) -> impl Stream<Item = Result<T, Error>> + 'p
+    where
+        T: 'p,
+    {
+        stream::try_unfold(Some(self.into_keyset()), move |state| async move {
+            let Some(mut keyset) = state else {
+                return Ok::<_, Error>(None);
+            };
+            let page = keyset.clone().fetch(pool).await?;
+            let next = page.next_cursor.map(|cursor| {
+                keyset.set_cursor(cursor);
+                keyset
+            });
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+}
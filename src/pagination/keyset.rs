@@ -0,0 +1,375 @@
+//! Keyset (cursor) pagination.
+
+use sqlx::{ColumnIndex, Database, Decode, Error, FromRow, Pool, Row, Type};
+use std::marker::PhantomData;
+
+use crate::filter::{Column, Expr, Order};
+use crate::proxy::BindValue;
+use crate::{get_or_insert_sql, prepare_where, Dialect, Scheme};
+
+#[cfg(feature = "postgres")]
+use sqlx::{postgres::PgRow, Postgres};
+
+#[cfg(feature = "mysql")]
+use sqlx::{mysql::MySqlRow, MySql};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{sqlite::SqliteRow, Sqlite};
+
+/// Prefix of the extra text columns carrying the cursor values.
+const CURSOR_COLUMN: &str = "__cursor_";
+
+/// Opaque position in a keyset-paginated result set.
+///
+/// The cursor holds only the sort-key values, rendered as text; the column
+/// types used to compare them come from the sort-key [`Column`]s. `encode`
+/// produces a URL-safe token that can be handed to clients and turned back
+/// into a cursor with `decode`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    values: Vec<String>,
+}
+
+impl Cursor {
+    /// Encodes the cursor as an opaque, URL-safe string.
+    pub fn encode(&self) -> String {
+        self.values.iter().map(|v| hex_encode(v)).collect::<Vec<_>>().join(".")
+    }
+
+    /// Decodes a string produced by [`Cursor::encode`]. Returns `None` for malformed input.
+    pub fn decode(encoded: &str) -> Option<Self> {
+        if encoded.is_empty() {
+            return None;
+        }
+        let values = encoded.split('.').map(hex_decode).collect::<Option<Vec<_>>>()?;
+        Some(Self { values })
+    }
+
+    fn from_row<R>(row: &R, key_count: usize) -> Result<Self, Error>
+    where
+        R: Row,
+        for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+        for<'a> &'a str: ColumnIndex<R>,
+    {
+        let values = (0..key_count)
+            .map(|i| row.try_get(format!("{}{}", CURSOR_COLUMN, i).as_str()))
+            .collect::<Result<Vec<String>, Error>>()?;
+        Ok(Self { values })
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+fn hex_encode(s: &str) -> String {
+    s.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_decode(s: &str) -> Option<String> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// A page of keyset-paginated results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetPage<T> {
+    pub items: Vec<T>,
+    /// Cursor for the following page, `None` when this is the last page.
+    pub next_cursor: Option<Cursor>,
+}
+
+/// Keyset pagination query created by `EnhancedCrud::after`.
+///
+/// Rows are ordered by the chosen columns, with the primary key appended as
+/// a tie-breaker, and each page continues strictly after the cursor. Key
+/// columns should be NOT NULL.
+pub struct Keyset<T, DB: Database> {
+    scheme: &'static Scheme,
+    id: Column,
+    cursor: Option<Cursor>,
+    order: Vec<(Column, Order)>,
+    filter: Option<Expr<DB>>,
+    limit: u64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, DB: Database> Clone for Keyset<T, DB> {
+    fn clone(&self) -> Self {
+        Self {
+            scheme: self.scheme,
+            id: self.id,
+            cursor: self.cursor.clone(),
+            order: self.order.clone(),
+            filter: self.filter.clone(),
+            limit: self.limit,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, DB: Database> Keyset<T, DB> {
+    /// Default page size when `limit` is not called.
+    pub const DEFAULT_LIMIT: u64 = 100;
+
+    /// Creates a keyset query starting after `cursor` (or at the beginning for `None`).
+    ///
+    /// `id` is the primary-key column, appended to the sort keys as a tie-breaker.
+    pub fn new(scheme: &'static Scheme, id: Column, cursor: Option<Cursor>) -> Self {
+        Self {
+            scheme,
+            id,
+            cursor,
+            order: Vec::new(),
+            filter: None,
+            limit: Self::DEFAULT_LIMIT,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Adds a sort key. The cursor must come from a query with the same keys.
+    ///
+    /// On PostgreSQL cursor values are compared in the column's type, so
+    /// the column must carry one: the generated column constants do for
+    /// common field types, others need [`Column::typed`]. `build` and
+    /// `fetch` fail otherwise.
+    pub fn order_by(mut self, column: Column, order: Order) -> Self {
+        self.order.push((column, order));
+        self
+    }
+
+    /// Restricts the rows being paginated. Repeated calls are combined with AND.
+    pub fn filter(mut self, expr: Expr<DB>) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => existing.and(expr),
+            None => expr,
+        });
+        self
+    }
+
+    /// Sets the page size (at least 1).
+    pub fn limit(mut self, n: u64) -> Self {
+        self.limit = n.max(1);
+        self
+    }
+
+    /// Moves the query to the page after `cursor`.
+    pub(crate) fn set_cursor(&mut self, cursor: Cursor) {
+        self.cursor = Some(cursor);
+    }
+
+    /// Sort keys including the primary-key tie-breaker.
+    fn key_columns(&self) -> Vec<(Column, Order)> {
+        let mut keys = self.order.clone();
+        if !keys.iter().any(|(column, _)| column.name() == self.id.name()) {
+            keys.push((self.id, Order::Asc));
+        }
+        keys
+    }
+
+    /// Builds the SQL for this page and the WHERE/keyset binds preceding the LIMIT.
+    ///
+    /// Fails if the cursor was produced for a different set of sort keys, if
+    /// a query nested in the filter is invalid, or on PostgreSQL if a sort key
+    /// has no known column type.
+    pub fn build(&self) -> Result<(&'static str, Vec<BindValue<DB>>), Error> {
+        if let Some(problem) = self.filter.as_ref().and_then(|filter| filter.problem()) {
+            return Err(Error::Configuration(format!("invalid subquery: {}", problem).into()));
+        }
+        // IMPORTANT: Call gen_select_columns_static() BEFORE acquiring the lock
+        // to avoid deadlock since it also accesses SQL_CACHE
+        let columns = self.scheme.gen_select_columns_static();
+        let keys = self.key_columns();
+        let db = Dialect::current();
+        if db == Dialect::PostgreSQL {
+            if let Some((column, _)) = keys.iter().find(|(column, _)| column.sql_type().is_none()) {
+                return Err(Error::Configuration(
+                    format!(
+                        "keyset sort key `{}` has no known column type to compare cursor values in; declare it with Column::typed",
+                        column.name()
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        let text_type = match db {
+            Dialect::MySQL => "CHAR",
+            Dialect::PostgreSQL | Dialect::SQLite => "TEXT",
+        };
+        let cursor_columns = keys
+            .iter()
+            .enumerate()
+            .map(|(i, (column, _))| {
+                format!(
+                    "CAST({} AS {}) AS {}",
+                    db.quote_identifier(column.name()),
+                    text_type,
+                    db.quote_identifier(&format!("{}{}", CURSOR_COLUMN, i))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        if let Some(filter) = &self.filter {
+            conditions.push(format!("({})", filter.sql()));
+            params.extend(filter.params().iter().cloned());
+        }
+        if let Some(cursor) = &self.cursor {
+            if cursor.values.len() != keys.len() {
+                return Err(Error::Configuration(
+                    format!("cursor has {} keys but the query orders by {}", cursor.values.len(), keys.len()).into(),
+                ));
+            }
+            // (k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...; row-value comparison
+            // cannot express mixed ASC/DESC keys.
+            let mut branches = Vec::new();
+            for (i, (column, order)) in keys.iter().enumerate() {
+                let mut parts = Vec::new();
+                for (j, (prev, _)) in keys[..i].iter().enumerate() {
+                    parts.push(format!("{} = {}", db.quote_identifier(prev.name()), key_placeholder(db, prev)));
+                    params.push(BindValue::String(cursor.values[j].clone()));
+                }
+                let op = if *order == Order::Desc { "<" } else { ">" };
+                parts.push(format!("{} {} {}", db.quote_identifier(column.name()), op, key_placeholder(db, column)));
+                params.push(BindValue::String(cursor.values[i].clone()));
+                branches.push(format!("({})", parts.join(" AND ")));
+            }
+            conditions.push(format!("({})", branches.join(" OR ")));
+        }
+        let where_sql = if conditions.is_empty() {
+            "1=1".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+
+        let order_sql = keys
+            .iter()
+            .map(|(column, order)| format!("{} {}", db.quote_identifier(column.name()), order))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let template = format!(
+            "SELECT {}, {} FROM {} WHERE {} ORDER BY {} LIMIT {{}}",
+            columns,
+            cursor_columns,
+            db.quote_identifier(&self.scheme.table_name),
+            where_sql,
+            order_sql
+        );
+        let sql = get_or_insert_sql(format!("keyset-{}", template), || prepare_where(&template, 1));
+        Ok((sql, params))
+    }
+
+    fn finish<R>(&self, rows: Vec<R>) -> Result<KeysetPage<T>, Error>
+    where
+        R: Row,
+        T: for<'r> FromRow<'r, R>,
+        for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+        for<'a> &'a str: ColumnIndex<R>,
+    {
+        let limit = self.limit as usize;
+        let next_cursor = if rows.len() > limit {
+            Some(Cursor::from_row(&rows[limit - 1], self.key_columns().len())?)
+        } else {
+            None
+        };
+        let items = rows.iter().take(limit).map(T::from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(KeysetPage { items, next_cursor })
+    }
+}
+
+/// Placeholder for a cursor value of `column`. PostgreSQL needs the bound
+/// text cast back to the column's type, taken from the column rather than
+/// from the projected (possibly `cast_as`) result column; `build` rejects
+/// keys without one there.
+fn key_placeholder(db: Dialect, column: &Column) -> String {
+    match (db, column.sql_type()) {
+        (Dialect::PostgreSQL, Some(sql_type)) => format!("CAST({{}} AS {})", sql_type),
+        _ => "{}".to_string(),
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> Keyset<T, Postgres>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    /// Execute the query and return the page with the cursor for the next one.
+    pub async fn fetch(self, pool: &Pool<Postgres>) -> Result<KeysetPage<T>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        let rows = query.bind(self.limit as i64 + 1).fetch_all(pool).await?;
+        self.finish(rows)
+    }
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+impl<T> Keyset<T, MySql>
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    /// Execute the query and return the page with the cursor for the next one.
+    pub async fn fetch(self, pool: &Pool<MySql>) -> Result<KeysetPage<T>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        let rows = query.bind(self.limit + 1).fetch_all(pool).await?;
+        self.finish(rows)
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+impl<T> Keyset<T, Sqlite>
+where
+    T: for<'r> FromRow<'r, SqliteRow> + Send + Unpin,
+{
+    /// Execute the query and return the page with the cursor for the next one.
+    pub async fn fetch(self, pool: &Pool<Sqlite>) -> Result<KeysetPage<T>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        let rows = query.bind(self.limit as i64 + 1).fetch_all(pool).await?;
+        self.finish(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor {
+            values: vec!["2024-01-01 10:00:00+00".to_string(), "evt.42:ü".to_string()],
+        };
+        let encoded = cursor.encode();
+        assert!(encoded.chars().all(|c| c.is_ascii_hexdigit() || c == '.'));
+        assert_eq!(Cursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn test_cursor_rejects_malformed_input() {
+        assert_eq!(Cursor::decode(""), None);
+        assert_eq!(Cursor::decode("zz"), None);
+        assert_eq!(Cursor::decode("abc"), None);
+        assert_eq!(Cursor::decode("494e5438:31"), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! Pagination helpers for entity queries.
+//!
+//! Offset pagination returns a [`Page`] with the matching rows and totals:
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::EnhancedCrud;
+//!
+//! let page = User::paginate(User::STATUS.eq("active"), 2, 20)
+//!     .fetch(&pool)
+//!     .await?;
+//!
+//! println!("page {}/{} ({} users)", page.page, page.total_pages, page.total);
+//! for user in page.items { /* ... */ }
+//! ```
+//!
+//! Keyset pagination walks large tables with an opaque cursor instead of an
+//! OFFSET, so every page costs the same:
+//!
+//! ```ignore
+//! let page = Event::after(None)
+//!     .order_by(Event::CREATED_AT, Order::Desc)
+//!     .limit(500)
+//!     .fetch(&pool)
+//!     .await?;
+//!
+//! if let Some(cursor) = page.next_cursor {
+//!     let token = cursor.encode(); // hand to the client, decode with Cursor::decode
+//!     let next = Event::after(cursor).order_by(Event::CREATED_AT, Order::Desc).limit(500);
+//! }
+//! ```
+//!
+//! Batch jobs can walk every row in primary-key ranges, and resume from the
+//! last key they saved:
+//!
+//! ```ignore
+//! User::chunked(1000)
+//!     .filter(User::STATUS.eq("active"))
+//!     .resume_after(last_processed_id)
+//!     .for_each_chunk(&pool, |batch| async move { reindex(&batch).await })
+//!     .await?;
+//!
+//! let mut users = User::chunked(1000).stream(&pool);
+//! while let Some(user) = users.try_next().await? { /* ... */ }
+//! ```
+
+mod chunked;
+mod keyset;
+mod offset;
+
+pub use chunked::Chunked;
+pub use keyset::{Cursor, Keyset, KeysetPage};
+pub use offset::{Page, Paginate};
+
+/// Column alias carrying the window-function total in paginated queries.
+pub(crate) const TOTAL_COLUMN: &str = "__total";
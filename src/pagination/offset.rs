@@ -0,0 +1,351 @@
+//! Offset (page number) pagination.
+
+use sqlx::{Acquire, Database, Error, FromRow, Row};
+use std::marker::PhantomData;
+
+use super::TOTAL_COLUMN;
+use crate::filter::IntoWhere;
+use crate::proxy::{BindProxy, BindValue};
+use crate::Scheme;
+
+#[cfg(feature = "postgres")]
+use sqlx::{postgres::PgRow, Postgres};
+
+#[cfg(feature = "mysql")]
+use sqlx::{mysql::MySqlRow, MySql};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{sqlite::SqliteRow, Sqlite};
+
+/// One page of results together with the totals for the whole result set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of rows matching the filter across all pages.
+    pub total: u64,
+    /// 1-based page number.
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
+
+impl<T> Page<T> {
+    /// Builds a page, deriving `total_pages` from `total` and `per_page`.
+    pub fn new(items: Vec<T>, total: u64, page: u64, per_page: u64) -> Self {
+        let total_pages = if per_page == 0 { 0 } else { total.div_ceil(per_page) };
+        Self { items, total, page, per_page, total_pages }
+    }
+
+    /// Returns true if there is a page after this one.
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// Returns true if there is a page before this one.
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+}
+
+/// Offset pagination query created by `EnhancedCrud::paginate`.
+///
+/// Rows are ordered by the primary key. The page and the total count share
+/// the same WHERE clause and binds; where the database supports window
+/// functions both come back from a single `COUNT(*) OVER()` query.
+///
+/// A string filter's "{}" placeholders are bound with
+/// [`bind_proxy`](Self::bind_proxy):
+///
+/// ```ignore
+/// let page = User::paginate("status = {} AND age >= {}", 1, 20)
+///     .bind_proxy("active")
+///     .bind_proxy(18)
+///     .fetch(&pool)
+///     .await?;
+/// ```
+pub struct Paginate<T, DB: Database> {
+    scheme: &'static Scheme,
+    where_clause: String,
+    where_params: Vec<BindValue<DB>>,
+    select: Option<(&'static str, String)>,
+    /// Why the select builder this came from cannot be paginated, reported
+    /// by `fetch`
+    problem: Option<String>,
+    page: u64,
+    per_page: u64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, DB: Database> Paginate<T, DB> {
+    /// Creates a pagination query. `page` is 1-based; `0` is treated as `1`,
+    /// and `per_page` is at least `1`.
+    pub fn new<W: IntoWhere<DB>>(scheme: &'static Scheme, filter: W, page: u64, per_page: u64) -> Self {
+        let (where_clause, where_params) = filter.into_where();
+        Self {
+            scheme,
+            where_clause,
+            where_params,
+            select: None,
+            problem: None,
+            page: page.max(1),
+            per_page: per_page.max(1),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Binds the next "{}" placeholder of a string filter with its native
+    /// type, after the filter expression's own values.
+    pub fn bind_proxy<V: BindProxy<DB>>(mut self, value: V) -> Self {
+        self.where_params.push(value.into_bind_value());
+        self
+    }
+
+    /// The filter binds, in placeholder order. LIMIT and OFFSET are bound
+    /// after them.
+    pub fn params(&self) -> &[BindValue<DB>] {
+        &self.where_params
+    }
+
+    /// Replaces the full column list and primary-key ordering with the
+    /// rendered columns and ORDER BY list of a select builder.
+    pub(crate) fn with_select(mut self, columns: &'static str, order_by: String) -> Self {
+        self.select = Some((columns, order_by));
+        self
+    }
+
+    /// Makes `fetch` fail with `problem`, for select builders using parts
+    /// that pagination cannot apply.
+    pub(crate) fn with_problem(mut self, problem: String) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+
+    /// The SQL used to fetch the page (LIMIT and OFFSET are the last two binds).
+    pub fn sql(&self) -> &'static str {
+        match &self.select {
+            Some((columns, order_by)) => {
+                self.scheme.gen_paginate_ordered_sql_static(&self.where_clause, columns, order_by, Self::WINDOW_TOTAL)
+            }
+            None => self.scheme.gen_paginate_sql_static(&self.where_clause, Self::WINDOW_TOTAL),
+        }
+    }
+
+    /// Fails if the select builder can't be paginated, or if the filter's
+    /// placeholders and binds don't line up, which would otherwise shift
+    /// LIMIT and OFFSET into the filter.
+    fn check(&self) -> Result<(), Error> {
+        if let Some(problem) = &self.problem {
+            return Err(Error::Configuration(format!("paginate: {}", problem).into()));
+        }
+        let placeholders = self.where_clause.matches("{}").count();
+        if placeholders != self.where_params.len() {
+            return Err(Error::Configuration(
+                format!(
+                    "paginate filter has {} placeholders but {} bound values; bind them with bind_proxy",
+                    placeholders,
+                    self.where_params.len()
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rows skipped before the page, saturating at the largest OFFSET the
+    /// databases accept.
+    fn offset(&self) -> u64 {
+        (self.page - 1).saturating_mul(self.per_page).min(i64::MAX as u64)
+    }
+
+    // MySQL 5.7 has no window functions, so the total needs its own COUNT query.
+    const WINDOW_TOTAL: bool = cfg!(not(feature = "mysql_5_7"));
+}
+
+#[cfg(feature = "postgres")]
+impl<T> Paginate<T, Postgres>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    /// Execute the query and return the requested page.
+    ///
+    /// Takes a pool, a connection or a transaction; the page and, past the
+    /// last page, the total count run on the same connection.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// let page = User::paginate(User::STATUS.eq("active"), 2, 20).fetch(&mut *tx).await?;
+    /// ```
+    pub async fn fetch<'a, A>(self, conn: A) -> Result<Page<T>, Error>
+    where
+        A: Acquire<'a, Database = Postgres>,
+    {
+        self.check()?;
+        let mut conn = conn.acquire().await?;
+        let offset = self.offset();
+        let mut query = sqlx::query(self.sql());
+        for param in self.where_params.iter().cloned() {
+            query = param.bind_to(query);
+        }
+        let rows = query
+            .bind(self.per_page as i64)
+            .bind(offset as i64)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let total = match rows.first() {
+            Some(row) => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if offset == 0 => 0,
+            // Past the last page: no row carries the window total
+            None => {
+                let mut count = sqlx::query_as::<_, (i64,)>(self.scheme.gen_count_sql_static(&self.where_clause));
+                for param in self.where_params.iter().cloned() {
+                    count = param.bind_to(count);
+                }
+                count.fetch_one(&mut *conn).await?.0
+            }
+        };
+
+        let items = rows.iter().map(T::from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+impl<T> Paginate<T, MySql>
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    /// Execute the query and return the requested page.
+    ///
+    /// Takes a pool, a connection or a transaction; the page and, past the
+    /// last page, the total count run on the same connection.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// let page = User::paginate(User::STATUS.eq("active"), 2, 20).fetch(&mut *tx).await?;
+    /// ```
+    pub async fn fetch<'a, A>(self, conn: A) -> Result<Page<T>, Error>
+    where
+        A: Acquire<'a, Database = MySql>,
+    {
+        self.check()?;
+        let mut conn = conn.acquire().await?;
+        let offset = self.offset();
+        let mut query = sqlx::query(self.sql());
+        for param in self.where_params.iter().cloned() {
+            query = param.bind_to(query);
+        }
+        let rows = query
+            .bind(self.per_page)
+            .bind(offset)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let total = match rows.first() {
+            Some(row) if Self::WINDOW_TOTAL => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if offset == 0 => 0,
+            _ => {
+                let mut count = sqlx::query_as::<_, (i64,)>(self.scheme.gen_count_sql_static(&self.where_clause));
+                for param in self.where_params.iter().cloned() {
+                    count = param.bind_to(count);
+                }
+                count.fetch_one(&mut *conn).await?.0
+            }
+        };
+
+        let items = rows.iter().map(T::from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+impl<T> Paginate<T, Sqlite>
+where
+    T: for<'r> FromRow<'r, SqliteRow> + Send + Unpin,
+{
+    /// Execute the query and return the requested page.
+    ///
+    /// Takes a pool, a connection or a transaction; the page and, past the
+    /// last page, the total count run on the same connection.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// let page = User::paginate(User::STATUS.eq("active"), 2, 20).fetch(&mut *tx).await?;
+    /// ```
+    pub async fn fetch<'a, A>(self, conn: A) -> Result<Page<T>, Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        self.check()?;
+        let mut conn = conn.acquire().await?;
+        let offset = self.offset();
+        let mut query = sqlx::query(self.sql());
+        for param in self.where_params.iter().cloned() {
+            query = param.bind_to(query);
+        }
+        let rows = query
+            .bind(self.per_page as i64)
+            .bind(offset as i64)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let total = match rows.first() {
+            Some(row) => row.try_get::<i64, _>(TOTAL_COLUMN)?,
+            None if offset == 0 => 0,
+            // Past the last page: no row carries the window total
+            None => {
+                let mut count = sqlx::query_as::<_, (i64,)>(self.scheme.gen_count_sql_static(&self.where_clause));
+                for param in self.where_params.iter().cloned() {
+                    count = param.bind_to(count);
+                }
+                count.fetch_one(&mut *conn).await?.0
+            }
+        };
+
+        let items = rows.iter().map(T::from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, total as u64, self.page, self.per_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_totals() {
+        let page = Page::new(vec![1, 2, 3], 23, 1, 10);
+        assert_eq!(page.total_pages, 3);
+        assert!(page.has_next());
+        assert!(!page.has_prev());
+
+        let last = Page::new(vec![21, 22, 23], 23, 3, 10);
+        assert!(!last.has_next());
+        assert!(last.has_prev());
+
+        let empty: Page<i32> = Page::new(Vec::new(), 0, 1, 10);
+        assert_eq!(empty.total_pages, 0);
+        assert!(!empty.has_next());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_offset_saturates() {
+        let scheme: &'static Scheme = Box::leak(Box::new(Scheme {
+            table_name: "users".to_string(),
+            insert_fields: vec!["id".to_string()],
+            update_fields: vec![],
+            id_field: "id".to_string(),
+            column_definitions: vec![],
+        }));
+        let offset = |page, per_page| Paginate::<(), Postgres>::new(scheme, "1=1", page, per_page).offset();
+        assert_eq!(offset(3, 20), 40);
+        assert_eq!(offset(u64::MAX, 20), i64::MAX as u64);
+        assert_eq!(offset(u64::MAX / 2, 4), i64::MAX as u64);
+    }
+}
@@ -3,13 +3,15 @@
 // This module defines the type conversion interface and the enum that holds
 // converted values ready for binding to database queries.
 
-use sqlx::Database;
+use sqlx::database::HasArguments;
+use sqlx::query::{Query, QueryAs, QueryScalar};
+use sqlx::{Database, Encode, Type};
 use std::marker::PhantomData;
 
 /// Values that can be bound to database queries with automatic type conversion.
 ///
 /// This enum wraps different types and converts them to database-compatible values.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum BindValue<DB: Database> {
     // Existing types (unchanged for backward compatibility)
     String(String),
@@ -76,6 +78,58 @@ impl<DB: Database> BindValue<DB> {
     }
 }
 
+// Implemented by hand: deriving would require `DB: Clone`, which the sqlx
+// database marker types (e.g. `Postgres`) do not implement.
+impl<DB: Database> Clone for BindValue<DB> {
+    fn clone(&self) -> Self {
+        match self {
+            BindValue::String(s) => BindValue::String(s.clone()),
+            BindValue::I32(i) => BindValue::I32(*i),
+            BindValue::I64(i) => BindValue::I64(*i),
+            BindValue::F64(f) => BindValue::F64(*f),
+            BindValue::Bool(b) => BindValue::Bool(*b),
+            BindValue::Decimal(s) => BindValue::Decimal(s.clone()),
+            BindValue::I8(i) => BindValue::I8(*i),
+            BindValue::I16(i) => BindValue::I16(*i),
+            BindValue::F32(f) => BindValue::F32(*f),
+            BindValue::NaiveDate(s) => BindValue::NaiveDate(s.clone()),
+            BindValue::NaiveTime(s) => BindValue::NaiveTime(s.clone()),
+            BindValue::NaiveDateTime(s) => BindValue::NaiveDateTime(s.clone()),
+            BindValue::DateTimeUtc(s) => BindValue::DateTimeUtc(s.clone()),
+            BindValue::Json(s) => BindValue::Json(s.clone()),
+            BindValue::Binary(bytes) => BindValue::Binary(bytes.clone()),
+            BindValue::Uuid(s) => BindValue::Uuid(s.clone()),
+            BindValue::_Marker(_) => BindValue::_Marker(PhantomData),
+        }
+    }
+}
+
+/// SQLx query types that a [`BindValue`] can be bound onto.
+///
+/// Implemented for `Query`, `QueryAs` and `QueryScalar` so that the per-database
+/// `BindValue::bind_to` helpers work with every statement the CRUD trait returns.
+pub trait BindTarget<'q, DB: Database>: Sized {
+    fn bind_value<T: 'q + Send + Encode<'q, DB> + Type<DB>>(self, value: T) -> Self;
+}
+
+impl<'q, DB: Database> BindTarget<'q, DB> for Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
+    fn bind_value<T: 'q + Send + Encode<'q, DB> + Type<DB>>(self, value: T) -> Self {
+        self.bind(value)
+    }
+}
+
+impl<'q, DB: Database, O> BindTarget<'q, DB> for QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
+    fn bind_value<T: 'q + Send + Encode<'q, DB> + Type<DB>>(self, value: T) -> Self {
+        self.bind(value)
+    }
+}
+
+impl<'q, DB: Database, O> BindTarget<'q, DB> for QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
+    fn bind_value<T: 'q + Send + Encode<'q, DB> + Type<DB>>(self, value: T) -> Self {
+        self.bind(value)
+    }
+}
+
 /// Trait for types that can be converted to bind values with automatic type conversion.
 ///
 /// Implement this trait for custom types to enable automatic conversion when using
@@ -96,6 +150,21 @@ pub trait BindProxy<DB: Database> {
     fn into_bind_value(self) -> BindValue<DB>;
 }
 
+/// Object-safe counterpart of [`BindProxy`].
+///
+/// Lets parameters of different types share one slice, e.g.
+/// `&[&"active", &1000i64, &since]`. Every `Clone` type that implements
+/// `BindProxy` gets this for free.
+pub trait BindParam<DB: Database> {
+    fn to_bind_value(&self) -> BindValue<DB>;
+}
+
+impl<DB: Database, T: BindProxy<DB> + Clone> BindParam<DB> for T {
+    fn to_bind_value(&self) -> BindValue<DB> {
+        self.clone().into_bind_value()
+    }
+}
+
 // ============================================================================
 // Implement BindProxy for basic types (generic across all databases)
 // ============================================================================
@@ -130,7 +199,19 @@ impl<DB: Database> BindProxy<DB> for bool {
     }
 }
 
+impl<DB: Database> BindProxy<DB> for BindValue<DB> {
+    fn into_bind_value(self) -> BindValue<DB> {
+        self
+    }
+}
+
 // Reference implementations
+impl<DB: Database> BindProxy<DB> for &String {
+    fn into_bind_value(self) -> BindValue<DB> {
+        BindValue::String(self.clone())
+    }
+}
+
 impl<'a, DB: Database> BindProxy<DB> for &'a str {
     fn into_bind_value(self) -> BindValue<DB> {
         BindValue::String(self.to_string())
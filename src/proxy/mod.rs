@@ -27,5 +27,5 @@ mod sqlite;
 pub use sqlite::EnhancedQueryAsSqlite;
 
 // Re-export common types
-pub use bind::{BindProxy, BindValue};
+pub use bind::{BindParam, BindProxy, BindTarget, BindValue};
 pub use r#trait::EnhancedQuery;
@@ -9,7 +9,7 @@ use sqlx::database::HasArguments;
 use sqlx::sqlite::SqliteRow;
 use std::future::Future;
 
-use crate::proxy::{BindProxy, BindValue, EnhancedQuery};
+use crate::proxy::{BindProxy, BindTarget, BindValue, EnhancedQuery};
 
 /// Enhanced query wrapper for SQLite SELECT queries with automatic type conversion.
 ///
@@ -249,3 +249,35 @@ where
         }
     }
 }
+
+// ============================================================================
+// Binding converted values onto plain SQLx queries
+// ============================================================================
+
+impl BindValue<Sqlite> {
+    /// Bind this value onto a `Query`, `QueryAs` or `QueryScalar`.
+    ///
+    /// Used by the filter DSL and the query builders to apply collected
+    /// parameters in order, using the same conversions as `bind_proxy`.
+    pub fn bind_to<'q, Q: BindTarget<'q, Sqlite>>(self, query: Q) -> Q {
+        match self {
+            BindValue::String(s) => query.bind_value(s),
+            BindValue::I32(i) => query.bind_value(i),
+            BindValue::I64(i) => query.bind_value(i),
+            BindValue::F64(f) => query.bind_value(f),
+            BindValue::Bool(b) => query.bind_value(b),
+            BindValue::Decimal(s) => query.bind_value(s),
+            BindValue::I8(i) => query.bind_value(i),
+            BindValue::I16(i) => query.bind_value(i),
+            BindValue::F32(f) => query.bind_value(f),
+            BindValue::NaiveDate(s) => query.bind_value(s),
+            BindValue::NaiveTime(s) => query.bind_value(s),
+            BindValue::NaiveDateTime(s) => query.bind_value(s),
+            BindValue::DateTimeUtc(s) => query.bind_value(s),
+            BindValue::Json(s) => query.bind_value(s),
+            BindValue::Binary(bytes) => query.bind_value(bytes),
+            BindValue::Uuid(s) => query.bind_value(s),
+            BindValue::_Marker(_) => panic!("BindValue::_Marker should never be used"),
+        }
+    }
+}
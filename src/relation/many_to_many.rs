@@ -0,0 +1,139 @@
+//! SQL for many-to-many relations stored in a join ("through") table.
+
+use crate::{get_or_insert_sql, Dialect, Scheme};
+
+/// Join table of a `#[crud(many_to_many(..))]` relation.
+///
+/// `left` references the declaring entity, `right` the related one: for
+/// `Post.tags` through `post_tags`, `left = "post_id"` and `right = "tag_id"`.
+#[derive(Debug, Clone, Copy)]
+pub struct Through {
+    pub table: &'static str,
+    pub left: &'static str,
+    pub right: &'static str,
+}
+
+/// Join rows changed by a `sync_*` call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncResult {
+    /// Join rows inserted
+    pub attached: u64,
+    /// Join rows deleted
+    pub detached: u64,
+}
+
+impl Through {
+    pub const fn new(table: &'static str, left: &'static str, right: &'static str) -> Self {
+        Self { table, left, right }
+    }
+
+    /// Inserts `count` (left, right) pairs, ignoring pairs that already exist.
+    ///
+    /// Duplicates are only detected through a unique constraint on
+    /// `(left, right)`: `ON CONFLICT DO NOTHING` on PostgreSQL and SQLite,
+    /// `INSERT IGNORE` on MySQL.
+    pub fn gen_attach_sql_static(&self, count: usize) -> &'static str {
+        let key = format!("{}-through-attach-{}-{}-{}", self.table, self.left, self.right, count);
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            let values = (0..count)
+                .map(|i| {
+                    format!(
+                        "({},{})",
+                        db.placeholder(i * 2 + 1),
+                        db.placeholder(i * 2 + 2)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            let insert = format!(
+                "INSERT INTO {} ({},{}) VALUES {}",
+                db.quote_identifier(self.table),
+                db.quote_identifier(self.left),
+                db.quote_identifier(self.right),
+                values
+            );
+            db.upsert(&insert, &[], &[])
+        })
+    }
+
+    /// Deletes the join rows of one left key and `count` right keys.
+    pub fn gen_detach_sql_static(&self, count: usize) -> &'static str {
+        let key = format!("{}-through-detach-{}-{}-{}", self.table, self.left, self.right, count);
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            let params = (2..=count + 1)
+                .map(|i| db.placeholder(i))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                "DELETE FROM {} WHERE {}={} AND {} IN ({})",
+                db.quote_identifier(self.table),
+                db.quote_identifier(self.left),
+                db.placeholder(1),
+                db.quote_identifier(self.right),
+                params
+            )
+        })
+    }
+
+    /// Selects the right keys currently attached to one left key.
+    pub fn gen_select_right_keys_sql_static(&self) -> &'static str {
+        let key = format!("{}-through-keys-{}-{}", self.table, self.left, self.right);
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            format!(
+                "SELECT {} FROM {} WHERE {}={}",
+                db.quote_identifier(self.right),
+                db.quote_identifier(self.table),
+                db.quote_identifier(self.left),
+                db.placeholder(1)
+            )
+        })
+    }
+
+    /// Selects the related rows of `count` left keys in one query.
+    ///
+    /// The join rows are read through a derived table exposing only
+    /// `__left` and `__right`, so the target's columns stay unambiguous. Each
+    /// result row carries the left key it belongs to in `__left`.
+    pub fn gen_preload_sql_static(&self, target: &Scheme, count: usize) -> &'static str {
+        // IMPORTANT: Call gen_select_columns_static() BEFORE acquiring the lock
+        // to avoid deadlock since it also accesses SQL_CACHE
+        let columns = target.gen_select_columns_static();
+        let key = format!(
+            "{}-through-preload-{}-{}-{}-{}",
+            self.table, self.left, self.right, target.table_name, count
+        );
+        get_or_insert_sql(key, || {
+            let db = Dialect::current();
+            let params = (1..=count)
+                .map(|i| db.placeholder(i))
+                .collect::<Vec<_>>()
+                .join(",");
+            let through = db.quote_identifier("__through");
+            let left = db.quote_identifier("__left");
+            let right = db.quote_identifier("__right");
+            let quoted_target = db.quote_identifier(&target.table_name);
+            format!(
+                "SELECT {}, {}.{} FROM {} INNER JOIN (SELECT {} AS {}, {} AS {} FROM {} WHERE {} IN ({})) AS {} ON {}.{}={}.{}",
+                columns,
+                through,
+                left,
+                quoted_target,
+                db.quote_identifier(self.left),
+                left,
+                db.quote_identifier(self.right),
+                right,
+                db.quote_identifier(self.table),
+                db.quote_identifier(self.left),
+                params,
+                through,
+                quoted_target,
+                db.quote_identifier(&target.id_field),
+                through,
+                right
+            )
+        })
+    }
+}
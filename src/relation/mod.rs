@@ -0,0 +1,83 @@
+//! Relations between entities and batch (eager) loading.
+//!
+//! Relations are declared on a field holding the related rows. The field is
+//! not a column, so it also needs `#[sqlx(skip)]`:
+//!
+//! ```ignore
+//! #[derive(Clone, FromRow, EnhancedCrud)]
+//! struct Order {
+//!     id: String,
+//!     customer_id: String,
+//!     #[sqlx(skip)]
+//!     #[crud(belongs_to(Customer, fk = "customer_id"))]
+//!     customer: Option<Customer>,
+//!     #[sqlx(skip)]
+//!     #[crud(has_many(OrderLine, fk = "order_id"))]
+//!     lines: Vec<OrderLine>,
+//! }
+//!
+//! let mut orders = Order::where_query("status = {}").bind("open").fetch_all(&pool).await?;
+//!
+//! // One IN query per relation (per chunk of keys) instead of one query per order
+//! Order::preload_customers(&mut orders, &pool).await?;
+//! Order::preload_lines(&mut orders, &pool).await?;
+//!
+//! // Or a single row
+//! order.load_customer(&pool).await?;
+//! ```
+//!
+//! `belongs_to` targets must implement `Clone`, since several rows can share
+//! the same parent.
+//!
+//! Many-to-many relations go through a join table:
+//!
+//! ```ignore
+//! #[sqlx(skip)]
+//! #[crud(many_to_many(Tag, through = "post_tags", left = "post_id", right = "tag_id"))]
+//! tags: Vec<Tag>,
+//!
+//! post.attach_tags(&pool, &[rust_id.clone()]).await?;
+//! post.detach_tags(&pool, &[old_id]).await?;
+//! post.sync_tags(&pool, &[rust_id, sql_id]).await?; // diff in one transaction
+//! Post::preload_tags(&mut posts, &pool).await?;
+//! ```
+
+mod many_to_many;
+
+pub use many_to_many::{SyncResult, Through};
+
+use crate::Scheme;
+
+/// Scheme and primary-key access for a derived entity, used to stitch
+/// related rows together. Implemented by `#[derive(EnhancedCrud)]`.
+pub trait Entity {
+    /// Type of the primary-key field.
+    type Key;
+
+    /// The table metadata of this entity.
+    fn scheme() -> &'static Scheme;
+
+    /// The primary-key value of this row.
+    fn primary_key(&self) -> &Self::Key;
+}
+
+/// A foreign-key field, either required (`K`) or nullable (`Option<K>`).
+///
+/// Lets the generated relation loaders treat both the same way; rows with a
+/// NULL key are never matched.
+pub trait ForeignKey<K> {
+    /// The referenced key, or `None` for a NULL foreign key.
+    fn key(&self) -> Option<&K>;
+}
+
+impl<K> ForeignKey<K> for K {
+    fn key(&self) -> Option<&K> {
+        Some(self)
+    }
+}
+
+impl<K> ForeignKey<K> for Option<K> {
+    fn key(&self) -> Option<&K> {
+        self.as_ref()
+    }
+}
@@ -0,0 +1,60 @@
+//! Fluent SELECT queries for entities.
+//!
+//! `EnhancedCrud::select()` keeps ordering, limits and row locks out of the
+//! WHERE fragment, so the cached SQL is keyed by the query's structure rather
+//! than by hand-written predicate strings:
+//!
+//! ```ignore
+//! use sqlx_struct_enhanced::{EnhancedCrud, Nulls, Order};
+//!
+//! let users = User::select()
+//!     .filter(User::STATUS.eq("active"))
+//!     .order_by(User::LAST_LOGIN, Order::Desc)
+//!     .order_by_nulls(User::NICKNAME, Order::Asc, Nulls::Last)
+//!     .limit(10)
+//!     .fetch_all(&pool)
+//!     .await?;
+//!
+//! // Work-queue style claiming inside a transaction
+//! let jobs = Job::select()
+//!     .filter(Job::STATE.eq("pending"))
+//!     .order_by(Job::ID, Order::Asc)
+//!     .limit(5)
+//!     .skip_locked()
+//!     .fetch_all(&mut *tx)
+//!     .await?;
+//! ```
+//!
+//! `select_as` narrows the column list to a [`Projection`] struct.
+//!
+//! `union`, `union_all`, `intersect` and `except` combine queries over
+//! entities with the same columns; ordering and limits apply to the result:
+//!
+//! ```ignore
+//! let jobs = Job::select()
+//!     .union_all(ArchivedJob::select())
+//!     .order_by(Job::ID, Order::Desc)
+//!     .limit(50)
+//!     .fetch_all(&pool)
+//!     .await?;
+//! ```
+//!
+//! Entities marked `#[crud(tree(parent = "parent_id"))]` get `descendants(id)`
+//! and `ancestors(id)`, walking the tree with a recursive CTE:
+//!
+//! ```ignore
+//! #[derive(FromRow, EnhancedCrud)]
+//! #[crud(tree(parent = "parent_id"))]
+//! struct Category { id: String, parent_id: Option<String>, name: String }
+//!
+//! let subtree = Category::descendants(&root.id).order_by(Category::NAME, Order::Asc).fetch_all(&pool).await?;
+//! let breadcrumbs = Category::ancestors(&leaf.id).fetch_all(&pool).await?;
+//! ```
+
+mod projection;
+mod query_builder;
+mod tree;
+
+pub use projection::Projection;
+pub use query_builder::SelectQueryBuilder;
+pub use tree::TreeWalk;
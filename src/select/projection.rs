@@ -0,0 +1,27 @@
+//! Partial DTOs selecting a subset of an entity's columns.
+
+/// A struct holding a subset of an entity's columns, generated by
+/// `#[derive(Projection)]`.
+///
+/// ```ignore
+/// #[derive(FromRow, Projection)]
+/// #[projection(User)]
+/// struct UserSummary {
+///     id: String,
+///     name: String,
+/// }
+///
+/// let summaries = User::select_as::<UserSummary>()
+///     .order_by(User::NAME, Order::Asc)
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+///
+/// The derive fails to compile if a field is not a column of the entity.
+pub trait Projection {
+    /// The entity whose table is queried.
+    type Entity;
+
+    /// The projected column names, in field order.
+    const COLUMNS: &'static [&'static str];
+}
@@ -0,0 +1,711 @@
+//! SELECT query builder with ordering, limits, DISTINCT, row locking and set
+//! operations.
+
+use sqlx::{Database, Error, Executor, FromRow};
+use std::marker::PhantomData;
+
+use crate::filter::{order_by_sql, Column, Expr, IntoSubquery, Nulls, Order, Subquery, With};
+use crate::pagination::Paginate;
+use crate::proxy::BindValue;
+use crate::{get_or_insert_sql, prepare_where, Dialect, Scheme};
+
+#[cfg(feature = "postgres")]
+use sqlx::{postgres::PgRow, Postgres};
+
+#[cfg(feature = "mysql")]
+use sqlx::{mysql::MySqlRow, MySql};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{sqlite::SqliteRow, Sqlite};
+
+/// Row lock requested with `for_update` / `skip_locked`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lock {
+    ForUpdate,
+    SkipLocked,
+}
+
+/// A query appended with UNION, INTERSECT or EXCEPT.
+struct Compound<DB: Database> {
+    op: &'static str,
+    columns: String,
+    sql: String,
+    params: Vec<BindValue<DB>>,
+    /// Why the query cannot be combined, reported by `build`
+    problem: Option<String>,
+}
+
+/// SELECT query builder created by `EnhancedCrud::select`.
+///
+/// Selects every column of the entity (honouring `cast_as`). LIMIT and OFFSET
+/// are bound as parameters, so queries differing only in those values share
+/// one cached SQL string.
+pub struct SelectQueryBuilder<T, DB: Database> {
+    scheme: &'static Scheme,
+    columns: Option<&'static [&'static str]>,
+    selected: Vec<Column>,
+    with: With<DB>,
+    /// FROM item replacing the table, and its binds
+    source: Option<(String, Vec<BindValue<DB>>)>,
+    /// Why the FROM subquery is invalid, reported by `build`
+    source_problem: Option<String>,
+    filter: Option<Expr<DB>>,
+    order: Vec<(Column, Order, Option<Nulls>)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    distinct: bool,
+    lock: Option<Lock>,
+    compounds: Vec<Compound<DB>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, DB: Database> SelectQueryBuilder<T, DB> {
+    /// Creates a query selecting all rows of the entity's table.
+    pub fn new(scheme: &'static Scheme) -> Self {
+        Self {
+            scheme,
+            columns: None,
+            selected: Vec::new(),
+            with: With::default(),
+            source: None,
+            source_problem: None,
+            filter: None,
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            distinct: false,
+            lock: None,
+            compounds: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a query selecting only `columns`, used by `EnhancedCrud::select_as`.
+    pub fn projected(scheme: &'static Scheme, columns: &'static [&'static str]) -> Self {
+        Self { columns: Some(columns), ..Self::new(scheme) }
+    }
+
+    /// Selects `column` instead of the entity's columns. Repeated calls add
+    /// columns.
+    ///
+    /// Meant for subqueries, e.g. the single column of
+    /// [`Column::in_subquery`]; rows no longer decode into the entity.
+    pub fn select_column(mut self, column: Column) -> Self {
+        self.selected.push(column);
+        self
+    }
+
+    /// Reads the rows from `query` instead of the entity's table.
+    ///
+    /// The subquery is aliased as the table, so it must select the entity's
+    /// columns and qualified columns keep working. Such a query cannot be
+    /// paginated: `paginate(..).fetch` returns `Error::Configuration`.
+    ///
+    /// ```ignore
+    /// // The 100 most recent sign-ups, by name
+    /// let recent = User::select().order_by(User::CREATED_AT, Order::Desc).limit(100);
+    /// let users = User::select()
+    ///     .from_subquery(recent)
+    ///     .order_by(User::NAME, Order::Asc)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn from_subquery<Q: IntoSubquery<DB>>(mut self, query: Q) -> Self {
+        let query = query.into_subquery();
+        self.source_problem = query.problem().map(str::to_string);
+        let (sql, params) = query.into_parts();
+        let table = Dialect::current().quote_identifier(&self.scheme.table_name);
+        self.source = Some((format!("({}) AS {}", sql, table), params));
+        self
+    }
+
+    /// Adds a common table expression `name AS (query)` to the WITH clause.
+    ///
+    /// ```ignore
+    /// // The paid ones among the 100 latest sales
+    /// let sales = Sale::select()
+    ///     .with("recent", Sale::select().order_by(Sale::CREATED_AT, Order::Desc).limit(100))
+    ///     .from_cte("recent")
+    ///     .filter(Sale::STATUS.eq("paid"))
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn with<Q: IntoSubquery<DB>>(mut self, name: &str, query: Q) -> Self {
+        self.with.push(name, query.into_subquery());
+        self
+    }
+
+    /// Adds a recursive common table expression `name AS (anchor UNION step)`,
+    /// where `step` joins the table to `name`. Rows already produced are not
+    /// expanded again, so cycles end the recursion.
+    ///
+    /// The generated `descendants` and `ancestors` of `#[crud(tree(..))]`
+    /// entities are built on this.
+    pub fn with_recursive<A, S>(mut self, name: &str, anchor: A, step: S) -> Self
+    where
+        A: IntoSubquery<DB>,
+        S: IntoSubquery<DB>,
+    {
+        self.with.push_recursive(name, anchor.into_subquery(), step.into_subquery());
+        self
+    }
+
+    /// Reads the rows from the common table expression `name` instead of the
+    /// entity's table. Like [`from_subquery`](Self::from_subquery), the CTE
+    /// is aliased as the table and must have the entity's columns.
+    pub fn from_cte(mut self, name: &str) -> Self {
+        let db = Dialect::current();
+        self.source = Some((
+            format!("{} AS {}", db.quote_identifier(name), db.quote_identifier(&self.scheme.table_name)),
+            Vec::new(),
+        ));
+        self
+    }
+
+    /// Restricts the selected rows. Repeated calls are combined with AND.
+    pub fn filter(mut self, expr: Expr<DB>) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => existing.and(expr),
+            None => expr,
+        });
+        self
+    }
+
+    /// Adds a sort key using the database's default NULL placement.
+    pub fn order_by(mut self, column: Column, order: Order) -> Self {
+        self.order.push((column, order, None));
+        self
+    }
+
+    /// Adds a sort key with explicit NULL placement.
+    pub fn order_by_nulls(mut self, column: Column, order: Order, nulls: Nulls) -> Self {
+        self.order.push((column, order, Some(nulls)));
+        self
+    }
+
+    /// Returns at most `n` rows.
+    pub fn limit(mut self, n: u64) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Skips the first `n` rows.
+    pub fn offset(mut self, n: u64) -> Self {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Selects only distinct rows.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Locks the selected rows until the end of the transaction (`FOR UPDATE`).
+    ///
+    /// SQLite has no row locks and ignores this; use `BEGIN IMMEDIATE` there.
+    pub fn for_update(mut self) -> Self {
+        if self.lock.is_none() {
+            self.lock = Some(Lock::ForUpdate);
+        }
+        self
+    }
+
+    /// Locks the selected rows, skipping rows already locked by other
+    /// transactions (`FOR UPDATE SKIP LOCKED`). Implies `for_update`.
+    ///
+    /// Not available on MySQL 5.7.
+    pub fn skip_locked(mut self) -> Self {
+        self.lock = Some(Lock::SkipLocked);
+        self
+    }
+
+    /// Appends `other` with UNION, dropping duplicate rows.
+    ///
+    /// `other` may select another entity whose columns match this one's,
+    /// e.g. an archive table with the same layout. ORDER BY, LIMIT and OFFSET
+    /// set on this builder apply to the combined rows, so sort keys must be
+    /// unqualified columns; `other` must not have its own:
+    ///
+    /// ```ignore
+    /// let sales = Sale::select()
+    ///     .filter(Sale::CUSTOMER_ID.eq(&customer_id))
+    ///     .union_all(ArchivedSale::select().filter(ArchivedSale::CUSTOMER_ID.eq(&customer_id)))
+    ///     .order_by(Sale::CREATED_AT, Order::Desc)
+    ///     .limit(20)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    pub fn union<U>(self, other: SelectQueryBuilder<U, DB>) -> Self {
+        self.compound("UNION", other)
+    }
+
+    /// Appends `other` with UNION ALL, keeping duplicate rows. See
+    /// [`union`](Self::union).
+    pub fn union_all<U>(self, other: SelectQueryBuilder<U, DB>) -> Self {
+        self.compound("UNION ALL", other)
+    }
+
+    /// Keeps only the rows also selected by `other`. See [`union`](Self::union).
+    ///
+    /// Requires MySQL 8.0.31 or later.
+    pub fn intersect<U>(self, other: SelectQueryBuilder<U, DB>) -> Self {
+        self.compound("INTERSECT", other)
+    }
+
+    /// Removes the rows selected by `other`. See [`union`](Self::union).
+    ///
+    /// Requires MySQL 8.0.31 or later.
+    pub fn except<U>(self, other: SelectQueryBuilder<U, DB>) -> Self {
+        self.compound("EXCEPT", other)
+    }
+
+    fn compound<U>(mut self, op: &'static str, other: SelectQueryBuilder<U, DB>) -> Self {
+        let problem = if !other.order.is_empty() || other.limit.is_some() || other.offset.is_some() {
+            Some("ORDER BY, LIMIT and OFFSET apply to the combined rows and must be set on the first query".to_string())
+        } else if other.lock.is_some() {
+            Some("row locks cannot be combined with set operations".to_string())
+        } else if !other.with.is_empty() {
+            Some("common table expressions must be added to the first query".to_string())
+        } else if !other.compounds.is_empty() {
+            Some("chain set operations on the first query instead of nesting them".to_string())
+        } else {
+            other.nested_problem().map(str::to_string)
+        };
+        let (sql, params) = other.body();
+        self.compounds.push(Compound { op, columns: other.columns_sql(), sql, params, problem });
+        self
+    }
+
+    /// Offset pagination over the filtered rows, returning a `Page` with
+    /// totals. The sort keys are kept, with the primary key appended as a
+    /// tie-breaker; `limit`, `offset`, `distinct`, locks and set
+    /// operations are not applied.
+    ///
+    /// Pages always read the entity's table: after
+    /// [`from_subquery`](Self::from_subquery), [`with`](Self::with) or a set
+    /// operation such as [`union`](Self::union), `fetch` fails with
+    /// `Error::Configuration` rather than paginating a different row set.
+    pub fn paginate(self, page: u64, per_page: u64) -> Paginate<T, DB> {
+        let columns = self.select_columns();
+        let order_by = self.paginate_order_by();
+        let problem = match self.nested_problem() {
+            Some(problem) => Some(format!("invalid subquery: {}", problem)),
+            None => self.paginate_problem().map(str::to_string),
+        };
+        let paginate = match self.filter {
+            Some(filter) => Paginate::new(self.scheme, filter, page, per_page),
+            None => Paginate::new(self.scheme, "1=1", page, per_page),
+        };
+        let paginate = paginate.with_select(columns, order_by);
+        match problem {
+            Some(problem) => paginate.with_problem(problem),
+            None => paginate,
+        }
+    }
+
+    /// Builds the SQL and the filter binds preceding LIMIT and OFFSET.
+    ///
+    /// Fails for combinations the database rejects: DISTINCT or a set
+    /// operation with a row lock, `skip_locked` on MySQL 5.7, set
+    /// operations over queries with different columns, and nested queries
+    /// that fail to build themselves.
+    pub fn build(&self) -> Result<(&'static str, Vec<BindValue<DB>>), Error> {
+        if let Some(problem) = self.nested_problem() {
+            return Err(Error::Configuration(format!("invalid subquery: {}", problem).into()));
+        }
+        if self.lock.is_some() && self.distinct {
+            return Err(Error::Configuration("FOR UPDATE cannot be combined with DISTINCT".into()));
+        }
+        if self.lock == Some(Lock::SkipLocked) && cfg!(feature = "mysql_5_7") {
+            return Err(Error::Configuration("SKIP LOCKED requires MySQL 8.0".into()));
+        }
+        if let Some(compound) = self.compounds.first() {
+            if self.lock.is_some() {
+                return Err(Error::Configuration(format!("FOR UPDATE cannot be combined with {}", compound.op).into()));
+            }
+        }
+        if let Some(problem) = self.compound_problem() {
+            return Err(Error::Configuration(problem.into()));
+        }
+
+        let db = Dialect::current();
+        let (mut template, params) = self.template();
+        match (self.lock, db) {
+            (_, Dialect::SQLite) | (None, _) => {}
+            (Some(Lock::ForUpdate), _) => template.push_str(" FOR UPDATE"),
+            (Some(Lock::SkipLocked), _) => template.push_str(" FOR UPDATE SKIP LOCKED"),
+        }
+
+        let key = format!("{}-select-{}", self.scheme.table_name, template);
+        let sql = get_or_insert_sql(key, || prepare_where(&template, 1));
+        Ok((sql, params))
+    }
+
+    /// Why a set operation would be rejected by the database: operands with
+    /// their own ordering, limits, locks or WITH clause, different columns,
+    /// and INTERSECT or EXCEPT on MySQL 5.7.
+    fn compound_problem(&self) -> Option<String> {
+        let columns = self.columns_sql();
+        for compound in &self.compounds {
+            if let Some(problem) = &compound.problem {
+                return Some(format!("{}: {}", compound.op, problem));
+            }
+            if compound.columns != columns {
+                return Some(format!(
+                    "{} requires both queries to select the same columns, got ({}) and ({})",
+                    compound.op, columns, compound.columns
+                ));
+            }
+            if matches!(compound.op, "INTERSECT" | "EXCEPT") && cfg!(feature = "mysql_5_7") {
+                return Some(format!("{} requires MySQL 8.0.31", compound.op));
+            }
+        }
+        None
+    }
+
+    /// Why a query nested in the FROM, WITH or WHERE clause is invalid.
+    fn nested_problem(&self) -> Option<&str> {
+        self.source_problem
+            .as_deref()
+            .or_else(|| self.with.problem())
+            .or_else(|| self.filter.as_ref().and_then(|filter| filter.problem()))
+    }
+
+    /// The statement up to LIMIT and OFFSET, with "{}" placeholders, and the
+    /// binds preceding LIMIT and OFFSET.
+    fn template(&self) -> (String, Vec<BindValue<DB>>) {
+        let db = Dialect::current();
+        let (body, body_params) = self.body();
+        let mut template = format!("{}{}", self.with.sql(None), body);
+        let mut params = self.with.params().to_vec();
+        params.extend(body_params);
+        for compound in &self.compounds {
+            template.push_str(&format!(" {} {}", compound.op, compound.sql));
+            params.extend(compound.params.iter().cloned());
+        }
+        if !self.order.is_empty() {
+            template.push_str(" ORDER BY ");
+            template.push_str(&order_by_sql(&self.order));
+        }
+        template.push_str(db.limit_offset(self.limit.is_some(), self.offset.is_some()));
+        (template, params)
+    }
+
+    /// `SELECT ... FROM ... [WHERE ...]`, without WITH, set operations,
+    /// ordering or limits, and its binds.
+    fn body(&self) -> (String, Vec<BindValue<DB>>) {
+        // IMPORTANT: Call gen_select_columns_static() BEFORE acquiring the lock
+        // to avoid deadlock since it also accesses SQL_CACHE
+        let columns = self.columns_sql();
+        let table = Dialect::current().quote_identifier(&self.scheme.table_name);
+
+        let mut params = Vec::new();
+        let source = match &self.source {
+            Some((source, source_params)) => {
+                params.extend(source_params.iter().cloned());
+                source.clone()
+            }
+            None => table,
+        };
+        let mut body = format!("SELECT {}{} FROM {}", if self.distinct { "DISTINCT " } else { "" }, columns, source);
+        if let Some(filter) = &self.filter {
+            body.push_str(" WHERE ");
+            body.push_str(filter.sql());
+            params.extend(filter.params().iter().cloned());
+        }
+        (body, params)
+    }
+
+    fn columns_sql(&self) -> String {
+        if self.selected.is_empty() {
+            self.select_columns().to_string()
+        } else {
+            self.selected.iter().map(|column| column.to_sql()).collect::<Vec<_>>().join(", ")
+        }
+    }
+
+    fn select_columns(&self) -> &'static str {
+        match self.columns {
+            Some(columns) => self.scheme.gen_select_columns_subset_static(columns),
+            None => self.scheme.gen_select_columns_static(),
+        }
+    }
+
+    /// Why `paginate` can't honour this query.
+    fn paginate_problem(&self) -> Option<&'static str> {
+        if !self.with.is_empty() {
+            Some("common table expressions cannot be paginated; nest the query in a filter with in_subquery instead")
+        } else if self.source.is_some() {
+            Some("pages are read from the entity's table; a FROM subquery or CTE cannot be paginated")
+        } else if !self.compounds.is_empty() {
+            Some("set operations cannot be paginated; paginate each query on its own")
+        } else {
+            None
+        }
+    }
+
+    fn paginate_order_by(&self) -> String {
+        let db = Dialect::current();
+        let id = db.quote_identifier(&self.scheme.id_field);
+        if self.order.is_empty() {
+            return id;
+        }
+        let mut order_by = order_by_sql(&self.order);
+        if !self.order.iter().any(|(column, _, _)| column.name() == self.scheme.id_field) {
+            order_by.push_str(", ");
+            order_by.push_str(&id);
+        }
+        order_by
+    }
+}
+
+/// Nests the query with its LIMIT and OFFSET bound as values. Row locks are
+/// left out, as they apply to the outer statement.
+///
+/// Invalid set operations, or invalid queries nested in this one, are
+/// reported as `Error::Configuration` by the outer builder's `build` and
+/// `fetch_*`.
+impl<T, DB: Database> IntoSubquery<DB> for SelectQueryBuilder<T, DB> {
+    fn into_subquery(self) -> Subquery<DB> {
+        let problem = self.nested_problem().map(str::to_string).or_else(|| self.compound_problem());
+        let (sql, mut params) = self.template();
+        params.extend(self.limit.into_iter().chain(self.offset).map(|n| BindValue::I64(n as i64)));
+        Subquery::raw(&sql, params).with_problem(problem)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> SelectQueryBuilder<T, Postgres>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    fn query(&self) -> Result<sqlx::query::QueryAs<'static, Postgres, T, sqlx::postgres::PgArguments>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query_as::<_, T>(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        if let Some(n) = self.limit {
+            query = query.bind(n as i64);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind(n as i64);
+        }
+        Ok(query)
+    }
+
+    /// Execute the query and return all rows.
+    ///
+    /// Accepts a pool or, for locking reads, a transaction (`&mut *tx`).
+    pub async fn fetch_all<'e, E>(self, executor: E) -> Result<Vec<T>, Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+        T: 'e,
+    {
+        self.query()?.fetch_all(executor).await
+    }
+
+    /// Execute the query and return exactly one row.
+    pub async fn fetch_one<'e, E>(self, executor: E) -> Result<T, Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+        T: 'e,
+    {
+        self.query()?.fetch_one(executor).await
+    }
+
+    /// Execute the query and return the first row, if any.
+    pub async fn fetch_optional<'e, E>(self, executor: E) -> Result<Option<T>, Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+        T: 'e,
+    {
+        self.query()?.fetch_optional(executor).await
+    }
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+impl<T> SelectQueryBuilder<T, MySql>
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    fn query(&self) -> Result<sqlx::query::QueryAs<'static, MySql, T, sqlx::mysql::MySqlArguments>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query_as::<_, T>(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        if let Some(n) = self.limit {
+            query = query.bind(n);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind(n);
+        }
+        Ok(query)
+    }
+
+    /// Execute the query and return all rows.
+    ///
+    /// Accepts a pool or, for locking reads, a transaction (`&mut *tx`).
+    pub async fn fetch_all<'e, E>(self, executor: E) -> Result<Vec<T>, Error>
+    where
+        E: Executor<'e, Database = MySql>,
+        T: 'e,
+    {
+        self.query()?.fetch_all(executor).await
+    }
+
+    /// Execute the query and return exactly one row.
+    pub async fn fetch_one<'e, E>(self, executor: E) -> Result<T, Error>
+    where
+        E: Executor<'e, Database = MySql>,
+        T: 'e,
+    {
+        self.query()?.fetch_one(executor).await
+    }
+
+    /// Execute the query and return the first row, if any.
+    pub async fn fetch_optional<'e, E>(self, executor: E) -> Result<Option<T>, Error>
+    where
+        E: Executor<'e, Database = MySql>,
+        T: 'e,
+    {
+        self.query()?.fetch_optional(executor).await
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+impl<T> SelectQueryBuilder<T, Sqlite>
+where
+    T: for<'r> FromRow<'r, SqliteRow> + Send + Unpin,
+{
+    fn query(&self) -> Result<sqlx::query::QueryAs<'static, Sqlite, T, sqlx::sqlite::SqliteArguments<'static>>, Error> {
+        let (sql, params) = self.build()?;
+        let mut query = sqlx::query_as::<_, T>(sql);
+        for param in params {
+            query = param.bind_to(query);
+        }
+        if let Some(n) = self.limit {
+            query = query.bind(n as i64);
+        }
+        if let Some(n) = self.offset {
+            query = query.bind(n as i64);
+        }
+        Ok(query)
+    }
+
+    /// Execute the query and return all rows.
+    pub async fn fetch_all<'e, E>(self, executor: E) -> Result<Vec<T>, Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+        T: 'e,
+    {
+        self.query()?.fetch_all(executor).await
+    }
+
+    /// Execute the query and return exactly one row.
+    pub async fn fetch_one<'e, E>(self, executor: E) -> Result<T, Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+        T: 'e,
+    {
+        self.query()?.fetch_one(executor).await
+    }
+
+    /// Execute the query and return the first row, if any.
+    pub async fn fetch_optional<'e, E>(self, executor: E) -> Result<Option<T>, Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+        T: 'e,
+    {
+        self.query()?.fetch_optional(executor).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "postgres")]
+    type Db = Postgres;
+    #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+    type Db = MySql;
+    #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
+    type Db = Sqlite;
+
+    fn scheme() -> &'static Scheme {
+        Box::leak(Box::new(Scheme {
+            table_name: "select_users".to_string(),
+            insert_fields: vec!["id".to_string(), "name".to_string()],
+            update_fields: vec!["name".to_string()],
+            id_field: "id".to_string(),
+            column_definitions: vec![],
+        }))
+    }
+
+    const NAME: Column = Column::new("select_users", "name");
+
+    #[test]
+    fn test_distinct_with_lock_is_rejected() {
+        let query = SelectQueryBuilder::<(), Db>::new(scheme()).distinct().for_update();
+        assert!(query.build().is_err());
+    }
+
+    #[test]
+    fn test_skip_locked_implies_for_update() {
+        let query = SelectQueryBuilder::<(), Db>::new(scheme()).skip_locked().for_update();
+        assert_eq!(query.lock, Some(Lock::SkipLocked));
+    }
+
+    #[test]
+    fn test_offset_without_limit() {
+        let (sql, _) = SelectQueryBuilder::<(), Db>::new(scheme()).offset(20).build().unwrap();
+        match Dialect::current() {
+            Dialect::PostgreSQL => assert!(sql.ends_with(" OFFSET $1")),
+            Dialect::MySQL => assert!(sql.ends_with(" LIMIT 18446744073709551615 OFFSET ?")),
+            Dialect::SQLite => assert!(sql.ends_with(" LIMIT -1 OFFSET ?")),
+        }
+    }
+
+    #[test]
+    fn test_nulls_placement() {
+        let query = SelectQueryBuilder::<(), Db>::new(scheme()).order_by_nulls(NAME, Order::Desc, Nulls::Last);
+        let (sql, _) = query.build().unwrap();
+        match Dialect::current() {
+            Dialect::PostgreSQL => assert!(sql.ends_with(r#"ORDER BY "name" DESC NULLS LAST"#)),
+            Dialect::MySQL => assert!(sql.ends_with("ORDER BY `name` IS NULL ASC, `name` DESC")),
+            Dialect::SQLite => assert!(sql.ends_with("ORDER BY name DESC NULLS LAST")),
+        }
+    }
+
+    #[test]
+    fn test_set_operations_are_checked() {
+        let other = || SelectQueryBuilder::<(), Db>::new(scheme());
+        let query = || SelectQueryBuilder::<(), Db>::new(scheme());
+        assert!(query().union(other()).build().is_ok());
+        assert!(query().union(other().select_column(NAME)).build().is_err());
+        assert!(query().union(other().limit(5)).build().is_err());
+        assert!(query().union(other().order_by(NAME, Order::Asc)).build().is_err());
+        assert!(query().union(other().union(other())).build().is_err());
+        assert!(query().union(other()).for_update().build().is_err());
+    }
+
+    #[test]
+    fn test_subquery_set_operations_are_checked() {
+        let invalid = || {
+            SelectQueryBuilder::<(), Db>::new(scheme())
+                .union(SelectQueryBuilder::<(), Db>::new(scheme()).select_column(NAME))
+        };
+        let query = || SelectQueryBuilder::<(), Db>::new(scheme());
+        let is_invalid_subquery = |result: Result<(&'static str, Vec<BindValue<Db>>), Error>| {
+            matches!(result, Err(Error::Configuration(err)) if err.to_string().starts_with("invalid subquery") && err.to_string().contains("same columns"))
+        };
+        assert!(is_invalid_subquery(query().from_subquery(invalid()).build()));
+        assert!(is_invalid_subquery(query().with("a", invalid()).build()));
+        assert!(is_invalid_subquery(query().filter(NAME.eq("x").and(NAME.in_subquery(invalid()))).build()));
+        assert!(is_invalid_subquery(query().filter(Expr::exists(invalid()).not()).build()));
+        assert!(query().union(query().filter(Expr::exists(invalid()))).build().is_err());
+        assert!(query().filter(Expr::exists(query())).build().is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+//! Ancestor and descendant walks over adjacency-list tables, used by the
+//! `descendants` and `ancestors` methods generated for
+//! `#[crud(tree(parent = "..."))]` entities.
+
+use sqlx::Database;
+
+use super::SelectQueryBuilder;
+use crate::filter::{Column, Subquery};
+use crate::proxy::BindProxy;
+use crate::{Dialect, Scheme};
+
+/// Direction of a tree walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeWalk {
+    /// Children, grandchildren and so on, down to the leaves
+    Descendants,
+    /// Parent, grandparent and so on, up to the root
+    Ancestors,
+}
+
+impl<T, DB: Database> SelectQueryBuilder<T, DB> {
+    /// Selects the rows above or below the row `id`, not including it.
+    ///
+    /// A recursive CTE collects the `(id, parent)` pairs reached from `id`,
+    /// then the entity's rows are selected by id, so filters, ordering and
+    /// limits apply as usual:
+    ///
+    /// ```sql
+    /// WITH RECURSIVE "category_tree" AS (
+    ///     SELECT "id", "parent_id" FROM "category" WHERE "parent_id" = $1
+    ///     UNION
+    ///     SELECT "category"."id", "category"."parent_id" FROM "category"
+    ///     JOIN "category_tree" ON "category"."parent_id" = "category_tree"."id"
+    /// )
+    /// SELECT ... FROM "category" WHERE "id" IN (SELECT "id" FROM "category_tree")
+    /// ```
+    pub fn tree<V: BindProxy<DB>>(scheme: &'static Scheme, parent: &'static str, walk: TreeWalk, id: V) -> Self {
+        let db = Dialect::current();
+        let table = db.quote_identifier(&scheme.table_name);
+        let cte_name = format!("{}_tree", scheme.table_name);
+        let cte = db.quote_identifier(&cte_name);
+        let id_column = Column::new(&scheme.table_name, &scheme.id_field);
+        let parent_column = Column::new(&scheme.table_name, parent);
+
+        // The walk starts at the children of `id`, or at its parent
+        let (start, params) = match walk {
+            TreeWalk::Descendants => parent_column.eq(id).into_parts(),
+            TreeWalk::Ancestors => {
+                let (row, params) = id_column.eq(id).into_parts();
+                let parent_of_row = format!("SELECT {} FROM {} WHERE {}", parent_column.to_sql(), table, row);
+                (format!("{} = ({})", id_column.to_sql(), parent_of_row), params)
+            }
+        };
+        let anchor = Subquery::raw(
+            &format!("SELECT {}, {} FROM {} WHERE {}", id_column.to_sql(), parent_column.to_sql(), table, start),
+            params,
+        );
+
+        // Then follows the parent links down or up from the rows found so far
+        let (table_side, cte_side) = match walk {
+            TreeWalk::Descendants => (parent_column, id_column),
+            TreeWalk::Ancestors => (id_column, parent_column),
+        };
+        let step = Subquery::raw(
+            &format!(
+                "SELECT {}, {} FROM {} JOIN {} ON {} = {}.{}",
+                id_column.qualified().to_sql(),
+                parent_column.qualified().to_sql(),
+                table,
+                cte,
+                table_side.qualified().to_sql(),
+                cte,
+                db.quote_identifier(cte_side.name()),
+            ),
+            Vec::new(),
+        );
+
+        let walked = Subquery::raw(&format!("SELECT {} FROM {}", id_column.to_sql(), cte), Vec::new());
+        Self::new(scheme)
+            .with_recursive(&cte_name, anchor, step)
+            .filter(id_column.in_subquery(walked))
+    }
+}
@@ -1,6 +1,8 @@
 use sqlx::query::{Query, QueryAs};
 use sqlx::database::HasArguments;
 
+use crate::filter::IntoWhere;
+
 #[cfg(feature = "postgres")]
 use sqlx::postgres::Postgres;
 
@@ -32,15 +34,88 @@ pub trait EnhancedCrud {
     fn by_pk<'q>() -> QueryAs<'q, Postgres, Self, <Postgres as HasArguments<'q>>::Arguments> where Self: Sized;
     fn make_query(sql: &str) -> QueryAs<'_, Postgres, Self, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
     fn make_execute(sql: &str) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn where_query(statement: &str) -> QueryAs<'_, Postgres, Self, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn count_query(statement: &str) -> QueryAs<'_, Postgres, (i64,), <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn delete_where_query(statement: &str) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
+    fn where_query<'q, W: IntoWhere<Postgres>>(statement: W) -> QueryAs<'q, Postgres, Self, <Postgres as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn count_query<'q, W: IntoWhere<Postgres>>(statement: W) -> QueryAs<'q, Postgres, (i64,), <Postgres as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn delete_where_query<'q, W: IntoWhere<Postgres>>(statement: W) -> Query<'q, Postgres, <Postgres as HasArguments<'q>>::Arguments> where Self: Sized;
     fn bulk_delete(ids: &[String]) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_insert(items: &[Self]) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_update(items: &[Self]) -> Query<'_, Postgres, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_select(ids: &[String]) -> QueryAs<'_, Postgres, Self, <Postgres as HasArguments<'_>>::Arguments> where Self: Sized;
     fn agg_query() -> crate::aggregate::AggQueryBuilder<'static, Postgres> where Self: Sized;
 
+    /// Fetch one page of rows matching `filter`, ordered by primary key, with totals.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// ```
+    fn paginate<W: IntoWhere<Postgres>>(filter: W, page: u64, per_page: u64) -> crate::pagination::Paginate<Self, Postgres> where Self: Sized;
+
+    /// Start keyset pagination after `cursor` (`None` for the first page).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = Event::after(cursor).order_by(Event::CREATED_AT, Order::Desc).limit(500).fetch(&pool).await?;
+    /// ```
+    fn after<C: Into<Option<crate::pagination::Cursor>>>(cursor: C) -> crate::pagination::Keyset<Self, Postgres> where Self: Sized;
+
+    /// Scan the table in primary-key order, `chunk_size` rows per query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut users = User::chunked(1000).resume_after(last_id).stream(&pool);
+    /// while let Some(user) = users.try_next().await? { /* ... */ }
+    /// ```
+    fn chunked(chunk_size: u64) -> crate::pagination::Chunked<Self, Postgres> where Self: Sized;
+
+    /// Start a SELECT over all columns with ordering, limits and row locks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recent = User::select()
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .order_by(User::CREATED_AT, Order::Desc)
+    ///     .limit(10)
+    ///     .fetch_all(&pool).await?;
+    /// ```
+    fn select() -> crate::select::SelectQueryBuilder<Self, Postgres> where Self: Sized;
+
+    /// Start a SELECT of only the columns of projection `P`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = User::select_as::<UserSummary>().limit(50).fetch_all(&pool).await?;
+    /// ```
+    fn select_as<P: crate::select::Projection<Entity = Self>>() -> crate::select::SelectQueryBuilder<P, Postgres> where Self: Sized;
+
+    /// Call `f` with every row of the table in chunks of `chunk_size`,
+    /// returning the number of rows processed.
+    ///
+    /// Shorthand for `Self::chunked(chunk_size).for_each_chunk(pool, f)`; use
+    /// `chunked` to add a filter or resume from a saved key.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::for_each_chunk(&pool, 1000, |batch| async move {
+    ///     reindex(&batch).await
+    /// }).await?;
+    /// ```
+    fn for_each_chunk<'p, F, Fut, E>(pool: &'p sqlx::Pool<Postgres>, chunk_size: u64, f: F) -> impl std::future::Future<Output = Result<u64, E>> + Send + 'p
+    where
+        Self: Sized + Send + Unpin + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + 'p,
+        F: FnMut(Vec<Self>) -> Fut + Send + 'p,
+        Fut: std::future::Future<Output = Result<(), E>> + Send + 'p,
+        E: From<sqlx::Error> + 'p,
+    {
+        Self::chunked(chunk_size).for_each_chunk(pool, f)
+    }
+
     /// Start an INNER JOIN with another table, returning a query builder.
     ///
     /// # Type Parameters
@@ -112,6 +187,93 @@ pub trait EnhancedCrud {
     where
         Self: Sized + crate::join::SchemeAccessor,
         T: Sized + crate::join::SchemeAccessor + Unpin + Send;
+
+    /// Start an aliased JOIN query, needed for self-joins and for joining a
+    /// table several times.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = Employee::alias("e")
+    ///     .join_left::<Employee>("m", r#""e"."manager_id" = "m"."id""#)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn alias(alias: &str) -> crate::join::JoinAlias<'static, Self, Postgres>
+    where
+        Self: Sized + crate::join::SchemeAccessor,
+    {
+        crate::join::JoinAlias::new(alias)
+    }
+
+    /// Start an INNER JOIN whose ON clause is inferred from a
+    /// `#[crud(references(..))]` foreign key between the two entities.
+    ///
+    /// Fails to compile when neither entity declares a foreign key to the
+    /// other, or when the foreign key is ambiguous.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Order has #[crud(references(Customer))] customer_id
+    /// let results = Order::join_inner_fk::<Customer>()
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn join_inner_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Postgres>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Inner,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a LEFT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_left_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Postgres>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Left,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a RIGHT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_right_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Postgres>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Right,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a FULL JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_full_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Postgres>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Full,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
 }
 
 #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
@@ -122,15 +284,88 @@ pub trait EnhancedCrud {
     fn by_pk<'q>() -> QueryAs<'q, MySql, Self, <MySql as HasArguments<'q>>::Arguments> where Self: Sized;
     fn make_query(sql: &str) -> QueryAs<'_, MySql, Self, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
     fn make_execute(sql: &str) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn where_query(statement: &str) -> QueryAs<'_, MySql, Self, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn count_query(statement: &str) -> QueryAs<'_, MySql, (i64,), <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn delete_where_query(statement: &str) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
+    fn where_query<'q, W: IntoWhere<MySql>>(statement: W) -> QueryAs<'q, MySql, Self, <MySql as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn count_query<'q, W: IntoWhere<MySql>>(statement: W) -> QueryAs<'q, MySql, (i64,), <MySql as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn delete_where_query<'q, W: IntoWhere<MySql>>(statement: W) -> Query<'q, MySql, <MySql as HasArguments<'q>>::Arguments> where Self: Sized;
     fn bulk_delete(ids: &[String]) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_insert(items: &[Self]) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_update(items: &[Self]) -> Query<'_, MySql, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_select(ids: &[String]) -> QueryAs<'_, MySql, Self, <MySql as HasArguments<'_>>::Arguments> where Self: Sized;
     fn agg_query() -> crate::aggregate::AggQueryBuilder<'static, MySql> where Self: Sized;
 
+    /// Fetch one page of rows matching `filter`, ordered by primary key, with totals.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// ```
+    fn paginate<W: IntoWhere<MySql>>(filter: W, page: u64, per_page: u64) -> crate::pagination::Paginate<Self, MySql> where Self: Sized;
+
+    /// Start keyset pagination after `cursor` (`None` for the first page).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = Event::after(cursor).order_by(Event::CREATED_AT, Order::Desc).limit(500).fetch(&pool).await?;
+    /// ```
+    fn after<C: Into<Option<crate::pagination::Cursor>>>(cursor: C) -> crate::pagination::Keyset<Self, MySql> where Self: Sized;
+
+    /// Scan the table in primary-key order, `chunk_size` rows per query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut users = User::chunked(1000).resume_after(last_id).stream(&pool);
+    /// while let Some(user) = users.try_next().await? { /* ... */ }
+    /// ```
+    fn chunked(chunk_size: u64) -> crate::pagination::Chunked<Self, MySql> where Self: Sized;
+
+    /// Start a SELECT over all columns with ordering, limits and row locks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recent = User::select()
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .order_by(User::CREATED_AT, Order::Desc)
+    ///     .limit(10)
+    ///     .fetch_all(&pool).await?;
+    /// ```
+    fn select() -> crate::select::SelectQueryBuilder<Self, MySql> where Self: Sized;
+
+    /// Start a SELECT of only the columns of projection `P`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = User::select_as::<UserSummary>().limit(50).fetch_all(&pool).await?;
+    /// ```
+    fn select_as<P: crate::select::Projection<Entity = Self>>() -> crate::select::SelectQueryBuilder<P, MySql> where Self: Sized;
+
+    /// Call `f` with every row of the table in chunks of `chunk_size`,
+    /// returning the number of rows processed.
+    ///
+    /// Shorthand for `Self::chunked(chunk_size).for_each_chunk(pool, f)`; use
+    /// `chunked` to add a filter or resume from a saved key.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::for_each_chunk(&pool, 1000, |batch| async move {
+    ///     reindex(&batch).await
+    /// }).await?;
+    /// ```
+    fn for_each_chunk<'p, F, Fut, E>(pool: &'p sqlx::Pool<MySql>, chunk_size: u64, f: F) -> impl std::future::Future<Output = Result<u64, E>> + Send + 'p
+    where
+        Self: Sized + Send + Unpin + for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + 'p,
+        F: FnMut(Vec<Self>) -> Fut + Send + 'p,
+        Fut: std::future::Future<Output = Result<(), E>> + Send + 'p,
+        E: From<sqlx::Error> + 'p,
+    {
+        Self::chunked(chunk_size).for_each_chunk(pool, f)
+    }
+
     /// Start an INNER JOIN with another table.
     #[cfg(feature = "join_queries")]
     fn join_inner<T>(condition: &str) -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
@@ -154,14 +389,101 @@ pub trait EnhancedCrud {
 
     /// Start a FULL JOIN with another table.
     ///
-    /// Note: MySQL does not support FULL JOIN natively.
-    /// This method is provided for API compatibility but will generate
-    /// invalid SQL. Use LEFT JOIN UNION RIGHT JOIN pattern instead.
+    /// Note: MySQL does not support FULL JOIN natively. It is emulated as a
+    /// LEFT JOIN `UNION ALL` the unmatched rows of `T`, so the WHERE binds
+    /// are sent twice and ORDER BY applies to the combined rows.
     #[cfg(feature = "join_queries")]
     fn join_full<T>(condition: &str) -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
     where
         Self: Sized + crate::join::SchemeAccessor,
         T: Sized + crate::join::SchemeAccessor + Unpin + Send;
+
+    /// Start an aliased JOIN query, needed for self-joins and for joining a
+    /// table several times.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = Employee::alias("e")
+    ///     .join_left::<Employee>("m", r#""e"."manager_id" = "m"."id""#)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn alias(alias: &str) -> crate::join::JoinAlias<'static, Self, MySql>
+    where
+        Self: Sized + crate::join::SchemeAccessor,
+    {
+        crate::join::JoinAlias::new(alias)
+    }
+
+    /// Start an INNER JOIN whose ON clause is inferred from a
+    /// `#[crud(references(..))]` foreign key between the two entities.
+    ///
+    /// Fails to compile when neither entity declares a foreign key to the
+    /// other, or when the foreign key is ambiguous.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Order has #[crud(references(Customer))] customer_id
+    /// let results = Order::join_inner_fk::<Customer>()
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn join_inner_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Inner,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a LEFT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_left_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Left,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a RIGHT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_right_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Right,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a FULL JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_full_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, MySql>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Full,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
 }
 
 #[cfg(all(feature = "sqlite", not(feature = "postgres"), not(feature = "mysql")))]
@@ -172,15 +494,88 @@ pub trait EnhancedCrud {
     fn by_pk<'q>() -> QueryAs<'q, Sqlite, Self, <Sqlite as HasArguments<'q>>::Arguments> where Self: Sized;
     fn make_query(sql: &str) -> QueryAs<'_, Sqlite, Self, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
     fn make_execute(sql: &str) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn where_query(statement: &str) -> QueryAs<'_, Sqlite, Self, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn count_query(statement: &str) -> QueryAs<'_, Sqlite, (i64,), <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
-    fn delete_where_query(statement: &str) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
+    fn where_query<'q, W: IntoWhere<Sqlite>>(statement: W) -> QueryAs<'q, Sqlite, Self, <Sqlite as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn count_query<'q, W: IntoWhere<Sqlite>>(statement: W) -> QueryAs<'q, Sqlite, (i64,), <Sqlite as HasArguments<'q>>::Arguments> where Self: Sized;
+    fn delete_where_query<'q, W: IntoWhere<Sqlite>>(statement: W) -> Query<'q, Sqlite, <Sqlite as HasArguments<'q>>::Arguments> where Self: Sized;
     fn bulk_delete(ids: &[String]) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_insert(items: &[Self]) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_update(items: &[Self]) -> Query<'_, Sqlite, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
     fn bulk_select(ids: &[String]) -> QueryAs<'_, Sqlite, Self, <Sqlite as HasArguments<'_>>::Arguments> where Self: Sized;
     fn agg_query() -> crate::aggregate::AggQueryBuilder<'static, Sqlite> where Self: Sized;
 
+    /// Fetch one page of rows matching `filter`, ordered by primary key, with totals.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = User::paginate(User::STATUS.eq("active"), 1, 20).fetch(&pool).await?;
+    /// ```
+    fn paginate<W: IntoWhere<Sqlite>>(filter: W, page: u64, per_page: u64) -> crate::pagination::Paginate<Self, Sqlite> where Self: Sized;
+
+    /// Start keyset pagination after `cursor` (`None` for the first page).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page = Event::after(cursor).order_by(Event::CREATED_AT, Order::Desc).limit(500).fetch(&pool).await?;
+    /// ```
+    fn after<C: Into<Option<crate::pagination::Cursor>>>(cursor: C) -> crate::pagination::Keyset<Self, Sqlite> where Self: Sized;
+
+    /// Scan the table in primary-key order, `chunk_size` rows per query.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut users = User::chunked(1000).resume_after(last_id).stream(&pool);
+    /// while let Some(user) = users.try_next().await? { /* ... */ }
+    /// ```
+    fn chunked(chunk_size: u64) -> crate::pagination::Chunked<Self, Sqlite> where Self: Sized;
+
+    /// Start a SELECT over all columns with ordering, limits and row locks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recent = User::select()
+    ///     .filter(User::STATUS.eq("active"))
+    ///     .order_by(User::CREATED_AT, Order::Desc)
+    ///     .limit(10)
+    ///     .fetch_all(&pool).await?;
+    /// ```
+    fn select() -> crate::select::SelectQueryBuilder<Self, Sqlite> where Self: Sized;
+
+    /// Start a SELECT of only the columns of projection `P`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = User::select_as::<UserSummary>().limit(50).fetch_all(&pool).await?;
+    /// ```
+    fn select_as<P: crate::select::Projection<Entity = Self>>() -> crate::select::SelectQueryBuilder<P, Sqlite> where Self: Sized;
+
+    /// Call `f` with every row of the table in chunks of `chunk_size`,
+    /// returning the number of rows processed.
+    ///
+    /// Shorthand for `Self::chunked(chunk_size).for_each_chunk(pool, f)`; use
+    /// `chunked` to add a filter or resume from a saved key.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// User::for_each_chunk(&pool, 1000, |batch| async move {
+    ///     reindex(&batch).await
+    /// }).await?;
+    /// ```
+    fn for_each_chunk<'p, F, Fut, E>(pool: &'p sqlx::Pool<Sqlite>, chunk_size: u64, f: F) -> impl std::future::Future<Output = Result<u64, E>> + Send + 'p
+    where
+        Self: Sized + Send + Unpin + for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + 'p,
+        F: FnMut(Vec<Self>) -> Fut + Send + 'p,
+        Fut: std::future::Future<Output = Result<(), E>> + Send + 'p,
+        E: From<sqlx::Error> + 'p,
+    {
+        Self::chunked(chunk_size).for_each_chunk(pool, f)
+    }
+
     /// Start an INNER JOIN with another table.
     #[cfg(feature = "join_queries")]
     fn join_inner<T>(condition: &str) -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
@@ -197,9 +592,9 @@ pub trait EnhancedCrud {
 
     /// Start a RIGHT JOIN with another table.
     ///
-    /// Note: SQLite does not support RIGHT JOIN natively.
-    /// This method is provided for API compatibility but will generate
-    /// invalid SQL. Use LEFT JOIN with reversed table order instead.
+    /// Note: SQLite does not support RIGHT JOIN natively. It is emulated as
+    /// a LEFT JOIN with the tables swapped; the results keep the `(Self, T)`
+    /// order.
     #[cfg(feature = "join_queries")]
     fn join_right<T>(condition: &str) -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
     where
@@ -208,14 +603,101 @@ pub trait EnhancedCrud {
 
     /// Start a FULL JOIN with another table.
     ///
-    /// Note: SQLite does not support FULL JOIN natively.
-    /// This method is provided for API compatibility but will generate
-    /// invalid SQL. Use LEFT JOIN UNION LEFT JOIN pattern instead.
+    /// Note: SQLite does not support FULL JOIN natively. It is emulated as a
+    /// LEFT JOIN `UNION ALL` the unmatched rows of `T`, so the WHERE binds
+    /// are sent twice and ORDER BY applies to the combined rows.
     #[cfg(feature = "join_queries")]
     fn join_full<T>(condition: &str) -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
     where
         Self: Sized + crate::join::SchemeAccessor,
         T: Sized + crate::join::SchemeAccessor + Unpin + Send;
+
+    /// Start an aliased JOIN query, needed for self-joins and for joining a
+    /// table several times.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rows = Employee::alias("e")
+    ///     .join_left::<Employee>("m", r#""e"."manager_id" = "m"."id""#)
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn alias(alias: &str) -> crate::join::JoinAlias<'static, Self, Sqlite>
+    where
+        Self: Sized + crate::join::SchemeAccessor,
+    {
+        crate::join::JoinAlias::new(alias)
+    }
+
+    /// Start an INNER JOIN whose ON clause is inferred from a
+    /// `#[crud(references(..))]` foreign key between the two entities.
+    ///
+    /// Fails to compile when neither entity declares a foreign key to the
+    /// other, or when the foreign key is ambiguous.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Order has #[crud(references(Customer))] customer_id
+    /// let results = Order::join_inner_fk::<Customer>()
+    ///     .fetch_all(&pool)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "join_queries")]
+    fn join_inner_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Inner,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a LEFT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_left_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Left,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a RIGHT JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_right_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Right,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
+
+    /// Start a FULL JOIN whose ON clause is inferred from a foreign key, like
+    /// [`join_inner_fk`](Self::join_inner_fk).
+    #[cfg(feature = "join_queries")]
+    fn join_full_fk<T>() -> crate::join::JoinQueryBuilder<'static, Self, T, Sqlite>
+    where
+        Self: Sized + crate::join::SchemeAccessor + crate::join::ForeignKeyJoin<T> + Unpin + Send,
+        T: Sized + crate::join::SchemeAccessor + Unpin + Send,
+    {
+        crate::join::JoinQueryBuilder::new(
+            crate::join::JoinType::Full,
+            &<Self as crate::join::ForeignKeyJoin<T>>::join_condition(),
+        )
+    }
 }
 
 // ============================================================================
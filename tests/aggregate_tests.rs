@@ -27,7 +27,7 @@ fn test_simple_sum_aggregation() {
     let builder = Order::agg_query().sum("amount");
     let sql = builder.build();
 
-    assert!(sql.contains("SELECT SUM(amount) FROM"));
+    assert!(sql.contains(r#"SELECT SUM("amount") FROM"#));
     assert!(sql.contains("order"));  // Should contain table name "order"
 }
 
@@ -36,7 +36,7 @@ fn test_simple_avg_aggregation() {
     let builder = Order::agg_query().avg("amount");
     let sql = builder.build();
 
-    assert!(sql.contains("SELECT AVG(amount) FROM"));
+    assert!(sql.contains(r#"SELECT AVG("amount") FROM"#));
 }
 
 #[test]
@@ -52,7 +52,7 @@ fn test_count_column_aggregation() {
     let builder = Order::agg_query().count_column("id");
     let sql = builder.build();
 
-    assert!(sql.contains("SELECT COUNT(id) FROM"));
+    assert!(sql.contains(r#"SELECT COUNT("id") FROM"#));
 }
 
 #[test]
@@ -60,7 +60,7 @@ fn test_min_aggregation() {
     let builder = Order::agg_query().min("amount");
     let sql = builder.build();
 
-    assert!(sql.contains("SELECT MIN(amount) FROM"));
+    assert!(sql.contains(r#"SELECT MIN("amount") FROM"#));
 }
 
 #[test]
@@ -68,7 +68,7 @@ fn test_max_aggregation() {
     let builder = Order::agg_query().max("amount");
     let sql = builder.build();
 
-    assert!(sql.contains("SELECT MAX(amount) FROM"));
+    assert!(sql.contains(r#"SELECT MAX("amount") FROM"#));
 }
 
 #[test]
@@ -78,8 +78,8 @@ fn test_group_by_with_sum() {
         .sum("amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT category, SUM(amount)"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount")"#));
+    assert!(sql.contains(r#"GROUP BY "category""#));
 }
 
 #[test]
@@ -91,32 +91,32 @@ fn test_group_by_with_multiple_aggregates() {
         .count();
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT category, SUM(amount), AVG(amount), COUNT(*)"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount"), AVG("amount"), COUNT(*)"#));
+    assert!(sql.contains(r#"GROUP BY "category""#));
 }
 
 #[test]
 fn test_with_where_clause() {
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .sum("amount");
 
     let sql = builder.build();
     assert!(sql.contains("WHERE status = $1"));
-    assert!(sql.contains("SELECT SUM(amount)"));
+    assert!(sql.contains(r#"SELECT SUM("amount")"#));
 }
 
 #[test]
 fn test_group_by_with_where() {
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum("amount");
 
     let sql = builder.build();
     assert!(sql.contains("WHERE status = $1"));
-    assert!(sql.contains("SELECT category, SUM(amount)"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount")"#));
+    assert!(sql.contains(r#"GROUP BY "category""#));
 }
 
 #[test]
@@ -158,14 +158,14 @@ fn test_min_max_aggregation() {
         .max("amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT MIN(amount), MAX(amount) FROM"));
+    assert!(sql.contains(r#"SELECT MIN("amount"), MAX("amount") FROM"#));
 }
 
 #[test]
 fn test_complex_query() {
     // Test a complex query with WHERE, GROUP BY, and multiple aggregates
     let builder = Order::agg_query()
-        .where_("status = {} AND amount > {}", &["active", "100"])
+        .where_("status = {} AND amount > {}", &[&"active", &100i64])
         .group_by("category")
         .sum("amount")
         .avg("amount")
@@ -176,9 +176,9 @@ fn test_complex_query() {
     let sql = builder.build();
 
     // Verify all components are present
-    assert!(sql.contains("SELECT category, SUM(amount), AVG(amount), COUNT(*), MIN(amount), MAX(amount)"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount"), AVG("amount"), COUNT(*), MIN("amount"), MAX("amount")"#));
     assert!(sql.contains("WHERE status = $1 AND amount > $2"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"GROUP BY "category""#));
 }
 
 // ============================================================================
@@ -191,7 +191,7 @@ fn test_sum_with_alias() {
         .sum_as("amount", "total_amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT SUM(amount) AS total_amount"));
+    assert!(sql.contains(r#"SELECT SUM("amount") AS total_amount"#));
 }
 
 #[test]
@@ -200,7 +200,7 @@ fn test_avg_with_alias() {
         .avg_as("amount", "average_amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT AVG(amount) AS average_amount"));
+    assert!(sql.contains(r#"SELECT AVG("amount") AS average_amount"#));
 }
 
 #[test]
@@ -218,7 +218,7 @@ fn test_count_column_with_alias() {
         .count_column_as("id", "id_count");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT COUNT(id) AS id_count"));
+    assert!(sql.contains(r#"SELECT COUNT("id") AS id_count"#));
 }
 
 #[test]
@@ -227,7 +227,7 @@ fn test_min_with_alias() {
         .min_as("amount", "min_amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT MIN(amount) AS min_amount"));
+    assert!(sql.contains(r#"SELECT MIN("amount") AS min_amount"#));
 }
 
 #[test]
@@ -236,7 +236,7 @@ fn test_max_with_alias() {
         .max_as("amount", "max_amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT MAX(amount) AS max_amount"));
+    assert!(sql.contains(r#"SELECT MAX("amount") AS max_amount"#));
 }
 
 #[test]
@@ -250,7 +250,7 @@ fn test_multiple_aggregates_with_custom_aliases() {
         .max_as("amount", "maximum");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT category, SUM(amount) AS total, AVG(amount) AS average, COUNT(*) AS cnt, MIN(amount) AS minimum, MAX(amount) AS maximum"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount") AS total, AVG("amount") AS average, COUNT(*) AS cnt, MIN("amount") AS minimum, MAX("amount") AS maximum"#));
 }
 
 // ============================================================================
@@ -265,7 +265,7 @@ fn test_having_with_aggregate_function() {
         .having("SUM(amount) > {}", &[&1000i64]);
 
     let sql = builder.build();
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"GROUP BY "category""#));
     assert!(sql.contains("HAVING SUM(amount) > $1"));
 }
 
@@ -283,14 +283,14 @@ fn test_having_with_alias() {
 #[test]
 fn test_where_group_by_having() {
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .having("total > {}", &[&500i64]);
 
     let sql = builder.build();
     assert!(sql.contains("WHERE status = $1"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"GROUP BY "category""#));
     assert!(sql.contains("HAVING total > $2"));  // $2 because comes after WHERE
 }
 
@@ -346,7 +346,7 @@ fn test_order_by_with_multiple_aggregates() {
         .order_by("total", "DESC");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT category, SUM(amount) AS total, AVG(amount)"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount") AS total, AVG("amount")"#));
     assert!(sql.contains("ORDER BY total DESC"));
 }
 
@@ -368,7 +368,7 @@ fn test_limit() {
 #[test]
 fn test_limit_with_where() {
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum("amount")
         .limit(5);
@@ -415,7 +415,7 @@ fn test_complete_complex_query() {
     // Test a query with all features: WHERE, GROUP BY, aggregates with aliases,
     // HAVING, ORDER BY, LIMIT
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .avg_as("amount", "average")
@@ -426,9 +426,9 @@ fn test_complete_complex_query() {
     let sql = builder.build();
 
     // Verify all components
-    assert!(sql.contains("SELECT category, SUM(amount) AS total, AVG(amount) AS average"));
+    assert!(sql.contains(r#"SELECT "category", SUM("amount") AS total, AVG("amount") AS average"#));
     assert!(sql.contains("WHERE status = $1"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"GROUP BY "category""#));
     assert!(sql.contains("HAVING total > $2"));
     assert!(sql.contains("ORDER BY total DESC"));
     assert!(sql.contains("LIMIT $3"));
@@ -438,7 +438,7 @@ fn test_complete_complex_query() {
 fn test_pagination_with_all_features() {
     // Test pagination with OFFSET and all other features
     let builder = Order::agg_query()
-        .where_("status = {} AND amount > {}", &["active", "100"])
+        .where_("status = {} AND amount > {}", &[&"active", &100i64])
         .group_by("category")
         .sum_as("amount", "total")
         .having("total > {}", &[&500i64])
@@ -459,7 +459,7 @@ fn test_pagination_with_all_features() {
 fn test_chaining_preserves_state() {
     // Test that method chaining preserves the builder state correctly
     let builder = Order::agg_query()
-        .where_("status = {}", &["active"])
+        .where_("status = {}", &[&"active"])
         .group_by("category")
         .sum_as("amount", "total")
         .having("total > {}", &[&1000i64])
@@ -468,7 +468,7 @@ fn test_chaining_preserves_state() {
 
     let sql = builder.build();
     assert!(sql.contains("WHERE status = $1"));
-    assert!(sql.contains("GROUP BY category"));
+    assert!(sql.contains(r#"GROUP BY "category""#));
     assert!(sql.contains("HAVING total > $2"));
     assert!(sql.contains("ORDER BY total DESC"));
     assert!(sql.contains("LIMIT $3"));
@@ -630,6 +630,45 @@ async fn test_fetch_all_group_by(pool: sqlx::PgPool) -> Result<(), sqlx::Error>
     Ok(())
 }
 
+#[sqlx::test]
+async fn test_fetch_all_typed_where_and_having(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS test_order_status (
+            id VARCHAR PRIMARY KEY,
+            status VARCHAR NOT NULL,
+            amount INTEGER NOT NULL
+        )"
+    )
+    .execute(&pool)
+    .await?;
+
+    for (id, status, amount) in [("o1", "completed", 100), ("o2", "completed", 900), ("o3", "pending", 50)] {
+        sqlx::query("INSERT INTO test_order_status (id, status, amount) VALUES ($1, $2, $3)")
+            .bind(id)
+            .bind(status)
+            .bind(amount)
+            .execute(&pool)
+            .await?;
+    }
+
+    // Numeric parameters must be bound as numbers, not text, for these comparisons to work
+    let results: Vec<(String, i64)> = TestOrderStatus::agg_query()
+        .where_("amount > {}", &[&60i32])
+        .group_by("status")
+        .sum("amount")
+        .having("SUM(amount) > {}", &[&500i64])
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(results, vec![("completed".to_string(), 1000)]);
+
+    sqlx::query("DROP TABLE test_order_status")
+        .execute(&pool)
+        .await?;
+
+    Ok(())
+}
+
 #[sqlx::test]
 async fn test_fetch_optional(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
     // Create test table
@@ -872,3 +911,88 @@ struct TestPagination {
     category: String,
 }
 
+
+#[test]
+fn test_window_running_total() {
+    use sqlx_struct_enhanced::aggregate::window::{lag, sum};
+    use sqlx_struct_enhanced::{FrameBound, Order as SortOrder};
+
+    let sql = Order::agg_query()
+        .column("category")
+        .column("amount")
+        .window(
+            "running_total",
+            sum("amount")
+                .partition_by("category")
+                .order_by("id", SortOrder::Asc)
+                .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+        )
+        .window("previous", lag("amount", 1).partition_by("category").order_by("id", SortOrder::Asc))
+        .build();
+
+    assert!(sql.contains(r#"SELECT "category", "amount", SUM("amount") OVER (PARTITION BY "category" ORDER BY "id" ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS "running_total""#));
+    assert!(sql.contains(r#"LAG("amount", 1) OVER (PARTITION BY "category" ORDER BY "id" ASC) AS "previous""#));
+}
+
+#[derive(Debug, FromRow, sqlx_struct_enhanced::AggRow)]
+struct RevenueByCategory {
+    category: String,
+    total: rust_decimal::Decimal,
+    orders: Option<i64>,
+}
+
+#[test]
+fn test_agg_row_derive_infers_casts() {
+    use sqlx_struct_enhanced::{AggCast, AggRow};
+
+    assert_eq!(
+        RevenueByCategory::FIELDS,
+        &[
+            ("category", Some(AggCast::Text)),
+            ("total", Some(AggCast::Decimal)),
+            ("orders", Some(AggCast::Integer)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_all_as_rejects_unmatched_fields() {
+    // Never connects: the fields are checked before the query runs
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+    let result = Order::agg_query()
+        .group_by("category")
+        .sum_as("amount", "revenue")
+        .count_as("orders")
+        .fetch_all_as::<RevenueByCategory>(&pool)
+        .await;
+
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("field `total`")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+#[sqlx::test]
+async fn test_fetch_all_as(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("CREATE TABLE \"order\" (id TEXT PRIMARY KEY, category TEXT, amount INTEGER, status TEXT)")
+        .execute(&pool)
+        .await?;
+    sqlx::query("INSERT INTO \"order\" VALUES ('1', 'books', 10, 'paid'), ('2', 'books', 5, 'paid'), ('3', 'toys', 7, 'paid')")
+        .execute(&pool)
+        .await?;
+
+    let rows = Order::agg_query()
+        .group_by("category")
+        .sum_as("amount", "total")
+        .count_as("orders")
+        .order_by("category", "ASC")
+        .fetch_all_as::<RevenueByCategory>(&pool)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].category, "books");
+    assert_eq!(rows[0].total, rust_decimal::Decimal::from(15));
+    assert_eq!(rows[0].orders, Some(2));
+    Ok(())
+}
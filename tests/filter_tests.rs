@@ -0,0 +1,157 @@
+// Tests for the filter expression DSL
+// Column constants generated by the derive, composed into WHERE clauses
+
+use sqlx_struct_enhanced::{EnhancedCrud, Expr};
+use sqlx::{Execute, FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "filter_users"]
+struct FilterUser {
+    id: String,
+    email: String,
+    age: i32,
+    role: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "filter_orders"]
+struct FilterOrder {
+    id: String,
+    user_id: String,
+    amount: i32,
+    status: String,
+}
+
+#[test]
+fn test_generated_column_constants() {
+    assert_eq!(FilterUser::EMAIL.name(), "email");
+    assert_eq!(FilterUser::DELETED_AT.name(), "deleted_at");
+    assert_eq!(FilterUser::AGE.table(), "filter_users");
+}
+
+#[test]
+fn test_where_query_with_expression() {
+    let roles = vec!["admin".to_string(), "owner".to_string()];
+    let query = FilterUser::where_query(
+        FilterUser::EMAIL.eq("alice@example.com")
+            .and(FilterUser::AGE.gt(18))
+            .or(FilterUser::ROLE.in_(roles)),
+    );
+
+    assert_eq!(
+        query.sql(),
+        r#"SELECT "id", "email", "age", "role", "deleted_at" FROM "filter_users" WHERE "email" = $1 AND "age" > $2 OR "role" IN ($3, $4)"#
+    );
+}
+
+#[test]
+fn test_where_query_still_accepts_strings() {
+    let query = FilterUser::where_query("age > {}");
+    assert!(query.sql().ends_with("WHERE age > $1"));
+}
+
+#[test]
+fn test_count_and_delete_with_expression() {
+    let count = FilterUser::count_query(FilterUser::DELETED_AT.is_null().and(FilterUser::AGE.between(18, 30)));
+    assert!(count.sql().ends_with(r#"WHERE "deleted_at" IS NULL AND "age" BETWEEN $1 AND $2"#));
+
+    let delete = FilterUser::delete_where_query(!FilterUser::EMAIL.like("%@spam.test"));
+    assert!(delete.sql().ends_with(r#"WHERE NOT ("email" LIKE $1)"#));
+}
+
+#[test]
+fn test_agg_query_filter() {
+    let builder = FilterOrder::agg_query()
+        .filter(FilterOrder::STATUS.eq("paid").and(FilterOrder::AMOUNT.ge(100)))
+        .group_by("user_id")
+        .sum_as("amount", "total")
+        .having("SUM(amount) > {}", &[&1000i64]);
+
+    let sql = builder.build();
+    assert!(sql.contains(r#"WHERE "status" = $1 AND "amount" >= $2 GROUP BY "user_id""#));
+    assert!(sql.contains("HAVING SUM(amount) > $3"));
+}
+
+#[test]
+fn test_join_query_filter_compiles() {
+    let expr: Expr<Postgres> = FilterOrder::STATUS.qualified().ne("cancelled");
+    assert_eq!(expr.sql(), r#""filter_orders"."status" <> {}"#);
+
+    let _builder = FilterOrder::join_inner::<FilterUser>("filter_orders.user_id = filter_users.id")
+        .filter(expr);
+}
+
+#[test]
+fn test_in_subquery_continues_placeholder_numbering() {
+    let paid = FilterOrder::select()
+        .filter(FilterOrder::STATUS.eq("paid"))
+        .select_column(FilterOrder::USER_ID);
+    let query = FilterUser::where_query(FilterUser::AGE.gt(18).and(FilterUser::ID.in_subquery(paid)));
+
+    assert!(query.sql().ends_with(
+        r#"WHERE "age" > $1 AND "id" IN (SELECT "user_id" FROM "filter_orders" WHERE "status" = $2)"#
+    ));
+}
+
+#[test]
+fn test_exists_with_correlated_subquery() {
+    let (sql, params) = FilterUser::select()
+        .filter(Expr::exists(
+            FilterOrder::select()
+                .select_column(FilterOrder::ID)
+                .filter(
+                    FilterOrder::USER_ID.qualified().eq_column(FilterUser::ID.qualified())
+                        .and(FilterOrder::AMOUNT.gt(100)),
+                )
+                .limit(1),
+        ))
+        .filter(Expr::not_exists(
+            FilterOrder::select().select_column(FilterOrder::ID).filter(FilterOrder::STATUS.eq("refunded")),
+        ))
+        .limit(10)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        concat!(
+            r#"SELECT "id", "email", "age", "role", "deleted_at" FROM "filter_users" WHERE EXISTS (SELECT "id" FROM "filter_orders""#,
+            r#" WHERE "filter_orders"."user_id" = "filter_users"."id" AND "amount" > $1 LIMIT $2)"#,
+            r#" AND NOT EXISTS (SELECT "id" FROM "filter_orders" WHERE "status" = $3) LIMIT $4"#,
+        )
+    );
+    // The subquery's LIMIT is bound with its filter values; the outer LIMIT by fetch
+    assert_eq!(params.len(), 3);
+}
+
+#[test]
+fn test_aggregate_subqueries() {
+    let big_spenders = FilterOrder::agg_query()
+        .group_by("user_id")
+        .where_("status = {}", &[&"paid"])
+        .having("SUM(amount) > {}", &[&1000i64]);
+    let sql = FilterUser::select()
+        .filter(FilterUser::ROLE.eq("customer").and(FilterUser::ID.in_subquery(big_spenders)))
+        .build()
+        .unwrap()
+        .0;
+    assert!(sql.ends_with(
+        r#"WHERE "role" = $1 AND "id" IN (SELECT "user_id" FROM filter_orders WHERE status = $2 GROUP BY "user_id" HAVING SUM(amount) > $3)"#
+    ));
+
+    let per_user = FilterOrder::agg_query()
+        .group_by("user_id")
+        .where_("status = {}", &[&"paid"])
+        .count_as("orders");
+    let sql = sqlx_struct_enhanced::AggQueryBuilder::<Postgres>::from_subquery("per_user", per_user)
+        .avg("orders")
+        .where_("orders > {}", &[&1i64])
+        .build();
+    assert_eq!(
+        sql,
+        r#"SELECT AVG("orders") FROM (SELECT "user_id", COUNT(*) AS orders FROM filter_orders WHERE status = $1 GROUP BY "user_id") AS "per_user" WHERE orders > $2"#
+    );
+}
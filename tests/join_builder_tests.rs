@@ -0,0 +1,293 @@
+// Tests for the entity-tuple JoinQueryBuilder
+// Covers ON clauses inferred from #[crud(references(..))] foreign keys and
+// joins over three to five entities, ordering, limits, pagination and aliases
+
+use sqlx_struct_enhanced::EnhancedCrud;
+use sqlx_struct_enhanced::filter::{Nulls, Order};
+use sqlx_struct_enhanced::join::{ForeignKeyJoin, JoinTuple2, JoinTuple3, JoinTuple5};
+use sqlx_struct_enhanced::pagination::Page;
+use sqlx::{Execute, FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_customers"]
+struct JbCustomer {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_orders"]
+struct JbOrder {
+    pub id: String,
+    #[crud(references(JbCustomer))]
+    pub customer_id: String,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_addresses"]
+struct JbAddress {
+    pub id: String,
+    pub customer_id: String,
+    pub region_id: String,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_regions"]
+struct JbRegion {
+    pub id: String,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_order_lines"]
+struct JbOrderLine {
+    pub id: String,
+    pub order_id: String,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_employees"]
+struct JbEmployee {
+    pub id: String,
+    pub name: String,
+    pub manager_id: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, EnhancedCrud)]
+#[table_name = "jb_shipments"]
+struct JbShipment {
+    pub id: String,
+    pub origin_id: String,
+    pub destination_id: String,
+}
+
+#[test]
+fn test_foreign_key_condition_from_referencing_side() {
+    assert_eq!(
+        <JbOrder as ForeignKeyJoin<JbCustomer>>::join_condition(),
+        r#""jb_orders"."customer_id" = "jb_customers"."id""#
+    );
+}
+
+#[test]
+fn test_foreign_key_condition_from_referenced_side() {
+    assert_eq!(
+        <JbCustomer as ForeignKeyJoin<JbOrder>>::join_condition(),
+        r#""jb_customers"."id" = "jb_orders"."customer_id""#
+    );
+}
+
+#[test]
+fn test_references_field_is_still_a_column() {
+    assert!(JbOrder::by_pk().sql().contains(r#""customer_id""#));
+}
+
+#[tokio::test]
+async fn test_join_fk_builders_accept_pool() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    drop(JbOrder::join_inner_fk::<JbCustomer>().fetch_all(&pool));
+    drop(JbCustomer::join_left_fk::<JbOrder>().fetch_all(&pool));
+    drop(JbOrder::join_right_fk::<JbCustomer>().fetch_optional(&pool));
+    drop(JbOrder::join_full_fk::<JbCustomer>().fetch_one(&pool));
+}
+
+#[test]
+fn test_three_entity_join_sql() {
+    let sql = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .sql();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"SELECT "jb_orders"."id" AS "jb_orders.id", "jb_orders"."customer_id" AS "jb_orders.customer_id", "jb_orders"."amount" AS "jb_orders.amount", "#,
+            r#""jb_customers"."id" AS "jb_customers.id", "jb_customers"."name" AS "jb_customers.name", "#,
+            r#""jb_addresses"."id" AS "jb_addresses.id", "jb_addresses"."customer_id" AS "jb_addresses.customer_id", "jb_addresses"."region_id" AS "jb_addresses.region_id" "#,
+            r#"FROM "jb_orders" INNER JOIN "jb_customers" ON jb_orders.customer_id = jb_customers.id "#,
+            r#"LEFT JOIN "jb_addresses" ON jb_addresses.customer_id = jb_customers.id"#,
+        )
+    );
+}
+
+#[test]
+fn test_five_entity_join_with_filter() {
+    let sql = JbOrder::join_inner_fk::<JbCustomer>()
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .join_inner::<JbRegion>("jb_regions.id = jb_addresses.region_id")
+        .join_left::<JbOrderLine>("jb_order_lines.order_id = jb_orders.id")
+        .filter(JbRegion::CODE.qualified().eq("EU"))
+        .sql();
+    assert!(sql.contains(r#""jb_order_lines"."order_id" AS "jb_order_lines.order_id""#));
+    assert!(sql.ends_with(concat!(
+        r#"FROM "jb_orders" INNER JOIN "jb_customers" ON "jb_orders"."customer_id" = "jb_customers"."id" "#,
+        r#"LEFT JOIN "jb_addresses" ON jb_addresses.customer_id = jb_customers.id "#,
+        r#"INNER JOIN "jb_regions" ON jb_regions.id = jb_addresses.region_id "#,
+        r#"LEFT JOIN "jb_order_lines" ON jb_order_lines.order_id = jb_orders.id "#,
+        r#"WHERE "jb_regions"."code" = $1"#,
+    )));
+}
+
+#[test]
+fn test_join_chain_cache_keys_include_conditions() {
+    let by_customer = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .join_inner::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .sql();
+    let by_id = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .join_inner::<JbAddress>("jb_addresses.id = jb_customers.id")
+        .sql();
+    assert_ne!(by_customer, by_id);
+}
+
+#[tokio::test]
+async fn test_multi_join_decodes_into_tuples() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let three = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .fetch_all(&pool);
+    let _: &dyn std::future::Future<Output = Result<Vec<JoinTuple3<JbOrder, JbCustomer, JbAddress>>, sqlx::Error>> = &three;
+    let five = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .join_inner::<JbRegion>("jb_regions.id = jb_addresses.region_id")
+        .join_full::<JbOrderLine>("jb_order_lines.order_id = jb_orders.id")
+        .fetch_optional(&pool);
+    let _: &dyn std::future::Future<Output = Result<Option<JoinTuple5<JbOrder, JbCustomer, JbAddress, JbRegion, JbOrderLine>>, sqlx::Error>> = &five;
+}
+
+#[test]
+fn test_join_order_limit_offset_sql() {
+    let sql = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .where_("jb_orders.amount >= {}", &[])
+        .bind_proxy(100)
+        .order_by(JbCustomer::NAME, Order::Asc)
+        .order_by_nulls(JbOrder::AMOUNT, Order::Desc, Nulls::Last)
+        .limit(20)
+        .offset(40)
+        .sql();
+    assert!(sql.ends_with(concat!(
+        r#"WHERE jb_orders.amount >= $1 "#,
+        r#"ORDER BY "jb_customers"."name" ASC, "jb_orders"."amount" DESC NULLS LAST "#,
+        r#"LIMIT $2 OFFSET $3"#,
+    )));
+}
+
+#[test]
+fn test_join_cache_keys_include_every_clause() {
+    let base = || JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id");
+    let plain = base().sql();
+    let other_condition = JbOrder::join_inner::<JbCustomer>("jb_orders.id = jb_customers.id").sql();
+    let ordered = base().order_by(JbOrder::AMOUNT, Order::Asc).sql();
+    let descending = base().order_by(JbOrder::AMOUNT, Order::Desc).sql();
+    let limited = base().limit(5).sql();
+    let offset = base().offset(5).sql();
+    let all = [plain, other_condition, ordered, descending, limited, offset];
+    for (i, a) in all.iter().enumerate() {
+        for b in &all[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+    assert!(offset.ends_with(" OFFSET $1"));
+}
+
+#[test]
+fn test_join_limit_follows_chained_joins() {
+    let sql = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .limit(10)
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .filter(JbAddress::REGION_ID.qualified().eq("r1"))
+        .sql();
+    assert!(sql.ends_with(r#"WHERE "jb_addresses"."region_id" = $1 LIMIT $2"#));
+}
+
+#[test]
+fn test_join_paginate_sql_carries_total() {
+    let paginate = JbOrder::join_inner::<JbCustomer>("jb_orders.customer_id = jb_customers.id")
+        .order_by(JbOrder::ID, Order::Asc)
+        .paginate(3, 25);
+    let sql = paginate.sql();
+    assert!(sql.contains(r#""jb_customers"."name" AS "jb_customers.name", COUNT(*) OVER() AS "__total" FROM"#));
+    assert!(sql.ends_with(r#"ORDER BY "jb_orders"."id" ASC LIMIT $1 OFFSET $2"#));
+}
+
+#[tokio::test]
+async fn test_join_paginate_and_typed_binds_accept_pool() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let page = JbOrder::join_inner_fk::<JbCustomer>()
+        .where_("jb_orders.amount > {}", &[])
+        .bind_proxy(10i32)
+        .paginate(1, 10)
+        .fetch(&pool);
+    let _: &dyn std::future::Future<Output = Result<Page<JoinTuple2<JbOrder, JbCustomer>>, sqlx::Error>> = &page;
+    drop(JbOrder::join_inner_fk::<JbCustomer>()
+        .join_left::<JbAddress>("jb_addresses.customer_id = jb_customers.id")
+        .order_by(JbAddress::ID, Order::Desc)
+        .paginate(2, 10)
+        .fetch(&pool));
+}
+
+#[test]
+fn test_self_join_qualifies_columns_by_alias() {
+    let sql = JbEmployee::alias("e")
+        .join_left::<JbEmployee>("m", r#""e"."manager_id" = "m"."id""#)
+        .order_by(JbEmployee::NAME.aliased("m"), Order::Asc)
+        .sql();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"SELECT "e"."id" AS "e.id", "e"."name" AS "e.name", "e"."manager_id" AS "e.manager_id", "#,
+            r#""m"."id" AS "m.id", "m"."name" AS "m.name", "m"."manager_id" AS "m.manager_id" "#,
+            r#"FROM "jb_employees" AS "e" LEFT JOIN "jb_employees" AS "m" ON "e"."manager_id" = "m"."id" "#,
+            r#"ORDER BY "m"."name" ASC"#,
+        )
+    );
+}
+
+#[test]
+fn test_same_table_joined_in_two_roles() {
+    let sql = JbShipment::join_inner::<JbAddress>(r#""jb_addresses"."id" = "jb_shipments"."origin_id""#)
+        .join_inner_as::<JbAddress>("dest", r#""dest"."id" = "jb_shipments"."destination_id""#)
+        .filter(JbAddress::REGION_ID.aliased("dest").eq("r1"))
+        .sql();
+    assert!(sql.contains(r#""jb_addresses"."region_id" AS "jb_addresses.region_id", "dest"."id" AS "dest.id""#));
+    assert!(sql.ends_with(concat!(
+        r#"FROM "jb_shipments" INNER JOIN "jb_addresses" ON "jb_addresses"."id" = "jb_shipments"."origin_id" "#,
+        r#"INNER JOIN "jb_addresses" AS "dest" ON "dest"."id" = "jb_shipments"."destination_id" "#,
+        r#"WHERE "dest"."region_id" = $1"#,
+    )));
+}
+
+#[test]
+fn test_join_cache_keys_include_aliases() {
+    let as_m = JbEmployee::alias("e").join_left::<JbEmployee>("m", "e.manager_id = m.id").sql();
+    let as_b = JbEmployee::alias("e").join_left::<JbEmployee>("b", "e.manager_id = m.id").sql();
+    assert_ne!(as_m, as_b);
+}
+
+#[tokio::test]
+async fn test_aliased_joins_accept_pool() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let managers = JbEmployee::alias("e")
+        .join_left::<JbEmployee>("m", r#""e"."manager_id" = "m"."id""#)
+        .fetch_all(&pool);
+    let _: &dyn std::future::Future<Output = Result<Vec<JoinTuple2<JbEmployee, JbEmployee>>, sqlx::Error>> = &managers;
+    drop(JbEmployee::alias("e")
+        .join_left::<JbEmployee>("m", r#""e"."manager_id" = "m"."id""#)
+        .join_left_as::<JbEmployee>("gm", r#""m"."manager_id" = "gm"."id""#)
+        .paginate(1, 20)
+        .fetch(&pool));
+}
+
+#[tokio::test]
+async fn test_fetch_grouped_returns_parents_with_children() {
+    // Only builds the future; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let grouped = JbOrder::join_left::<JbOrderLine>(r#""jb_order_lines"."order_id" = "jb_orders"."id""#)
+        .order_by(JbOrder::ID, Order::Asc)
+        .fetch_grouped(&pool);
+    let _: &dyn std::future::Future<Output = Result<Vec<(JbOrder, Vec<JbOrderLine>)>, sqlx::Error>> = &grouped;
+}
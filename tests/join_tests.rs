@@ -43,7 +43,7 @@ fn test_inner_join() {
         .sum("order.amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT SUM(order.amount) FROM order INNER JOIN customer ON order.customer_id = customer.id"));
+    assert!(sql.contains(r#"SELECT SUM("order"."amount") FROM order INNER JOIN customer ON order.customer_id = customer.id"#));
 }
 
 #[test]
@@ -88,7 +88,7 @@ fn test_join_with_single_group_by() {
         .sum("order.amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT customer.region, SUM(order.amount) FROM order INNER JOIN customer ON order.customer_id = customer.id GROUP BY customer.region"));
+    assert!(sql.contains(r#"SELECT "customer"."region", SUM("order"."amount") FROM order INNER JOIN customer ON order.customer_id = customer.id GROUP BY "customer"."region""#));
 }
 
 #[test]
@@ -103,7 +103,7 @@ fn test_join_with_multiple_group_by() {
     let sql = builder.build();
     assert!(sql.contains("INNER JOIN customer"));
     assert!(sql.contains("INNER JOIN product"));
-    assert!(sql.contains("GROUP BY customer.region, product.category"));
+    assert!(sql.contains(r#"GROUP BY "customer"."region", "product"."category""#));
 }
 
 #[test]
@@ -116,7 +116,7 @@ fn test_join_with_multiple_aggregates() {
         .count_as("count");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT customer.region, SUM(order.amount) AS total, AVG(order.amount) AS average, COUNT(*) AS count"));
+    assert!(sql.contains(r#"SELECT "customer"."region", SUM("order"."amount") AS total, AVG("order"."amount") AS average, COUNT(*) AS count"#));
 }
 
 // ============================================================================
@@ -127,7 +127,7 @@ fn test_join_with_multiple_aggregates() {
 fn test_join_with_where() {
     let builder = Order::agg_query()
         .join("customer", "order.customer_id = customer.id")
-        .where_("customer.status = {}", &["active"])
+        .where_("customer.status = {}", &[&"active"])
         .group_by("customer.region")
         .sum("order.amount");
 
@@ -139,7 +139,7 @@ fn test_join_with_where() {
 fn test_join_with_complex_where() {
     let builder = Order::agg_query()
         .join("customer", "order.customer_id = customer.id")
-        .where_("customer.status = {} AND order.amount > {}", &["active", "100"])
+        .where_("customer.status = {} AND order.amount > {}", &[&"active", &100i64])
         .group_by("customer.region")
         .sum("order.amount");
 
@@ -167,7 +167,7 @@ fn test_join_with_having() {
 fn test_join_with_where_and_having() {
     let builder = Order::agg_query()
         .join("customer", "order.customer_id = customer.id")
-        .where_("customer.status = {}", &["active"])
+        .where_("customer.status = {}", &[&"active"])
         .group_by("customer.region")
         .sum_as("order.amount", "total")
         .having("total > {}", &[&500i64]);
@@ -256,7 +256,7 @@ fn test_complex_join_query() {
     let builder = Order::agg_query()
         .join("customer", "order.customer_id = customer.id")
         .join_left("product", "order.product_id = product.id")
-        .where_("customer.status = {} AND order.amount > {}", &["active", "100"])
+        .where_("customer.status = {} AND order.amount > {}", &[&"active", &100i64])
         .group_by("customer.region")
         .group_by("product.category")
         .sum_as("order.amount", "total")
@@ -269,7 +269,7 @@ fn test_complex_join_query() {
     assert!(sql.contains("INNER JOIN customer"));
     assert!(sql.contains("LEFT JOIN product"));
     assert!(sql.contains("WHERE customer.status = $1 AND order.amount > $2"));
-    assert!(sql.contains("GROUP BY customer.region, product.category"));
+    assert!(sql.contains(r#"GROUP BY "customer"."region", "product"."category""#));
     assert!(sql.contains("HAVING total > $3"));
     assert!(sql.contains("ORDER BY total DESC"));
     assert!(sql.contains("LIMIT $4"));
@@ -288,7 +288,7 @@ fn test_three_way_join() {
     let sql = builder.build();
     assert!(sql.contains("INNER JOIN customer"));
     assert!(sql.contains("INNER JOIN product"));
-    assert!(sql.contains("GROUP BY customer.region, product.category"));
+    assert!(sql.contains(r#"GROUP BY "customer"."region", "product"."category""#));
 }
 
 #[test]
@@ -303,11 +303,11 @@ fn test_join_with_all_aggregates() {
         .max_as("order.amount", "maximum");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT customer.region, SUM(order.amount) AS total"));
-    assert!(sql.contains("AVG(order.amount) AS average"));
+    assert!(sql.contains(r#"SELECT "customer"."region", SUM("order"."amount") AS total"#));
+    assert!(sql.contains(r#"AVG("order"."amount") AS average"#));
     assert!(sql.contains("COUNT(*) AS count"));
-    assert!(sql.contains("MIN(order.amount) AS minimum"));
-    assert!(sql.contains("MAX(order.amount) AS maximum"));
+    assert!(sql.contains(r#"MIN("order"."amount") AS minimum"#));
+    assert!(sql.contains(r#"MAX("order"."amount") AS maximum"#));
 }
 
 // ============================================================================
@@ -392,7 +392,7 @@ fn test_join_without_group_by() {
         .sum("order.amount");
 
     let sql = builder.build();
-    assert!(sql.contains("SELECT SUM(order.amount) FROM order INNER JOIN customer"));
+    assert!(sql.contains(r#"SELECT SUM("order"."amount") FROM order INNER JOIN customer"#));
     assert!(!sql.contains("GROUP BY"));
 }
 
@@ -417,7 +417,7 @@ fn test_sales_by_region_with_customer_info() {
     // Typical business intelligence query
     let builder = Order::agg_query()
         .join("customer", "order.customer_id = customer.id")
-        .where_("customer.status = {} AND order.status = {}", &["active", "completed"])
+        .where_("customer.status = {} AND order.status = {}", &[&"active", &"completed"])
         .group_by("customer.region")
         .sum_as("order.amount", "total_sales")
         .count_as("order_count")
@@ -427,7 +427,7 @@ fn test_sales_by_region_with_customer_info() {
 
     let sql = builder.build();
     assert!(sql.contains("WHERE customer.status = $1 AND order.status = $2"));
-    assert!(sql.contains("GROUP BY customer.region"));
+    assert!(sql.contains(r#"GROUP BY "customer"."region""#));
     assert!(sql.contains("ORDER BY total_sales DESC"));
     assert!(sql.contains("LIMIT $3"));
 }
@@ -437,7 +437,7 @@ fn test_product_sales_by_category() {
     // Product category analysis
     let builder = Order::agg_query()
         .join("product", "order.product_id = product.id")
-        .where_("order.status = {}", &["completed"])
+        .where_("order.status = {}", &[&"completed"])
         .group_by("product.category")
         .sum_as("order.amount", "revenue")
         .count_as("units_sold")
@@ -447,7 +447,7 @@ fn test_product_sales_by_category() {
     let sql = builder.build();
     assert!(sql.contains("INNER JOIN product"));
     assert!(sql.contains("WHERE order.status = $1"));
-    assert!(sql.contains("GROUP BY product.category"));
+    assert!(sql.contains(r#"GROUP BY "product"."category""#));
     assert!(sql.contains("HAVING revenue > $2"));
     assert!(sql.contains("ORDER BY revenue DESC"));
 }
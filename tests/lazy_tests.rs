@@ -0,0 +1,120 @@
+// Tests for #[crud(lazy)] fields
+// Lazy columns are left out of default SELECTs and UPDATEs, and loaded with load_<field>
+
+use sqlx_struct_enhanced::EnhancedCrud;
+use sqlx_struct_enhanced::lazy::LazyDirty;
+use sqlx::{Execute, FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "lazy_documents"]
+struct LazyDocument {
+    id: String,
+    title: String,
+    #[crud(lazy)]
+    #[sqlx(default)]
+    body: Option<String>,
+    updated_at: String,
+    #[sqlx(skip)]
+    dirty: LazyDirty,
+}
+
+fn document(body: Option<String>) -> LazyDocument {
+    LazyDocument {
+        id: "doc-1".to_string(),
+        title: "Spec".to_string(),
+        body,
+        updated_at: "2024-01-01".to_string(),
+        dirty: LazyDirty::default(),
+    }
+}
+
+#[test]
+fn test_lazy_column_excluded_from_selects() {
+    let expected = r#"SELECT "id", "title", "updated_at" FROM "lazy_documents""#;
+    assert!(LazyDocument::by_pk().sql().starts_with(expected));
+    assert!(LazyDocument::where_query("title = {}").sql().starts_with(expected));
+    assert!(LazyDocument::bulk_select(&["a".to_string()]).sql().starts_with(expected));
+}
+
+#[test]
+fn test_update_skips_unset_lazy_column() {
+    let mut doc = document(None);
+    assert_eq!(
+        doc.update_bind().sql(),
+        r#"UPDATE "lazy_documents" SET "title"=$1,"updated_at"=$2 WHERE "id"=$3"#
+    );
+}
+
+#[test]
+fn test_update_skips_loaded_lazy_column() {
+    // A loaded (or fetched) body is Some but was never set
+    let mut doc = document(Some("# Spec".to_string()));
+    assert_eq!(
+        doc.update_bind().sql(),
+        r#"UPDATE "lazy_documents" SET "title"=$1,"updated_at"=$2 WHERE "id"=$3"#
+    );
+}
+
+#[test]
+fn test_update_writes_lazy_column_once_set() {
+    let mut doc = document(None);
+    doc.set_body(Some("# Spec".to_string()));
+    let expected = r#"UPDATE "lazy_documents" SET "title"=$1,"updated_at"=$2,"body"=$3 WHERE "id"=$4"#;
+    assert_eq!(doc.update_bind().sql(), expected);
+
+    // Building the update does not clear the mark; only a successful save does
+    assert_eq!(doc.update_bind().sql(), expected);
+}
+
+#[test]
+fn test_lazy_marks_belong_to_the_row() {
+    let mut fetched = document(Some("# Spec".to_string()));
+    let mut copy = fetched.clone();
+    copy.set_body(None);
+
+    // Setting the copy's body must not make the other copy write its own
+    assert_eq!(
+        fetched.update_bind().sql(),
+        r#"UPDATE "lazy_documents" SET "title"=$1,"updated_at"=$2 WHERE "id"=$3"#
+    );
+    assert!(copy.update_bind().sql().contains(r#""body"=$3"#));
+}
+
+#[test]
+fn test_update_clears_lazy_column_set_to_none() {
+    let mut doc = document(Some("# Spec".to_string()));
+    doc.set_body(None);
+    assert_eq!(
+        doc.update_bind().sql(),
+        r#"UPDATE "lazy_documents" SET "title"=$1,"updated_at"=$2,"body"=$3 WHERE "id"=$4"#
+    );
+}
+
+#[test]
+fn test_insert_keeps_lazy_column() {
+    let mut doc = document(Some("# Spec".to_string()));
+    assert!(doc.insert_bind().sql().contains(r#""body""#));
+}
+
+#[tokio::test]
+async fn test_load_lazy_column_accepts_executor() {
+    // Only builds the future; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let mut doc = document(None);
+    let load = doc.load_body(&pool);
+    drop(load);
+}
+
+#[tokio::test]
+async fn test_failed_save_keeps_lazy_mark() {
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_millis(200))
+        .connect_lazy("postgres://localhost:1/unused")
+        .unwrap();
+    let mut doc = document(None);
+    doc.set_body(Some("# Spec".to_string()));
+    assert!(doc.save(&pool).await.is_err());
+    assert!(doc.update_bind().sql().contains(r#""body"=$3"#));
+}
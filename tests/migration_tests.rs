@@ -5,6 +5,7 @@
 
 use sqlx::PgPool;
 use sqlx_struct_enhanced::migration::*;
+use sqlx_struct_enhanced::Dialect;
 
 #[sqlx::test]
 async fn test_migration_history_init(pool: PgPool) -> Result<(), MigrationError> {
@@ -383,9 +384,9 @@ async fn test_sql_generator_create_table(pool: PgPool) -> Result<(), MigrationEr
     let create_sql = generator.generate_create_table_sql(&table);
 
     // Verify SQL contains expected elements
-    assert!(create_sql.contains("CREATE TABLE users"));
-    assert!(create_sql.contains("id VARCHAR(36) NOT NULL"));
-    assert!(create_sql.contains("name VARCHAR(500) NOT NULL"));
+    assert!(create_sql.contains("CREATE TABLE \"users\""));
+    assert!(create_sql.contains("\"id\" VARCHAR(36) NOT NULL"));
+    assert!(create_sql.contains("\"name\" VARCHAR(500) NOT NULL"));
 
     Ok(())
 }
@@ -406,8 +407,8 @@ async fn test_sql_generator_add_column(pool: PgPool) -> Result<(), MigrationErro
     let add_sql = generator.generate_add_column_sql("users", &column);
 
     // Verify SQL
-    assert!(add_sql.contains("ALTER TABLE users"));
-    assert!(add_sql.contains("ADD COLUMN email"));
+    assert!(add_sql.contains("ALTER TABLE \"users\""));
+    assert!(add_sql.contains("ADD COLUMN \"email\""));
     assert!(add_sql.contains("VARCHAR(500)"));
 
     Ok(())
@@ -429,7 +430,7 @@ async fn test_sql_generator_create_index(pool: PgPool) -> Result<(), MigrationEr
     // Verify SQL
     assert!(create_sql.contains("CREATE UNIQUE INDEX"));
     assert!(create_sql.contains("idx_users_email"));
-    assert!(create_sql.contains("ON users"));
+    assert!(create_sql.contains("ON \"users\""));
     assert!(create_sql.contains("email"));
 
     Ok(())
@@ -442,12 +443,73 @@ async fn test_sql_generator_rename_table(pool: PgPool) -> Result<(), MigrationEr
     let rename_sql = generator.generate_rename_table_sql("old_users", "users");
 
     // Verify SQL
-    assert!(rename_sql.contains("ALTER TABLE old_users"));
-    assert!(rename_sql.contains("RENAME TO users"));
+    assert!(rename_sql.contains("ALTER TABLE \"old_users\""));
+    assert!(rename_sql.contains("RENAME TO \"users\""));
 
     Ok(())
 }
 
+#[test]
+fn test_sql_generator_dialects() {
+    let index = IndexDef {
+        name: "idx_users_email".to_string(),
+        columns: vec!["email".to_string()],
+        unique: false,
+        index_type: "btree".to_string(),
+    };
+    let cases = [
+        (
+            SqlGenerator::new(Dialect::PostgreSQL),
+            r#"ALTER TABLE "old_users" RENAME TO "users";"#,
+            r#"CREATE INDEX IF NOT EXISTS "idx_users_email" ON "users" USING btree ("email");"#,
+        ),
+        (
+            SqlGenerator::new(Dialect::MySQL),
+            "RENAME TABLE `old_users` TO `users`;",
+            "CREATE INDEX `idx_users_email` ON `users` (`email`);",
+        ),
+        (
+            SqlGenerator::new(Dialect::SQLite),
+            "ALTER TABLE old_users RENAME TO users;",
+            "CREATE INDEX IF NOT EXISTS idx_users_email ON users (email);",
+        ),
+    ];
+    for (generator, rename, create_index) in cases {
+        assert_eq!(generator.generate_rename_table_sql("old_users", "users"), rename);
+        assert_eq!(generator.generate_create_index_sql("users", &index), create_index);
+    }
+    assert_eq!(SqlGenerator::default().dialect, Dialect::PostgreSQL);
+}
+
+#[test]
+fn test_sql_generator_quotes_identifiers() {
+    let generator = SqlGenerator::new(Dialect::PostgreSQL);
+    let index = IndexDef {
+        name: "idx_orders_user".to_string(),
+        columns: vec!["user".to_string(), "lower(email)".to_string()],
+        unique: false,
+        index_type: "btree".to_string(),
+    };
+
+    assert_eq!(generator.generate_drop_table_sql("order"), r#"DROP TABLE IF EXISTS "order";"#);
+    assert_eq!(
+        generator.generate_update_sql("order", "group", "'a'"),
+        r#"UPDATE "order" SET "group" = 'a';"#
+    );
+    assert_eq!(
+        generator.generate_create_index_sql("order", &index),
+        r#"CREATE INDEX IF NOT EXISTS "idx_orders_user" ON "order" USING btree ("user", lower(email));"#
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_sql_generator_database_type() {
+    assert_eq!(SqlGenerator::new_postgres().database_type(), "postgres");
+    assert_eq!(SqlGenerator::new_mysql().database_type(), "mysql");
+    assert_eq!(SqlGenerator::new_sqlite().database_type(), "sqlite");
+}
+
 #[sqlx::test]
 async fn test_migration_creation(pool: PgPool) -> Result<(), MigrationError> {
     // Create a simple migration
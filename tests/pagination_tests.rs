@@ -0,0 +1,237 @@
+// Tests for offset and keyset pagination
+// SQL generation for EnhancedCrud::paginate / EnhancedCrud::after, Page totals and cursors
+
+use sqlx_struct_enhanced::{BindValue, Column, Cursor, EnhancedCrud, Order, Page};
+use sqlx::{FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "paged_users"]
+struct PagedUser {
+    id: String,
+    name: String,
+    status: String,
+}
+
+#[test]
+fn test_paginate_with_string_filter() {
+    let query = PagedUser::paginate("status = {}", 2, 20).bind_proxy("active");
+    assert_eq!(
+        query.sql(),
+        r#"SELECT "id", "name", "status", COUNT(*) OVER() AS "__total" FROM "paged_users" WHERE status = $1 ORDER BY "id" LIMIT $2 OFFSET $3"#
+    );
+    assert_eq!(query.params().len(), 1);
+}
+
+#[test]
+fn test_paginate_binds_in_placeholder_order() {
+    let query = PagedUser::paginate("status = {} AND name LIKE {} AND length(id) > {}", 1, 10)
+        .bind_proxy("active")
+        .bind_proxy("A%")
+        .bind_proxy(3i64);
+    assert!(query.sql().contains("WHERE status = $1 AND name LIKE $2 AND length(id) > $3 ORDER BY"));
+    match query.params() {
+        [BindValue::String(status), BindValue::String(name), BindValue::I64(3)] => {
+            assert_eq!(status, "active");
+            assert_eq!(name, "A%");
+        }
+        other => panic!("unexpected binds {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_rejects_unbound_placeholders() {
+    // Never connects: the binds are checked before the query runs
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+    let result = PagedUser::paginate("status = {}", 1, 20).fetch(&pool).await;
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("1 placeholders but 0 bound values")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+// Compile check: pages can be read inside a transaction
+#[allow(dead_code)]
+async fn paginate_in_transaction(tx: &mut sqlx::Transaction<'_, Postgres>) -> Result<Page<PagedUser>, sqlx::Error> {
+    PagedUser::paginate(PagedUser::STATUS.eq("active"), 1, 20).fetch(&mut **tx).await
+}
+
+#[test]
+fn test_paginate_with_expression_filter() {
+    let query = PagedUser::paginate(
+        PagedUser::STATUS.eq("active").and(PagedUser::NAME.like("A%")),
+        1,
+        10,
+    );
+    assert!(query.sql().contains(r#"WHERE "status" = $1 AND "name" LIKE $2 ORDER BY "id" LIMIT $3 OFFSET $4"#));
+}
+
+#[test]
+fn test_page_total_pages() {
+    let page = Page::new(vec!["a", "b"], 41, 5, 10);
+    assert_eq!(page.total_pages, 5);
+    assert!(!page.has_next());
+    assert!(page.has_prev());
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "keyset_events"]
+struct KeysetEvent {
+    id: i64,
+    kind: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[test]
+fn test_keyset_first_page_orders_by_primary_key() {
+    let (sql, params) = KeysetEvent::after(None).limit(50).build().unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "kind", "created_at", CAST("id" AS TEXT) AS "__cursor_0" FROM "keyset_events" WHERE 1=1 ORDER BY "id" ASC LIMIT $1"#
+    );
+    assert!(params.is_empty());
+}
+
+#[test]
+fn test_keyset_after_cursor_with_mixed_directions() {
+    // created_at = '2024-05-01', id = 42
+    let cursor = Cursor::decode("323032342d30352d3031.3432").unwrap();
+    let (sql, params) = KeysetEvent::after(cursor)
+        .order_by(KeysetEvent::CREATED_AT, Order::Desc)
+        .filter(KeysetEvent::KIND.eq("click"))
+        .limit(2)
+        .build()
+        .unwrap();
+
+    assert!(sql.contains(r#"CAST("created_at" AS TEXT) AS "__cursor_0", CAST("id" AS TEXT) AS "__cursor_1""#));
+    assert!(sql.contains(
+        r#"WHERE ("kind" = $1) AND (("created_at" < CAST($2 AS TIMESTAMPTZ)) OR ("created_at" = CAST($3 AS TIMESTAMPTZ) AND "id" > CAST($4 AS BIGINT)))"#
+    ));
+    assert!(sql.ends_with(r#"ORDER BY "created_at" DESC, "id" ASC LIMIT $5"#));
+    assert_eq!(params.len(), 4);
+}
+
+#[test]
+fn test_keyset_rejects_cursor_for_other_keys() {
+    let cursor = Cursor::decode("3432").unwrap();
+    let result = KeysetEvent::after(cursor)
+        .order_by(KeysetEvent::CREATED_AT, Order::Desc)
+        .build();
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+}
+
+#[test]
+fn test_keyset_filters_are_combined() {
+    let (sql, params) = KeysetEvent::after(None)
+        .filter(KeysetEvent::KIND.eq("click"))
+        .filter(KeysetEvent::ID.gt(10i64))
+        .build()
+        .unwrap();
+    assert!(sql.contains(r#"WHERE ("kind" = $1 AND "id" > $2) ORDER BY"#));
+    assert_eq!(params.len(), 2);
+}
+
+#[test]
+fn test_keyset_rejects_untyped_sort_key() {
+    let result = KeysetEvent::after(None)
+        .order_by(Column::new("keyset_events", "kind"), Order::Asc)
+        .build();
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("`kind` has no known column type")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    let typed = KeysetEvent::after(None).order_by(Column::new("keyset_events", "kind").typed("TEXT"), Order::Asc);
+    assert!(typed.build().is_ok());
+}
+
+#[test]
+fn test_keyset_reports_invalid_subquery() {
+    let mismatched = KeysetEvent::select()
+        .select_column(KeysetEvent::ID)
+        .union(KeysetEvent::select().select_column(KeysetEvent::KIND));
+    let result = KeysetEvent::after(None).filter(KeysetEvent::ID.in_subquery(mismatched)).build();
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().starts_with("invalid subquery: UNION")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cursor_encoding_is_reversible() {
+    let cursor = Cursor::decode("3432.6162").unwrap();
+    assert_eq!(cursor.encode(), "3432.6162");
+    assert_eq!(cursor.to_string(), cursor.encode());
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "keyset_products"]
+struct KeysetProduct {
+    id: String,
+    #[crud(decimal(precision = 10, scale = 2))]
+    #[crud(cast_as = "TEXT")]
+    price: Option<String>,
+}
+
+#[test]
+fn test_keyset_on_cast_as_key_compares_in_column_type() {
+    // price = '12.50', id = 'p1'
+    let cursor = Cursor::decode("31322e3530.7031").unwrap();
+    let (sql, _) = KeysetProduct::after(cursor)
+        .order_by(KeysetProduct::PRICE, Order::Asc)
+        .build()
+        .unwrap();
+
+    // The row is read as TEXT, but the cursor compares against NUMERIC
+    assert!(sql.starts_with(r#"SELECT "id", "price"::TEXT as "price", CAST("price" AS TEXT) AS "__cursor_0""#));
+    assert!(sql.contains(
+        r#"WHERE (("price" > CAST($1 AS NUMERIC)) OR ("price" = CAST($2 AS NUMERIC) AND "id" > CAST($3 AS TEXT)))"#
+    ));
+}
+
+#[test]
+fn test_chunked_scan_resumes_after_saved_key() {
+    let keyset = KeysetEvent::chunked(1000)
+        .filter(KeysetEvent::KIND.eq("click"))
+        .resume_after(42i64)
+        .into_keyset();
+    let (sql, params) = keyset.build().unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "kind", "created_at", CAST("id" AS TEXT) AS "__cursor_0" FROM "keyset_events" WHERE ("kind" = $1 AND "id" > $2) ORDER BY "id" ASC LIMIT $3"#
+    );
+    assert_eq!(params.len(), 2);
+
+    let (sql, _) = KeysetEvent::chunked(1000)
+        .filter(KeysetEvent::KIND.eq("click"))
+        .filter(KeysetEvent::CREATED_AT.is_not_null())
+        .into_keyset()
+        .build()
+        .unwrap();
+    assert!(sql.contains(r#"WHERE ("kind" = $1 AND "created_at" IS NOT NULL) ORDER BY"#));
+}
+
+#[tokio::test]
+async fn test_for_each_chunk_accepts_async_callback() {
+    // Only builds the future; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let scan = KeysetEvent::for_each_chunk(&pool, 500, |batch| async move {
+        assert!(batch.len() <= 500);
+        Ok::<_, sqlx::Error>(())
+    });
+    drop(scan);
+}
+
+#[tokio::test]
+async fn test_for_each_chunk_can_be_spawned() {
+    fn assert_send<F: std::future::Future + Send>(_: &F) {}
+
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let backfill = KeysetEvent::for_each_chunk(&pool, 500, |_batch| async move { Ok::<_, sqlx::Error>(()) });
+    assert_send(&backfill);
+    let filtered = KeysetEvent::chunked(500)
+        .filter(KeysetEvent::KIND.eq("click"))
+        .for_each_chunk(&pool, |_batch| async move { Ok::<_, sqlx::Error>(()) });
+    assert_send(&filtered);
+}
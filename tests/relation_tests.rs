@@ -0,0 +1,163 @@
+// Tests for #[crud(belongs_to(..))] and #[crud(has_many(..))] relations
+// Relation fields are not columns; preloading issues one IN query per relation
+
+use sqlx_struct_enhanced::EnhancedCrud;
+use sqlx_struct_enhanced::relation::{Entity, Through};
+use sqlx::{Execute, FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "rel_customers"]
+struct RelCustomer {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "rel_order_lines"]
+struct RelOrderLine {
+    id: String,
+    order_id: String,
+    sku: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "rel_orders"]
+struct RelOrder {
+    id: String,
+    customer_id: Option<String>,
+    status: String,
+    #[sqlx(skip)]
+    #[crud(belongs_to(RelCustomer, fk = "customer_id"))]
+    customer: Option<RelCustomer>,
+    #[sqlx(skip)]
+    #[crud(has_many(RelOrderLine, fk = "order_id"))]
+    lines: Vec<RelOrderLine>,
+}
+
+fn order(id: &str, customer_id: Option<&str>) -> RelOrder {
+    RelOrder {
+        id: id.to_string(),
+        customer_id: customer_id.map(str::to_string),
+        status: "open".to_string(),
+        customer: None,
+        lines: Vec::new(),
+    }
+}
+
+#[test]
+fn test_relation_fields_are_not_columns() {
+    let scheme = RelOrder::scheme();
+    assert_eq!(scheme.insert_fields, vec!["id", "customer_id", "status"]);
+    assert_eq!(
+        scheme.gen_select_by_id_sql_static(),
+        r#"SELECT "id", "customer_id", "status" FROM "rel_orders" WHERE "id"=$1"#
+    );
+
+    let mut o = order("o-1", Some("c-1"));
+    assert_eq!(
+        o.insert_bind().sql(),
+        r#"INSERT INTO "rel_orders" ("id","customer_id","status") VALUES ($1,$2,$3)"#
+    );
+}
+
+#[test]
+fn test_has_many_selects_children_by_foreign_key() {
+    assert_eq!(
+        RelOrderLine::scheme().gen_select_in_sql_static("order_id", 3),
+        r#"SELECT "id", "order_id", "sku" FROM "rel_order_lines" WHERE "order_id" IN ($1,$2,$3)"#
+    );
+}
+
+#[test]
+fn test_belongs_to_reuses_bulk_select() {
+    assert_eq!(
+        RelCustomer::scheme().gen_select_in_sql_static("id", 2),
+        RelCustomer::scheme().gen_bulk_select_sql_static(2)
+    );
+}
+
+#[test]
+fn test_entity_primary_key() {
+    let o = order("o-7", None);
+    assert_eq!(o.primary_key(), "o-7");
+}
+
+#[tokio::test]
+async fn test_relation_loaders_accept_executor() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let mut orders = vec![order("o-1", Some("c-1")), order("o-2", None)];
+    drop(RelOrder::preload_customers(&mut orders, &pool));
+    drop(RelOrder::preload_lines(&mut orders, &pool));
+    let mut single = order("o-3", Some("c-2"));
+    drop(single.load_customer(&pool));
+    drop(single.load_lines(&pool));
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "rel_tags"]
+struct RelTag {
+    id: String,
+    label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "rel_posts"]
+struct RelPost {
+    id: String,
+    title: String,
+    #[sqlx(skip)]
+    #[crud(many_to_many(RelTag, through = "rel_post_tags", left = "post_id", right = "tag_id"))]
+    tags: Vec<RelTag>,
+}
+
+fn post(id: &str) -> RelPost {
+    RelPost { id: id.to_string(), title: "Hello".to_string(), tags: Vec::new() }
+}
+
+#[test]
+fn test_many_to_many_join_table_sql() {
+    let through = Through::new("rel_post_tags", "post_id", "tag_id");
+    assert_eq!(
+        through.gen_attach_sql_static(2),
+        r#"INSERT INTO "rel_post_tags" ("post_id","tag_id") VALUES ($1,$2),($3,$4) ON CONFLICT DO NOTHING"#
+    );
+    assert_eq!(
+        through.gen_detach_sql_static(2),
+        r#"DELETE FROM "rel_post_tags" WHERE "post_id"=$1 AND "tag_id" IN ($2,$3)"#
+    );
+    assert_eq!(
+        through.gen_select_right_keys_sql_static(),
+        r#"SELECT "tag_id" FROM "rel_post_tags" WHERE "post_id"=$1"#
+    );
+}
+
+#[test]
+fn test_many_to_many_preload_sql() {
+    let through = Through::new("rel_post_tags", "post_id", "tag_id");
+    assert_eq!(
+        through.gen_preload_sql_static(RelTag::scheme(), 2),
+        r#"SELECT "id", "label", "__through"."__left" FROM "rel_tags" INNER JOIN (SELECT "post_id" AS "__left", "tag_id" AS "__right" FROM "rel_post_tags" WHERE "post_id" IN ($1,$2)) AS "__through" ON "rel_tags"."id"="__through"."__right""#
+    );
+}
+
+#[test]
+fn test_many_to_many_field_is_not_a_column() {
+    assert_eq!(RelPost::scheme().insert_fields, vec!["id", "title"]);
+}
+
+#[tokio::test]
+async fn test_many_to_many_methods_accept_executor() {
+    // Only builds the futures; connect_lazy never touches the network.
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let mut posts = vec![post("p-1"), post("p-2")];
+    drop(RelPost::preload_tags(&mut posts, &pool));
+    let mut single = post("p-3");
+    let keys = vec!["t-1".to_string(), "t-2".to_string()];
+    drop(single.attach_tags(&pool, &keys));
+    drop(single.detach_tags(&pool, &keys));
+    drop(single.sync_tags(&pool, &keys));
+    drop(single.load_tags(&pool));
+}
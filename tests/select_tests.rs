@@ -0,0 +1,289 @@
+// Tests for the fluent select builder
+// SQL generation for EnhancedCrud::select / select_as: ordering, limits, DISTINCT, locking,
+// pagination and projections
+
+use sqlx_struct_enhanced::{EnhancedCrud, Nulls, Order, Projection};
+use sqlx::{FromRow, Postgres, query::Query, query::QueryAs};
+use sqlx::database::HasArguments;
+use sqlx::Row;
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "select_jobs"]
+struct SelectJob {
+    id: i64,
+    state: String,
+    priority: Option<i32>,
+}
+
+#[test]
+fn test_select_without_clauses() {
+    let (sql, params) = SelectJob::select().build().unwrap();
+    assert_eq!(sql, r#"SELECT "id", "state", "priority" FROM "select_jobs""#);
+    assert!(params.is_empty());
+}
+
+#[test]
+fn test_select_with_order_limit_offset() {
+    let (sql, params) = SelectJob::select()
+        .filter(SelectJob::STATE.eq("pending"))
+        .order_by_nulls(SelectJob::PRIORITY, Order::Desc, Nulls::Last)
+        .order_by(SelectJob::ID, Order::Asc)
+        .limit(10)
+        .offset(20)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "state", "priority" FROM "select_jobs" WHERE "state" = $1 ORDER BY "priority" DESC NULLS LAST, "id" ASC LIMIT $2 OFFSET $3"#
+    );
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn test_select_distinct() {
+    let (sql, _) = SelectJob::select().distinct().build().unwrap();
+    assert!(sql.starts_with(r#"SELECT DISTINCT "id", "state", "priority""#));
+}
+
+#[test]
+fn test_select_skip_locked() {
+    let (sql, _) = SelectJob::select()
+        .filter(SelectJob::STATE.eq("pending"))
+        .order_by(SelectJob::ID, Order::Asc)
+        .limit(5)
+        .skip_locked()
+        .build()
+        .unwrap();
+    assert!(sql.ends_with(r#"ORDER BY "id" ASC LIMIT $2 FOR UPDATE SKIP LOCKED"#));
+
+    let (sql, _) = SelectJob::select().for_update().build().unwrap();
+    assert!(sql.ends_with(r#"FROM "select_jobs" FOR UPDATE"#));
+}
+
+#[test]
+fn test_select_filters_are_combined() {
+    let (sql, params) = SelectJob::select()
+        .filter(SelectJob::STATE.eq("pending"))
+        .filter(SelectJob::PRIORITY.gt(3))
+        .build()
+        .unwrap();
+    assert!(sql.ends_with(r#"WHERE "state" = $1 AND "priority" > $2"#));
+    assert_eq!(params.len(), 2);
+}
+
+#[test]
+fn test_select_paginate_keeps_order() {
+    let page = SelectJob::select()
+        .filter(SelectJob::STATE.eq("done"))
+        .order_by(SelectJob::PRIORITY, Order::Desc)
+        .paginate(3, 25);
+    assert_eq!(
+        page.sql(),
+        r#"SELECT "id", "state", "priority", COUNT(*) OVER() AS "__total" FROM "select_jobs" WHERE "state" = $1 ORDER BY "priority" DESC, "id" LIMIT $2 OFFSET $3"#
+    );
+}
+
+#[tokio::test]
+async fn test_select_paginate_rejects_from_subquery() {
+    // Never connects: the query is rejected before it runs
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+    let recent = SelectJob::select().order_by(SelectJob::ID, Order::Desc).limit(100);
+    let result = SelectJob::select().from_subquery(recent).paginate(1, 10).fetch(&pool).await;
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("FROM subquery")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "select_accounts"]
+struct SelectAccount {
+    id: String,
+    name: String,
+    #[crud(cast_as = "TEXT")]
+    balance: Option<String>,
+    profile: String,
+}
+
+#[derive(Debug, FromRow, Projection)]
+#[projection(SelectAccount)]
+struct AccountSummary {
+    id: String,
+    balance: Option<String>,
+}
+
+#[test]
+fn test_select_as_narrows_columns() {
+    let (sql, _) = SelectAccount::select_as::<AccountSummary>()
+        .filter(SelectAccount::NAME.like("A%"))
+        .limit(50)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "balance"::TEXT as "balance" FROM "select_accounts" WHERE "name" LIKE $1 LIMIT $2"#
+    );
+}
+
+#[test]
+fn test_select_as_paginate_uses_projected_columns() {
+    let page = SelectAccount::select_as::<AccountSummary>().paginate(1, 10);
+    assert_eq!(
+        page.sql(),
+        r#"SELECT "id", "balance"::TEXT as "balance", COUNT(*) OVER() AS "__total" FROM "select_accounts" WHERE 1=1 ORDER BY "id" LIMIT $1 OFFSET $2"#
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "select_categories"]
+#[crud(tree(parent = "parent_id"))]
+struct SelectCategory {
+    id: String,
+    parent_id: Option<String>,
+    name: String,
+}
+
+#[test]
+fn test_select_from_cte() {
+    let (sql, params) = SelectJob::select()
+        .with("urgent", SelectJob::select().filter(SelectJob::PRIORITY.gt(5)))
+        .from_cte("urgent")
+        .filter(SelectJob::STATE.eq("pending"))
+        .limit(10)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"WITH "urgent" AS (SELECT "id", "state", "priority" FROM "select_jobs" WHERE "priority" > $1)"#,
+            r#" SELECT "id", "state", "priority" FROM "urgent" AS "select_jobs" WHERE "state" = $2 LIMIT $3"#,
+        )
+    );
+    assert_eq!(params.len(), 2);
+}
+
+#[tokio::test]
+async fn test_select_paginate_rejects_cte() {
+    // Never connects: the query is rejected before it runs
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+    let result = SelectJob::select()
+        .with("urgent", SelectJob::select().filter(SelectJob::PRIORITY.gt(5)))
+        .from_cte("urgent")
+        .paginate(1, 10)
+        .fetch(&pool)
+        .await;
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("common table expressions")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tree_descendants() {
+    let (sql, params) = SelectCategory::descendants("root")
+        .order_by(SelectCategory::NAME, Order::Asc)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"WITH RECURSIVE "select_categories_tree" AS (SELECT "id", "parent_id" FROM "select_categories" WHERE "parent_id" = $1"#,
+            r#" UNION SELECT "select_categories"."id", "select_categories"."parent_id" FROM "select_categories""#,
+            r#" JOIN "select_categories_tree" ON "select_categories"."parent_id" = "select_categories_tree"."id")"#,
+            r#" SELECT "id", "parent_id", "name" FROM "select_categories" WHERE "id" IN (SELECT "id" FROM "select_categories_tree")"#,
+            r#" ORDER BY "name" ASC"#,
+        )
+    );
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn test_tree_ancestors() {
+    let (sql, params) = SelectCategory::ancestors(&"leaf".to_string())
+        .filter(SelectCategory::NAME.ne("hidden"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"WITH RECURSIVE "select_categories_tree" AS (SELECT "id", "parent_id" FROM "select_categories""#,
+            r#" WHERE "id" = (SELECT "parent_id" FROM "select_categories" WHERE "id" = $1)"#,
+            r#" UNION SELECT "select_categories"."id", "select_categories"."parent_id" FROM "select_categories""#,
+            r#" JOIN "select_categories_tree" ON "select_categories"."id" = "select_categories_tree"."parent_id")"#,
+            r#" SELECT "id", "parent_id", "name" FROM "select_categories""#,
+            r#" WHERE "id" IN (SELECT "id" FROM "select_categories_tree") AND "name" <> $2"#,
+        )
+    );
+    assert_eq!(params.len(), 2);
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, EnhancedCrud)]
+#[table_name = "select_jobs_archive"]
+struct ArchivedSelectJob {
+    id: i64,
+    state: String,
+    priority: Option<i32>,
+}
+
+#[test]
+fn test_union_all_orders_and_limits_combined_rows() {
+    let (sql, params) = SelectJob::select()
+        .filter(SelectJob::STATE.eq("failed"))
+        .union_all(ArchivedSelectJob::select().filter(ArchivedSelectJob::STATE.eq("failed")))
+        .order_by(SelectJob::ID, Order::Desc)
+        .limit(20)
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"SELECT "id", "state", "priority" FROM "select_jobs" WHERE "state" = $1"#,
+            r#" UNION ALL SELECT "id", "state", "priority" FROM "select_jobs_archive" WHERE "state" = $2"#,
+            r#" ORDER BY "id" DESC LIMIT $3"#,
+        )
+    );
+    assert_eq!(params.len(), 2);
+}
+
+#[test]
+fn test_set_operations_chain() {
+    let (sql, params) = SelectJob::select()
+        .with("urgent", SelectJob::select().filter(SelectJob::PRIORITY.gt(5)))
+        .union(ArchivedSelectJob::select())
+        .except(SelectJob::select().from_cte("urgent"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            r#"WITH "urgent" AS (SELECT "id", "state", "priority" FROM "select_jobs" WHERE "priority" > $1)"#,
+            r#" SELECT "id", "state", "priority" FROM "select_jobs""#,
+            r#" UNION SELECT "id", "state", "priority" FROM "select_jobs_archive""#,
+            r#" EXCEPT SELECT "id", "state", "priority" FROM "urgent" AS "select_jobs""#,
+        )
+    );
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn test_set_operations_require_matching_columns() {
+    let result = SelectJob::select().union(SelectAccount::select()).build();
+    assert!(result.is_err());
+
+    let result = SelectJob::select().union_all(ArchivedSelectJob::select().limit(5)).build();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_set_operations_are_not_paginated() {
+    // Never connects: the query is rejected before it runs
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+    let result = SelectJob::select().union_all(ArchivedSelectJob::select()).paginate(1, 10).fetch(&pool).await;
+    match result {
+        Err(sqlx::Error::Configuration(err)) => assert!(err.to_string().contains("set operations")),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}